@@ -0,0 +1,60 @@
+use std::fmt::Write;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use keep_a_changelog::Changelog;
+
+/// Changelog with `releases` releases, a few entries of each kind and compare links.
+fn changelog(releases: usize) -> String {
+    let mut markdown = String::from(
+        "# Changelog\n\nAll notable changes to this project will be documented in this file.\n",
+    );
+
+    for minor in (0..releases).rev() {
+        writeln!(markdown, "\n## [0.{minor}.0] - 2024-04-28\n").unwrap();
+        for kind in ["Added", "Changed", "Fixed"] {
+            writeln!(markdown, "### {kind}\n").unwrap();
+            for entry in 0..5 {
+                writeln!(
+                    markdown,
+                    "- Entry {entry} of release 0.{minor}.0 with `code` and a [link](https://example.com)"
+                )
+                .unwrap();
+            }
+            markdown.push('\n');
+        }
+    }
+
+    markdown.push('\n');
+    for minor in (1..releases).rev() {
+        writeln!(
+            markdown,
+            "[0.{minor}.0]: https://github.com/owner/repo/compare/v0.{}.0...v0.{minor}.0",
+            minor - 1
+        )
+        .unwrap();
+    }
+    markdown
+}
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+
+    for releases in [10, 100, 5000] {
+        let markdown = changelog(releases);
+        group.throughput(Throughput::Bytes(markdown.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("owned", releases), &markdown, |b, md| {
+            b.iter(|| Changelog::parse(black_box(md.clone()), None).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("borrowed", releases),
+            &markdown,
+            |b, md| b.iter(|| Changelog::parse_borrowed(black_box(md)).unwrap()),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, parse);
+criterion_main!(benches);
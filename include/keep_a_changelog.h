@@ -0,0 +1,68 @@
+#ifndef KEEP_A_CHANGELOG_H
+#define KEEP_A_CHANGELOG_H
+
+/* Generated with cbindgen, do not edit manually. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Represents a change kind.
+//
+// This is used to categorize changes in a changelog.
+typedef struct ChangeKind ChangeKind;
+
+// Opaque changelog handle.
+typedef struct KacChangelog KacChangelog;
+
+// Message of the last error occurred on the current thread, or NULL if there was none.
+//
+// The returned string is valid until the next failed call on the same thread.
+const char *kac_last_error(void);
+
+// Parse changelog from UTF-8 Markdown buffer of `len` bytes.
+//
+// Returns NULL on error, see [`kac_last_error`]. The handle must be released with
+// [`kac_changelog_free`].
+//
+// # Safety
+//
+// `buf` must point to `len` readable bytes.
+KacChangelog *kac_changelog_parse(const uint8_t *buf, size_t len);
+
+// Render changelog as Markdown into `buf` of `len` bytes, NUL-terminated.
+//
+// Returns the length of the rendered Markdown excluding the terminating NUL, as `snprintf`
+// does, so the output was truncated if the returned value is not less than `len`. Pass NULL
+// `buf` to query the required size. Returns -1 on error, see [`kac_last_error`].
+//
+// # Safety
+//
+// `changelog` must be a valid handle and `buf` must point to `len` writable bytes.
+ptrdiff_t kac_changelog_render(const KacChangelog *changelog, char *buf, size_t len);
+
+// Add entry of `kind` (e.g. "added", "fixed") to the release with `version`, or to the
+// Unreleased release if `version` is NULL. The Unreleased release is created if missing.
+//
+// Returns 0 on success and -1 on error, see [`kac_last_error`].
+//
+// # Safety
+//
+// `changelog` must be a valid handle, `kind` and `text` must be valid NUL-terminated strings
+// and `version` must be either NULL or a valid NUL-terminated string.
+int kac_changelog_add_entry(KacChangelog *changelog,
+                            const char *version,
+                            const char *kind,
+                            const char *text);
+
+// Release changelog handle, NULL is ignored.
+//
+// # Safety
+//
+// `changelog` must be NULL or a handle returned by [`kac_changelog_parse`] that is not yet
+// released.
+void kac_changelog_free(KacChangelog *changelog);
+
+#endif  /* KEEP_A_CHANGELOG_H */
@@ -0,0 +1,161 @@
+//! [`Arbitrary`] implementations generating valid changelogs for property testing.
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use semver::Version;
+
+use crate::{
+    changelog::ChangelogBuilder, CalendarDate, ChangeKind, Changelog, Changes, Date, Link, Release,
+};
+
+const WORDS: &[&str] = &[
+    "add", "api", "bug", "cache", "config", "crash", "docs", "error", "fix", "flag", "format",
+    "header", "link", "list", "memory", "output", "parser", "release", "support", "update",
+];
+
+/// Sentence of 1 to `max` words, capitalized.
+fn sentence(u: &mut Unstructured, max: usize) -> Result<String> {
+    let len = u.int_in_range(1..=max)?;
+    let words = (0..len)
+        .map(|_| u.choose(WORDS).copied())
+        .collect::<Result<Vec<_>>>()?;
+
+    let sentence = words.join(" ");
+    let mut chars = sentence.chars();
+    Ok(chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default())
+}
+
+fn date(u: &mut Unstructured) -> Result<Date> {
+    let start = Date::new(2015, 1, 1).unwrap();
+    Ok(start.add_days(u.int_in_range(0..=3650)?).unwrap())
+}
+
+impl<'a> Arbitrary<'a> for Changes {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut changes = Changes::default();
+        for _ in 0..u.int_in_range(0..=8)? {
+            let kind = u.choose(&ChangeKind::ALL)?.clone();
+            changes.add(kind, sentence(u, 8)?);
+        }
+        Ok(changes)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Link {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let anchor = u.choose(WORDS)?;
+        let path = u.choose(WORDS)?;
+        Ok(Link::new(anchor.to_string(), format!("https://example.com/{path}")).unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Release {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let version = Version::new(
+            u.int_in_range(0..=20)?,
+            u.int_in_range(0..=20)?,
+            u.int_in_range(0..=20)?,
+        );
+
+        Ok(Release::builder()
+            .version(version)
+            .date(date(u)?)
+            .yanked(u.ratio(1, 10)?)
+            .changes(Changes::arbitrary(u)?)
+            .build()
+            .unwrap())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Changelog {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut releases = vec![];
+        let mut version = Version::new(0, 1, 0);
+        let mut date = date(u)?;
+
+        for _ in 0..u.int_in_range(0..=10)? {
+            let mut release = Release::arbitrary(u)?;
+            release.set_version(version.clone()).set_date(date);
+            releases.push(release);
+
+            version = match u.int_in_range(0..=2)? {
+                0 => Version::new(version.major + 1, 0, 0),
+                1 => Version::new(version.major, version.minor + 1, 0),
+                _ => Version::new(version.major, version.minor, version.patch + 1),
+            };
+            date = date.add_days(u.int_in_range(0..=90)?).unwrap();
+        }
+
+        if u.arbitrary()? {
+            releases.push(
+                Release::builder()
+                    .changes(Changes::arbitrary(u)?)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        let url = match u.arbitrary()? {
+            true => Some(format!(
+                "https://github.com/{}/{}",
+                u.choose(WORDS)?,
+                u.choose(WORDS)?
+            )),
+            false => None,
+        };
+
+        let links = (0..u.int_in_range(0..=3)?)
+            .map(|_| Link::arbitrary(u).map(|link| link.to_string()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut builder = ChangelogBuilder::default();
+        builder
+            .title(sentence(u, 3)?)
+            .description(format!("{}.", sentence(u, 12)?))
+            .url(url)
+            .releases(releases)
+            .links(links)
+            .unwrap();
+
+        Ok(builder.build().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use super::*;
+    use crate::ChangelogParseOptions;
+
+    #[test]
+    fn test_parse_print_round_trip() -> Result<()> {
+        let mut seed = 0x2545f4914f6cdd1d_u64;
+        let data = (0..64 * 1024)
+            .map(|_| {
+                // xorshift64
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                seed as u8
+            })
+            .collect::<Vec<_>>();
+
+        for chunk in data.chunks(1024) {
+            let changelog = Changelog::arbitrary(&mut Unstructured::new(chunk))?;
+            // the URL can't be inferred from a changelog without compare links
+            let opts = ChangelogParseOptions {
+                url: changelog.url().clone(),
+                ..Default::default()
+            };
+            let parsed = Changelog::parse(changelog.file_contents(), Some(opts))?;
+            assert!(
+                parsed.semantic_eq(&changelog),
+                "Round trip failed:\n{}",
+                changelog.file_contents()
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,155 @@
+//! Views of the changelog for a subset of readers, see [`Changelog::view`].
+use crate::{
+    changes::{split_audiences, ChangeEntry, BREAKING_PREFIX},
+    Changelog, Changes,
+};
+
+/// Entries included in a [`Changelog::view`]
+///
+/// Entries are tagged with audiences by leading bracket tokens, e.g.
+/// `[ops] [api] Retry failed jobs`, see [`ChangeEntry::audiences`].
+#[derive(Debug, Clone, Default)]
+pub struct AudienceFilter {
+    /// Include entries tagged with any of the audiences, compared case-insensitively
+    pub audiences: Vec<String>,
+    /// Include entries without audience tags
+    pub include_untagged: bool,
+    /// Remove the audience tags from the included entries
+    pub strip_tags: bool,
+}
+
+impl AudienceFilter {
+    /// Filter including entries tagged with any of `audiences` and the untagged ones
+    pub fn new<I, S>(audiences: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            audiences: audiences.into_iter().map(Into::into).collect(),
+            include_untagged: true,
+            strip_tags: false,
+        }
+    }
+
+    pub fn matches(&self, entry: &ChangeEntry) -> bool {
+        match entry.audiences().is_empty() {
+            true => self.include_untagged,
+            false => entry.audiences().iter().any(|audience| {
+                self.audiences
+                    .iter()
+                    .any(|wanted| wanted.eq_ignore_ascii_case(audience))
+            }),
+        }
+    }
+}
+
+impl Changelog {
+    /// Copy of the changelog with only the entries matching the `filter`, e.g. the operator
+    /// notes of a release
+    ///
+    /// Sections without matching entries are omitted when rendered, releases are kept.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{AudienceFilter, Changelog};
+    ///
+    /// let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- [api] Batch endpoint\n\
+    ///     - [ops] [api] Rate limit metrics\n- Dark mode\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let filter = AudienceFilter {
+    ///     strip_tags: true,
+    ///     ..AudienceFilter::new(["ops"])
+    /// };
+    /// let ops = changelog.view(filter).to_string();
+    /// assert!(ops.contains("### Added\n- Rate limit metrics\n- Dark mode\n"));
+    /// assert!(!ops.contains("Batch endpoint"));
+    /// ```
+    pub fn view(&self, filter: AudienceFilter) -> Changelog {
+        let mut changelog = self.clone();
+        for release in changelog.releases_mut() {
+            let changes = release
+                .changes()
+                .iter()
+                .map(|(kind, text)| ChangeEntry::new(kind, text.clone()))
+                .filter(|entry| filter.matches(entry))
+                .map(|entry| {
+                    let text = match filter.strip_tags {
+                        true => strip_audiences(entry.text()),
+                        false => entry.text().clone(),
+                    };
+                    (entry.kind().clone(), text)
+                })
+                .collect::<Vec<_>>();
+            release.set_changes(Changes::from(changes));
+        }
+
+        changelog
+    }
+}
+
+/// Entry text without the audience tags, the `**BREAKING**` prefix is kept
+fn strip_audiences(text: &str) -> String {
+    let text = text.trim_start();
+    match text.strip_prefix(BREAKING_PREFIX) {
+        Some(rest) => {
+            let (_, rest) = split_audiences(rest.trim_start());
+            format!("{BREAKING_PREFIX} {rest}")
+        }
+        None => split_audiences(text).1.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::tagged("[ops] Restart workers", "Restart workers")]
+    #[case::many("[ops] [API] Restart workers", "Restart workers")]
+    #[case::breaking("**BREAKING** [api] Drop v1", "**BREAKING** Drop v1")]
+    #[case::link(
+        "[docs](https://example.com) updated",
+        "[docs](https://example.com) updated"
+    )]
+    #[case::task("[x] Done", "[x] Done")]
+    fn test_strip_audiences(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(strip_audiences(text), expected);
+    }
+
+    #[test]
+    fn test_view() -> Result<()> {
+        let markdown = "# Changelog\n\n## [Unreleased]\n\n### Fixed\n\n- [UI] Button contrast\n- **BREAKING** [api] Drop v1 endpoints\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        let filter = AudienceFilter {
+            include_untagged: false,
+            ..AudienceFilter::new(["ui"])
+        };
+        let ui = changelog.view(filter).to_string();
+        assert!(ui.contains("### Fixed\n\n- [UI] Button contrast\n\n## [0.1.0]"));
+        assert!(!ui.contains("### Added"));
+
+        let api = changelog
+            .view(AudienceFilter {
+                strip_tags: true,
+                ..AudienceFilter::new(["API"])
+            })
+            .to_string();
+        assert!(api.contains("### Fixed\n\n- **BREAKING** Drop v1 endpoints\n\n## [0.1.0]"));
+        assert!(api.contains("- Initial release\n"));
+
+        assert_eq!(
+            changelog.to_string(),
+            changelog
+                .view(AudienceFilter::new(["ui", "api"]))
+                .to_string()
+        );
+        Ok(())
+    }
+}
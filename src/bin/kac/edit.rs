@@ -0,0 +1,418 @@
+//! `kac edit`: terminal editor listing the releases and their entries
+//!
+//! Changes are kept in memory until saved with `s`, the file is written with
+//! [`Changelog::save_to_file`].
+use eyre::Result;
+use keep_a_changelog::{
+    CalendarDate, ChangeKind, Changelog, Changes, Date, Release, ReleaseVersion,
+};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    widgets::{Block, List, ListState, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+const HELP: &str =
+    "←/→ release  ↑/↓ entry  a add  e edit  d delete  J/K move  p promote  s save  q quit";
+
+/// Open the editor for the changelog at `path`
+pub fn run(path: &str) -> Result<()> {
+    let changelog = Changelog::parse_from_file(path, None)?;
+    let mut app = App::new(path, changelog);
+
+    let mut terminal = ratatui::init();
+    let result = app.event_loop(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Mode {
+    Normal,
+    /// Typing a new entry, Tab cycles the section
+    Add {
+        kind: ChangeKind,
+        text: String,
+    },
+    /// Typing the new text of the selected entry
+    Edit {
+        text: String,
+    },
+    /// Typing the version the Unreleased changes are promoted to
+    Promote {
+        version: String,
+    },
+}
+
+struct App {
+    path: String,
+    changelog: Changelog,
+    /// Index of the selected release
+    release: usize,
+    /// Index of the selected entry within the release, in render order
+    entry: usize,
+    mode: Mode,
+    status: String,
+    /// Whether there are unsaved changes
+    dirty: bool,
+    quit: bool,
+}
+
+impl App {
+    fn new(path: &str, changelog: Changelog) -> Self {
+        Self {
+            path: path.to_string(),
+            changelog,
+            release: 0,
+            entry: 0,
+            mode: Mode::Normal,
+            status: String::new(),
+            dirty: false,
+            quit: false,
+        }
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key.code);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Entries of the selected release, in render order
+    fn entries(&self) -> Vec<(ChangeKind, String)> {
+        self.changelog
+            .releases()
+            .get(self.release)
+            .map(|release| {
+                release
+                    .changes()
+                    .iter()
+                    .map(|(kind, text)| (kind, text.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn set_entries(&mut self, entries: Vec<(ChangeKind, String)>) {
+        if let Some(release) = self.changelog.releases_mut().get_mut(self.release) {
+            release.set_changes(Changes::from(entries));
+            self.dirty = true;
+        }
+    }
+
+    fn handle_key(&mut self, code: KeyCode) {
+        let mode = std::mem::replace(&mut self.mode, Mode::Normal);
+        self.mode = match mode {
+            Mode::Normal => return self.handle_normal_key(code),
+            Mode::Add { mut kind, mut text } => match code {
+                KeyCode::Enter => return self.add_entry(kind, text),
+                KeyCode::Tab => {
+                    let idx = ChangeKind::ALL.iter().position(|k| *k == kind);
+                    kind = ChangeKind::ALL[idx.map_or(0, |idx| (idx + 1) % ChangeKind::ALL.len())]
+                        .clone();
+                    Mode::Add { kind, text }
+                }
+                code => match edit_input(&mut text, code) {
+                    true => Mode::Add { kind, text },
+                    false => Mode::Normal,
+                },
+            },
+            Mode::Edit { mut text } => match code {
+                KeyCode::Enter => return self.edit_entry(text),
+                code => match edit_input(&mut text, code) {
+                    true => Mode::Edit { text },
+                    false => Mode::Normal,
+                },
+            },
+            Mode::Promote { mut version } => match code {
+                KeyCode::Enter => return self.promote(&version),
+                code => match edit_input(&mut version, code) {
+                    true => Mode::Promote { version },
+                    false => Mode::Normal,
+                },
+            },
+        };
+    }
+
+    fn handle_normal_key(&mut self, code: KeyCode) {
+        let confirm_quit = std::mem::take(&mut self.status) == UNSAVED;
+        let entries = self.entries();
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                if self.dirty && !confirm_quit {
+                    self.status = UNSAVED.to_string();
+                } else {
+                    self.quit = true;
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.release = self.release.saturating_sub(1);
+                self.entry = 0;
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                let len = self.changelog.releases().len();
+                self.release = (self.release + 1).min(len.saturating_sub(1));
+                self.entry = 0;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.entry = self.entry.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.entry = (self.entry + 1).min(entries.len().saturating_sub(1));
+            }
+            KeyCode::Char('a') => {
+                let kind = entries
+                    .get(self.entry)
+                    .map_or(ChangeKind::Added, |(kind, _)| kind.clone());
+                self.mode = Mode::Add {
+                    kind,
+                    text: String::new(),
+                };
+            }
+            KeyCode::Char('e') => {
+                if let Some((_, text)) = entries.get(self.entry) {
+                    self.mode = Mode::Edit { text: text.clone() };
+                }
+            }
+            KeyCode::Char('d') if self.entry < entries.len() => {
+                let mut entries = entries;
+                entries.remove(self.entry);
+                self.entry = self.entry.min(entries.len().saturating_sub(1));
+                self.set_entries(entries);
+            }
+            KeyCode::Char('K') => self.move_entry(entries, -1),
+            KeyCode::Char('J') => self.move_entry(entries, 1),
+            KeyCode::Char('p') => {
+                if self.changelog.get_unreleased().is_none() {
+                    self.status = "Missing Unreleased release".to_string();
+                    return;
+                }
+                let version = self
+                    .changelog
+                    .suggest_next_version()
+                    .map(|version| version.to_string())
+                    .unwrap_or_default();
+                self.mode = Mode::Promote { version };
+            }
+            KeyCode::Char('s') => match self.changelog.save_to_file(&self.path) {
+                Ok(()) => {
+                    self.dirty = false;
+                    self.status = format!("Saved {}", self.path);
+                }
+                Err(e) => self.status = format!("Failed to save: {e}"),
+            },
+            _ => {}
+        }
+    }
+
+    fn add_entry(&mut self, kind: ChangeKind, text: String) {
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+
+        let mut entries = self.entries();
+        entries.push((kind.clone(), text));
+        self.set_entries(entries);
+
+        // Entries are regrouped by section, the new one is the last of its section
+        self.entry = self
+            .entries()
+            .iter()
+            .rposition(|(k, _)| *k == kind)
+            .unwrap_or_default();
+    }
+
+    fn edit_entry(&mut self, text: String) {
+        let text = text.trim().to_string();
+        let mut entries = self.entries();
+        match entries.get_mut(self.entry) {
+            Some(entry) if !text.is_empty() => entry.1 = text,
+            _ => return,
+        }
+        self.set_entries(entries);
+    }
+
+    /// Swap the selected entry with its neighbour in the same section
+    fn move_entry(&mut self, mut entries: Vec<(ChangeKind, String)>, offset: isize) {
+        let Some(target) = self.entry.checked_add_signed(offset) else {
+            return;
+        };
+        if target >= entries.len() || entries[target].0 != entries[self.entry].0 {
+            return;
+        }
+
+        entries.swap(self.entry, target);
+        self.entry = target;
+        self.set_entries(entries);
+    }
+
+    fn promote(&mut self, version: &str) {
+        let result = ReleaseVersion::parse(version.trim()).and_then(|version| {
+            self.changelog
+                .promote_unreleased(version, Date::today())
+                .map(|_| ())
+        });
+
+        match result {
+            Ok(()) => {
+                self.dirty = true;
+                self.status = format!("Promoted Unreleased to {}", version.trim());
+                self.entry = 0;
+            }
+            Err(e) => self.status = format!("Failed to promote: {e}"),
+        }
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main, status] =
+            Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [releases, entries] =
+            Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+                .areas(main);
+
+        let highlight = Style::default().add_modifier(Modifier::REVERSED);
+
+        let titles = self
+            .changelog
+            .releases()
+            .iter()
+            .map(release_title)
+            .collect::<Vec<_>>();
+        let mut state = ListState::default().with_selected(Some(self.release));
+        frame.render_stateful_widget(
+            List::new(titles)
+                .block(Block::bordered().title(self.path.as_str()))
+                .highlight_style(highlight),
+            releases,
+            &mut state,
+        );
+
+        let items = self
+            .entries()
+            .into_iter()
+            .map(|(kind, text)| {
+                let text = text.lines().next().unwrap_or_default().to_string();
+                format!("{:<16} {text}", kind.heading())
+            })
+            .collect::<Vec<_>>();
+        let mut state = ListState::default().with_selected(Some(self.entry));
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::bordered().title("Entries"))
+                .highlight_style(highlight),
+            entries,
+            &mut state,
+        );
+
+        let line = match &self.mode {
+            Mode::Normal if self.status.is_empty() => HELP.to_string(),
+            Mode::Normal => self.status.clone(),
+            Mode::Add { kind, text } => format!("Add to {kind} (Tab: section): {text}_"),
+            Mode::Edit { text } => format!("Edit: {text}_"),
+            Mode::Promote { version } => format!("Promote Unreleased to version: {version}_"),
+        };
+        frame.render_widget(Paragraph::new(line), status);
+    }
+}
+
+/// Release heading text, e.g. `1.0.0 - 2024-06-01 [YANKED]` or `Unreleased`
+fn release_title(release: &Release) -> String {
+    let mut title = release
+        .version()
+        .as_ref()
+        .map_or("Unreleased".to_string(), |version| version.to_string());
+    if let Some(date) = release.date() {
+        title.push_str(&format!(" - {date}"));
+    }
+    if *release.yanked() {
+        title.push_str(" [YANKED]");
+    }
+    title
+}
+
+const UNSAVED: &str = "Unsaved changes, press q again to quit or s to save";
+
+/// Apply the key to the input text, returns false if the input is cancelled
+fn edit_input(text: &mut String, code: KeyCode) -> bool {
+    match code {
+        KeyCode::Esc => return false,
+        KeyCode::Backspace => {
+            text.pop();
+        }
+        KeyCode::Char(c) => text.push(c),
+        _ => {}
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use keep_a_changelog::ChangelogParseOptions;
+
+    use super::*;
+
+    fn keys(app: &mut App, codes: &str) {
+        codes.chars().for_each(|c| app.handle_key(KeyCode::Char(c)));
+    }
+
+    #[test]
+    fn test_edit_keys() -> Result<()> {
+        let markdown = "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- First\n- Second\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n";
+        let changelog =
+            Changelog::parse(markdown.to_string(), Some(ChangelogParseOptions::default()))?;
+        let mut app = App::new("CHANGELOG.md", changelog);
+
+        keys(&mut app, "jK");
+        assert_eq!(app.entry, 0);
+        assert_eq!(app.entries()[0].1, "Second");
+
+        keys(&mut app, "a");
+        app.handle_key(KeyCode::Tab);
+        keys(&mut app, "Typo");
+        app.handle_key(KeyCode::Tab);
+        app.handle_key(KeyCode::Tab);
+        app.handle_key(KeyCode::Tab);
+        app.handle_key(KeyCode::Enter);
+        assert_eq!(app.entries()[2], (ChangeKind::Fixed, "Typo".to_string()));
+        assert_eq!(app.entry, 2);
+
+        keys(&mut app, "e");
+        app.handle_key(KeyCode::Backspace);
+        keys(&mut app, "os");
+        app.handle_key(KeyCode::Enter);
+        keys(&mut app, "kd");
+        assert_eq!(
+            app.entries(),
+            [
+                (ChangeKind::Added, "Second".to_string()),
+                (ChangeKind::Fixed, "Typos".to_string()),
+            ]
+        );
+
+        keys(&mut app, "p");
+        assert_eq!(
+            app.mode,
+            Mode::Promote {
+                version: "0.2.0".to_string()
+            }
+        );
+        app.handle_key(KeyCode::Enter);
+        assert!(app.changelog.get_unreleased().unwrap().changes().is_empty());
+        let promoted = app.changelog.find_release("0.2.0".to_string())?.unwrap();
+        assert_eq!(promoted.changes().iter().count(), 2);
+
+        keys(&mut app, "q");
+        assert!(!app.quit);
+        keys(&mut app, "q");
+        assert!(app.quit);
+        Ok(())
+    }
+}
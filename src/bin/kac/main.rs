@@ -0,0 +1,27 @@
+//! `kac` command line tool, `kac edit [CHANGELOG.md]` opens the terminal editor.
+use std::process::ExitCode;
+
+mod edit;
+
+const USAGE: &str = "Usage: kac edit [PATH]\n\nEdit the changelog at PATH, CHANGELOG.md by default";
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["edit"] => edit::run("CHANGELOG.md"),
+        ["edit", path] => edit::run(path),
+        _ => {
+            eprintln!("{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {e:?}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,479 @@
+//! Zero-copy parsing into types borrowing from the CHANGELOG.md source.
+use std::{borrow::Cow, str::FromStr};
+
+use eyre::Result;
+
+use crate::{
+    consts::CONTRIBUTORS_HEADING,
+    error::ParseError,
+    link::parse_definition,
+    parser::{
+        compare_head, compare_repo_url, is_release_flag, parse_lint, parse_toc, text_content,
+        HeadingRegexes,
+    },
+    placeholder::Marker,
+    token::{tokenize, Token, TokenKind},
+    ChangeKind, Changelog, Changes, Link, Release, ReleaseDate, ReleaseVersion,
+};
+
+/// Changelog borrowing its texts from the parsed source, see [`Changelog::parse_borrowed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangelogRef<'a> {
+    /// `markdownlint-disable` comment, e.g. `markdownlint-disable MD022 MD032`
+    pub lint: Option<Cow<'a, str>>,
+    /// Comment flag above the title
+    pub flag: Option<Cow<'a, str>>,
+    pub title: Option<Cow<'a, str>>,
+    pub description: Option<Cow<'a, str>>,
+    /// Repository URL extracted from the compare links
+    pub url: Option<Cow<'a, str>>,
+    /// Git HEAD reference extracted from the Unreleased compare link
+    pub head: Option<Cow<'a, str>>,
+    pub releases: Vec<ReleaseRef<'a>>,
+    pub links: Vec<LinkRef<'a>>,
+    pub footer: Option<Cow<'a, str>>,
+    /// Whether blank lines after headings are omitted
+    pub compact: bool,
+}
+
+/// Release borrowing its texts from the parsed source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseRef<'a> {
+    pub version: Option<ReleaseVersion>,
+    pub date: Option<ReleaseDate>,
+    pub yanked: bool,
+    pub description: Option<Cow<'a, str>>,
+    /// Changes in the order of the source, entries with indented continuation lines are owned
+    pub changes: Vec<(ChangeKind, Cow<'a, str>)>,
+}
+
+/// Link definition borrowing from the parsed source, definitions spanning multiple lines are
+/// owned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkRef<'a> {
+    pub anchor: Cow<'a, str>,
+    pub url: Cow<'a, str>,
+    pub title: Option<Cow<'a, str>>,
+}
+
+impl Changelog {
+    /// Parse CHANGELOG.md without copying the texts
+    ///
+    /// Suited for analyzing many changelogs, the result could be converted with
+    /// [`ChangelogRef::to_changelog`]. The source is tokenized the same way as by
+    /// [`Changelog::parse`], release flags, markers and the table of contents are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::borrow::Cow;
+    ///
+    /// use keep_a_changelog::{ChangeKind, Changelog};
+    ///
+    /// let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse_borrowed(markdown).unwrap();
+    ///
+    /// assert_eq!(changelog.title.as_deref(), Some("Changelog"));
+    /// let (kind, entry) = &changelog.releases[0].changes[0];
+    /// assert_eq!(*kind, ChangeKind::Added);
+    /// assert!(matches!(entry, Cow::Borrowed("Initial release")));
+    /// ```
+    pub fn parse_borrowed(markdown: &str) -> Result<ChangelogRef<'_>> {
+        BorrowedParser::new(markdown)?.parse()
+    }
+}
+
+impl ChangelogRef<'_> {
+    /// Convert into an owned [`Changelog`].
+    pub fn to_changelog(&self) -> Result<Changelog> {
+        let releases = self
+            .releases
+            .iter()
+            .map(|release| {
+                let mut changes = Changes::default();
+                for (kind, change) in &release.changes {
+                    changes.add(kind.clone(), change.to_string());
+                }
+
+                let mut builder = Release::builder();
+                builder
+                    .yanked(release.yanked)
+                    .description(release.description.as_ref().map(|d| d.to_string()))
+                    .changes(changes);
+                if let Some(version) = &release.version {
+                    builder.version(version.clone());
+                }
+                if let Some(date) = release.date {
+                    builder.release_date(date);
+                }
+                Ok(builder.build()?)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let links = self
+            .links
+            .iter()
+            .map(|link| Link {
+                anchor: link.anchor.to_string(),
+                url: link.url.to_string(),
+                title: link.title.as_ref().map(|title| title.to_string()),
+                span: Default::default(),
+            })
+            .collect();
+
+        let mut builder = Changelog::builder();
+        builder
+            .lint(self.lint.as_deref().map(parse_lint).transpose()?)
+            .flag(self.flag.as_ref().map(|f| f.to_string()))
+            .title(self.title.as_ref().map(|t| t.to_string()))
+            .description(self.description.as_ref().map(|d| d.to_string()))
+            .footer(self.footer.as_ref().map(|f| f.to_string()))
+            .compact(self.compact)
+            .releases(releases)
+            .parsed_links(links);
+        if let Some(url) = &self.url {
+            builder.url(Some(url.to_string()));
+        }
+        if let Some(head) = &self.head {
+            builder.head(head.to_string());
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+struct BorrowedParser<'a> {
+    source: &'a str,
+    tokens: Vec<Token<'a>>,
+    links: Vec<Token<'a>>,
+    compact: bool,
+    regexes: HeadingRegexes,
+    url: Option<Cow<'a, str>>,
+    head: Option<Cow<'a, str>>,
+    idx: usize,
+}
+
+impl<'a> BorrowedParser<'a> {
+    fn new(source: &'a str) -> Result<Self> {
+        let (compact, tokens) = tokenize(source)?;
+        let (links, tokens) = tokens.into_iter().partition(|t| t.kind == TokenKind::Link);
+
+        Ok(Self {
+            source,
+            tokens,
+            links,
+            compact,
+            regexes: HeadingRegexes::new(false)?,
+            url: None,
+            head: None,
+            idx: 0,
+        })
+    }
+
+    fn parse(mut self) -> Result<ChangelogRef<'a>> {
+        let lint = self.next(TokenKind::Lint);
+        let flag = self.next(TokenKind::Flag);
+        let title = self.next(TokenKind::H1);
+        let description = self.text();
+
+        if let Some((len, _)) = parse_toc(&self.tokens[self.idx..]) {
+            self.idx += len;
+        }
+        self.skip_markers();
+
+        let mut releases = vec![];
+        while let Some(heading) = self.next(TokenKind::H2) {
+            if let Some(release) = self.release(&heading)? {
+                releases.push(release);
+            }
+            self.skip_markers();
+        }
+
+        let footer = self.next(TokenKind::Hr);
+        if let Some(token) = self.tokens.get(self.idx) {
+            return Err(ParseError::UnexpectedContent {
+                line: token.line,
+                content: token.content.join("\n"),
+            }
+            .into());
+        }
+
+        let links = std::mem::take(&mut self.links)
+            .into_iter()
+            .map(|token| self.link(token))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(ChangelogRef {
+            lint,
+            flag,
+            title,
+            description,
+            url: self.url,
+            head: self.head,
+            releases,
+            links,
+            footer,
+            compact: self.compact,
+        })
+    }
+
+    fn release(&mut self, heading: &Cow<'a, str>) -> Result<Option<ReleaseRef<'a>>> {
+        let line = self.tokens[self.idx - 1].line;
+        if self.regexes.year.is_match(heading.trim()) {
+            return Ok(None);
+        }
+
+        let mut heading = heading.to_string();
+        if let Some(captures) = self.regexes.inline_link.captures(heading.trim()) {
+            if let Some(url) = compare_repo_url(&captures[2]) {
+                self.url = Some(Cow::Owned(url.to_string()));
+            }
+            if captures[1].eq_ignore_ascii_case("unreleased") {
+                if let Some(head) = compare_head(&captures[2]) {
+                    self.head = Some(Cow::Owned(head.to_string()));
+                }
+            }
+            heading = self
+                .regexes
+                .inline_link
+                .replace(heading.trim(), "[$1]")
+                .to_string();
+        }
+
+        let heading_lc = heading.to_lowercase();
+        let mut release = ReleaseRef {
+            version: None,
+            date: None,
+            yanked: heading_lc.contains("[yanked]"),
+            description: None,
+            changes: vec![],
+        };
+
+        if let Some(captures) = self.regexes.release.captures(&heading_lc) {
+            let version = captures[1].trim();
+            release.version =
+                Some(
+                    ReleaseVersion::parse(version).map_err(|_| ParseError::InvalidVersion {
+                        line,
+                        version: version.to_string(),
+                    })?,
+                );
+
+            let date = captures[2].trim();
+            release.date = match date.parse() {
+                Ok(date @ (ReleaseDate::Day(_) | ReleaseDate::Month(..))) => Some(date),
+                _ => Err(ParseError::InvalidDate {
+                    line,
+                    date: date.to_string(),
+                })?,
+            };
+        } else if heading_lc.contains("unreleased") || self.regexes.unreleased.is_match(&heading_lc)
+        {
+            if let Some(captures) = self.regexes.unreleased.captures(&heading_lc) {
+                let version = captures[1].trim();
+                release.version = Some(ReleaseVersion::parse(version).map_err(|_| {
+                    ParseError::InvalidVersion {
+                        line,
+                        version: version.to_string(),
+                    }
+                })?);
+            }
+        } else {
+            Err(ParseError::InvalidRelease { line, heading })?;
+        }
+
+        while self.tokens.get(self.idx).is_some_and(|token| {
+            token.kind == TokenKind::Flag && is_release_flag(&token.content.join("\n"))
+        }) {
+            self.idx += 1;
+        }
+
+        release.description = self.text();
+
+        while let Some(heading) = self.next(TokenKind::H3) {
+            let kind = match heading.trim().eq_ignore_ascii_case(CONTRIBUTORS_HEADING) {
+                true => None,
+                false => Some(ChangeKind::from_str(&heading)?),
+            };
+
+            while let Some(entry) = self.next(TokenKind::Li) {
+                if let Some(kind) = &kind {
+                    release.changes.push((kind.clone(), entry));
+                }
+            }
+        }
+
+        Ok(Some(release))
+    }
+
+    fn link(&mut self, token: Token<'a>) -> Result<LinkRef<'a>> {
+        let link = match join(self.source, &token.content) {
+            Cow::Borrowed(line) => {
+                let (anchor, url, title) = parse_definition(line)?;
+                LinkRef {
+                    anchor: Cow::Borrowed(anchor),
+                    url: Cow::Borrowed(url),
+                    title,
+                }
+            }
+            Cow::Owned(line) => {
+                let (anchor, url, title) = parse_definition(&line)?;
+                LinkRef {
+                    anchor: Cow::Owned(anchor.to_string()),
+                    url: Cow::Owned(url.to_string()),
+                    title: title.map(|title| Cow::Owned(title.into_owned())),
+                }
+            }
+        };
+
+        if let Some(url) = map_borrowed(&link.url, compare_repo_url) {
+            self.url = Some(url);
+        }
+        if link.anchor.eq_ignore_ascii_case("unreleased") {
+            if let Some(head) = map_borrowed(&link.url, compare_head) {
+                self.head = Some(head);
+            }
+        }
+
+        Ok(link)
+    }
+
+    /// Content of the next token if it's of the `kind`
+    fn next(&mut self, kind: TokenKind) -> Option<Cow<'a, str>> {
+        let token = self.tokens.get(self.idx).filter(|t| t.kind == kind)?;
+        self.idx += 1;
+        Some(join(self.source, &token.content))
+    }
+
+    /// Paragraphs and list items until the next heading, borrowed if it's a single paragraph
+    fn text(&mut self) -> Option<Cow<'a, str>> {
+        let start = self.idx;
+        while self
+            .tokens
+            .get(self.idx)
+            .is_some_and(|token| [TokenKind::P, TokenKind::Li].contains(&token.kind))
+        {
+            self.idx += 1;
+        }
+
+        match &self.tokens[start..self.idx] {
+            [] => None,
+            [token] if token.kind == TokenKind::P => Some(join(self.source, &token.content)),
+            tokens => Some(Cow::Owned(text_content(tokens))),
+        }
+    }
+
+    /// Skip the release automation markers, e.g. `<!-- next-header -->`
+    fn skip_markers(&mut self) {
+        while self.tokens.get(self.idx).is_some_and(|token| {
+            token.kind == TokenKind::Flag
+                && Marker::from_comment(&token.content.join("\n")).is_some()
+        }) {
+            self.idx += 1;
+        }
+    }
+}
+
+/// Lines of a token joined by newlines, borrowed if they're consecutive lines of the `source`
+fn join<'a>(source: &'a str, lines: &[Cow<'a, str>]) -> Cow<'a, str> {
+    if let [line] = lines {
+        return line.clone();
+    }
+
+    let borrowed = || {
+        let (Cow::Borrowed(first), Cow::Borrowed(last)) = (lines.first()?, lines.last()?) else {
+            return None;
+        };
+        let start = offset_in(source, first)?;
+        let end = offset_in(source, last)? + last.len();
+        let text = source.get(start..end)?;
+        text.split('\n')
+            .eq(lines.iter().map(|line| line.as_ref()))
+            .then_some(text)
+    };
+
+    match borrowed() {
+        Some(text) => Cow::Borrowed(text),
+        None => Cow::Owned(lines.join("\n")),
+    }
+}
+
+/// Byte offset of the `text` borrowed from the `source`
+fn offset_in(source: &str, text: &str) -> Option<usize> {
+    let offset = (text.as_ptr() as usize).checked_sub(source.as_ptr() as usize)?;
+    (offset + text.len() <= source.len()).then_some(offset)
+}
+
+/// Part of the text found by `f`, borrowed if the text is
+fn map_borrowed<'a>(text: &Cow<'a, str>, f: fn(&str) -> Option<&str>) -> Option<Cow<'a, str>> {
+    match text {
+        Cow::Borrowed(text) => f(text).map(Cow::Borrowed),
+        Cow::Owned(text) => f(text).map(|part| Cow::Owned(part.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::model::v1;
+
+    #[rstest]
+    #[case("tests/data/cargo_release_changelog.md")]
+    #[case("tests/data/default_changelog.md")]
+    #[case("tests/data/default_changelog_compact.md")]
+    #[case("tests/data/default_changelog_with_unreleased.md")]
+    #[case("tests/data/default_changelog_with_unreleased_compact.md")]
+    #[case("tests/data/early_changelog.md")]
+    #[case("tests/data/early_changelog_compact.md")]
+    #[case("tests/data/early_changelog_multiple_sections.md")]
+    #[case("tests/data/early_changelog_multiple_sections_compact.md")]
+    #[case("tests/data/initial_changelog_unreleased.md")]
+    #[case("tests/data/initial_changelog_unreleased_compact.md")]
+    #[case("tests/data/year_grouped_changelog.md")]
+    fn test_parse_borrowed_matches_parse(#[case] path: &str) -> Result<()> {
+        let markdown = std::fs::read_to_string(path)?;
+        let owned = Changelog::parse(markdown.clone(), None)?;
+        let borrowed = Changelog::parse_borrowed(&markdown)?;
+
+        assert_eq!(
+            v1::Changelog::from(&borrowed.to_changelog()?),
+            v1::Changelog::from(&owned)
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::closing_hashes("# Changelog #\n## [1.0.0] - 2024-01-01 ##\n### Added ###\n- Feature\n")]
+    #[case::month_date("# Changelog\n## [0.3.0] - 2019-02\n### Added\n- Feature\n")]
+    #[case::tbd("# Changelog\n## [1.3.0] - TBD\n### Added\n- Planned\n")]
+    #[case::setext("Changelog\n=========\n\n[1.0.0] - 2024-01-01\n--------------------\n### Added\n- Feature\n")]
+    #[case::continuation(
+        "# Changelog\n## [1.0.0] - 2024-01-01\n### Added\n-   Feature\n    continued\n"
+    )]
+    fn test_parse_borrowed_headings(#[case] markdown: &str) -> Result<()> {
+        let owned = Changelog::parse(markdown.to_string(), None)?;
+        let borrowed = Changelog::parse_borrowed(markdown)?;
+
+        assert_eq!(borrowed.releases.len(), 1);
+        assert_eq!(
+            v1::Changelog::from(&borrowed.to_changelog()?),
+            v1::Changelog::from(&owned)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_borrowed_borrows() -> Result<()> {
+        let markdown = "# Changelog\nFirst line\nsecond line\n\n## [1.0.0] - 2024-01-01\n### Added\n- Feature\n\n[1.0.0]: https://github.com/owner/repo/releases/tag/1.0.0\n";
+        let changelog = Changelog::parse_borrowed(markdown)?;
+
+        assert!(matches!(
+            changelog.description,
+            Some(Cow::Borrowed("First line\nsecond line"))
+        ));
+        assert!(matches!(changelog.links[0].url, Cow::Borrowed(_)));
+        Ok(())
+    }
+}
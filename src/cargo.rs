@@ -0,0 +1,159 @@
+use std::{fs, path::Path};
+
+use eyre::{bail, eyre, Context, Result};
+use semver::Version;
+use toml::{Table, Value};
+
+use crate::{Changelog, ChangelogParseOptions};
+
+impl ChangelogParseOptions {
+    /// Read parse options from the `Cargo.toml` manifest at `path`
+    ///
+    /// The `url` is the `repository` of the package, inherited from the workspace if
+    /// `repository.workspace = true` is set.
+    pub fn from_cargo_manifest<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let url = package_field(path.as_ref(), "repository")?.map(|url| {
+            url.trim_end_matches('/')
+                .trim_end_matches(".git")
+                .to_string()
+        });
+
+        Ok(Self {
+            url,
+            ..Default::default()
+        })
+    }
+}
+
+impl Changelog {
+    /// Check that the latest release matches the crate version of the `Cargo.toml` manifest at
+    /// `path`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::fs;
+    ///
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let dir = std::env::temp_dir().join(format!("keep-a-changelog-{}", std::process::id()));
+    /// fs::create_dir_all(&dir).unwrap();
+    /// let manifest = dir.join("Cargo.toml");
+    /// fs::write(&manifest, "[package]\nname = \"demo\"\nversion = \"1.0.0\"\n").unwrap();
+    ///
+    /// let markdown = "# Changelog\n## [1.0.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    /// assert!(changelog.verify_against_manifest(&manifest).is_ok());
+    ///
+    /// fs::remove_dir_all(dir).unwrap();
+    /// ```
+    pub fn verify_against_manifest<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let version = package_field(path, "version")?
+            .ok_or_else(|| eyre!("Manifest has no package version: {}", path.display()))?;
+        let version = Version::parse(&version)
+            .wrap_err_with(|| format!("Invalid package version: {version}"))?;
+
+        let latest = self
+            .releases()
+            .iter()
+            .find_map(|release| release.version().as_ref())
+            .ok_or_else(|| eyre!("Changelog has no releases"))?;
+
+        if latest.semver() != Some(&version) {
+            bail!("Latest release {latest} does not match crate version {version}");
+        }
+
+        Ok(())
+    }
+}
+
+fn read_manifest(path: &Path) -> Result<Table> {
+    let manifest = fs::read_to_string(path)
+        .wrap_err_with(|| format!("Failed to read manifest: {}", path.display()))?;
+    manifest
+        .parse::<Table>()
+        .wrap_err_with(|| format!("Failed to parse manifest: {}", path.display()))
+}
+
+/// Read string field of the `[package]` table, resolving workspace inheritance.
+fn package_field(path: &Path, key: &str) -> Result<Option<String>> {
+    let manifest = read_manifest(path)?;
+    let Some(value) = manifest.get("package").and_then(|p| p.get(key)) else {
+        return Ok(None);
+    };
+
+    if let Value::String(value) = value {
+        return Ok(Some(value.clone()));
+    }
+
+    if value.get("workspace").and_then(Value::as_bool) != Some(true) {
+        bail!("Invalid package {key} in manifest: {}", path.display());
+    }
+
+    let path = path.canonicalize()?;
+    for dir in path.ancestors().skip(2) {
+        let workspace = dir.join("Cargo.toml");
+        if !workspace.is_file() {
+            continue;
+        }
+
+        let manifest = read_manifest(&workspace)?;
+        let Some(workspace) = manifest.get("workspace") else {
+            continue;
+        };
+
+        return Ok(workspace
+            .get("package")
+            .and_then(|p| p.get(key))
+            .and_then(Value::as_str)
+            .map(|value| value.to_string()));
+    }
+
+    bail!("Workspace manifest not found for: {}", path.display())
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_from_cargo_manifest() -> Result<()> {
+        let opts = ChangelogParseOptions::from_cargo_manifest("Cargo.toml")?;
+        assert_eq!(
+            opts.url.as_deref(),
+            Some("https://github.com/napalmpapalam/keep-a-changelog-rs")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_workspace_inheritance() -> Result<()> {
+        let dir = format!("tests/tmp/cargo_{}", Uuid::new_v4());
+        fs::create_dir_all(format!("{dir}/member"))?;
+        fs::write(
+            format!("{dir}/Cargo.toml"),
+            "[workspace]\nmembers = [\"member\"]\n\n[workspace.package]\nversion = \"0.2.0\"\nrepository = \"https://github.com/owner/repo.git\"\n",
+        )?;
+        fs::write(
+            format!("{dir}/member/Cargo.toml"),
+            "[package]\nname = \"member\"\nversion.workspace = true\nrepository.workspace = true\n",
+        )?;
+        let manifest = format!("{dir}/member/Cargo.toml");
+
+        let opts = ChangelogParseOptions::from_cargo_manifest(&manifest)?;
+        assert_eq!(opts.url.as_deref(), Some("https://github.com/owner/repo"));
+
+        let changelog = Changelog::parse_from_file("tests/data/early_changelog.md", None)?;
+        let err = changelog.verify_against_manifest(&manifest).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Latest release 0.1.2 does not match crate version 0.2.0"
+        );
+
+        fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
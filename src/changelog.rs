@@ -1,27 +1,51 @@
 use std::{
+    borrow::Cow,
     collections::HashSet,
     fmt::{self, Display},
-    fs::{File, OpenOptions},
-    io::{Read, Write},
-    path::Path,
+    fs::{self, File, OpenOptions},
+    io::{ErrorKind, Read, Write},
+    ops::RangeBounds,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use derive_builder::Builder;
 use derive_getters::Getters;
-use eyre::{Context, OptionExt, Result};
-use regex::Regex;
-use semver::Version;
+use eyre::{bail, Context, OptionExt, Result};
+use semver::{Version, VersionReq};
 
 use crate::{
-    consts::{CHANGELOG_DESCRIPTION, CHANGELOG_TITLE},
-    link::Link,
+    changes::{ChangeKind, Changes},
+    conflict::{resolve_conflicts, ConflictSide},
+    consts::{
+        CHANGELOG_DESCRIPTION, CHANGELOG_TITLE, COMPARE_BASE_FLAG_PREFIX, DRAFT_FLAG,
+        METADATA_FLAG_PREFIX,
+    },
+    entry_template::TemplateRegistry,
+    error::{BuildError, BuildProblem, ParseError},
+    event::{ChangelogEvent, Observers},
+    format::FormatOptions,
+    include::{self, Include},
+    link::{upsert_link, Link},
     parser::Parser,
+    patch,
+    placeholder::{Marker, MarkerAnchor, UnreleasedTemplate, RELEASE_DATE_SUFFIX},
     release::Release,
-    utils::{get_compare_url, get_release_url},
+    span::Unspanned,
+    tag::{SharedResolver, TagNameResolver},
+    token::split_documents,
+    utils::{
+        fmt_pairs, get_compare_url, get_file_url, get_release_url, normalize_repo_url, DisplayFn,
+    },
+    version::{CalVerScheme, ReleaseVersion},
+    CalendarDate, Date,
 };
 
 #[derive(Debug, Clone, Builder, Getters)]
-#[builder(derive(Debug))]
+#[builder(
+    derive(Debug),
+    build_fn(validate = "Self::validate", error = "BuildError")
+)]
 pub struct Changelog {
     #[builder(setter(into), default)]
     lint: Option<HashSet<String>>,
@@ -43,8 +67,9 @@ pub struct Changelog {
     #[builder(setter(into), default)]
     footer: Option<String>,
     /// Repository URL, used for generating release and compare links, required for compare links,
-    /// could be extracted from the CHANGELOG.md file if the links are present
-    #[builder(setter(into), default)]
+    /// could be extracted from the CHANGELOG.md file if the links are present. Must be an http(s)
+    /// URL, trailing slashes are removed
+    #[builder(setter(into), field(build = "self.normalized_url()"))]
     url: Option<String>,
     /// Releases
     #[builder(setter(custom), public, default)]
@@ -56,12 +81,47 @@ pub struct Changelog {
     /// used to add a prefix to the version number, for example, "v"
     #[builder(setter(into), default)]
     tag_prefix: Option<String>,
+    /// Tag name resolver, takes precedence over `tag_prefix`, see
+    /// [`Changelog::set_tag_name_resolver`]
+    #[getter(skip)]
+    #[builder(setter(custom), default)]
+    tag_name_resolver: Option<SharedResolver>,
     /// Allow compact output, default is false.
     ///
     /// Compact output removes blank lines after headers and lists and inserts a flag to disable
     /// checking for these lines by markdownlint.
     #[builder(setter(custom), default = "false")]
     compact: bool,
+    /// Rendering options
+    #[builder(default)]
+    format: FormatOptions,
+    /// Release automation markers, e.g. `<!-- next-header -->`, with the blocks they follow,
+    /// preserved on output
+    #[builder(setter(into), default)]
+    markers: Vec<(Marker, MarkerAnchor)>,
+    /// Release dates normalized to ISO 8601 while parsing with
+    /// [`ChangelogParseOptions::lenient_dates`]
+    #[builder(setter(into), default)]
+    normalized_dates: Vec<NormalizedDate>,
+    /// `<!-- include: path -->` directives, preserved on output
+    #[builder(setter(into), default)]
+    pub(crate) includes: Vec<Include>,
+    /// Formats the entries must match, see [`Changelog::set_templates`]
+    #[builder(default)]
+    pub(crate) templates: TemplateRegistry,
+    /// Placeholder entries of a fresh Unreleased release, see
+    /// [`Changelog::set_unreleased_template`]
+    #[builder(default)]
+    pub(crate) unreleased_template: Option<UnreleasedTemplate>,
+    /// Observers of the mutations, see [`Changelog::on_event`]
+    #[getter(skip)]
+    #[builder(setter(skip), default)]
+    pub(crate) observers: Observers,
+    /// Undo and redo stacks of the operations, see [`Changelog::apply`]
+    #[cfg(feature = "history")]
+    #[getter(skip)]
+    #[builder(setter(skip), default)]
+    pub(crate) history: crate::history::History,
 }
 
 impl ChangelogBuilder {
@@ -69,6 +129,54 @@ impl ChangelogBuilder {
         "HEAD".into()
     }
 
+    fn validate(&self) -> std::result::Result<(), BuildError> {
+        let mut problems = vec![];
+
+        if let Some(Some(url)) = &self.url {
+            if let Err(e) = normalize_repo_url(url) {
+                problems.push(BuildProblem::InvalidUrl(e.to_string()));
+            }
+        }
+
+        let mut versions = HashSet::new();
+        for release in self.releases.iter().flatten() {
+            match (release.version(), release.date()) {
+                (Some(version), _) if !versions.insert(version) => {
+                    problems.push(BuildProblem::DuplicateVersion(version.clone()))
+                }
+                (None, Some(date)) => problems.push(BuildProblem::MissingVersion(*date)),
+                _ => {}
+            }
+        }
+
+        if matches!(&self.head, Some(head) if head.trim().is_empty()) {
+            problems.push(BuildProblem::EmptyHead);
+        }
+
+        if let Some(Some(prefix)) = &self.tag_prefix {
+            if prefix.chars().any(char::is_whitespace) {
+                problems.push(BuildProblem::InvalidTagPrefix(prefix.clone()));
+            }
+        }
+
+        match problems.is_empty() {
+            true => Ok(()),
+            false => Err(BuildError::Invalid(problems)),
+        }
+    }
+
+    /// Repository URL without trailing slashes, invalid URLs are rejected by `validate`
+    fn normalized_url(&self) -> Option<String> {
+        let url = self.url.clone().flatten()?;
+        Some(normalize_repo_url(&url).unwrap_or(url))
+    }
+
+    /// Tag name resolver, takes precedence over `tag_prefix`
+    pub fn tag_name_resolver(&mut self, resolver: impl TagNameResolver + 'static) -> &mut Self {
+        self.tag_name_resolver = Some(Some(SharedResolver(Arc::new(resolver))));
+        self
+    }
+
     pub fn releases(&mut self, releases: Vec<Release>) -> &mut Self {
         self.releases = Some(releases);
         self.sort_releases()
@@ -92,14 +200,23 @@ impl ChangelogBuilder {
         self
     }
 
+    /// Set links, for duplicate anchors the last definition wins
     pub fn links(&mut self, links: Vec<String>) -> Result<&mut Self> {
         let links = links
             .iter()
             .map(|link| Link::parse(link.clone()))
             .collect::<Result<Vec<Link>>>()
             .wrap_err_with(|| "Failed to parse links")?;
+        Ok(self.parsed_links(links))
+    }
+
+    pub(crate) fn parsed_links(&mut self, links: Vec<Link>) -> &mut Self {
+        let links = links.into_iter().fold(vec![], |mut links, link| {
+            upsert_link(&mut links, link);
+            links
+        });
         self.links = Some(links);
-        Ok(self)
+        self
     }
 
     pub fn compact(&mut self, compact: bool) -> &mut Self {
@@ -120,12 +237,164 @@ impl ChangelogBuilder {
 pub struct ChangelogParseOptions {
     pub url: Option<String>,
     pub tag_prefix: Option<String>,
+    /// Git HEAD reference, inferred from the `[Unreleased]` compare link if not set, e.g.
+    /// `develop` for `.../compare/v1.2.3...develop`
     pub head: Option<String>,
+    /// Require release versions to follow semantic versioning, other versions are reported as
+    /// [`ParseError::InvalidVersion`](crate::ParseError::InvalidVersion)
+    pub strict_versions: bool,
+    /// Parse only the first document of a file with multiple `# ` headings, e.g. with vendored
+    /// changelogs appended, instead of failing with
+    /// [`ParseError::MultipleDocuments`](crate::ParseError::MultipleDocuments)
+    pub first_document_only: bool,
+    /// Accept release dates like `2024-6-3`, `2024/06/03` or `June 3, 2024`, they are written
+    /// as ISO 8601 dates on output and listed in [`Changelog::normalized_dates`]
+    pub lenient_dates: bool,
+    /// Entries must match the templates of their kind, other entries are reported as
+    /// [`ParseError::InvalidEntry`](crate::ParseError::InvalidEntry). The templates are kept in
+    /// the changelog, see [`Changelog::set_templates`]
+    pub templates: Option<TemplateRegistry>,
+    /// Recognize the placeholder entries of the Unreleased release. The template is kept in the
+    /// changelog, see [`Changelog::set_unreleased_template`]
+    pub unreleased_template: Option<UnreleasedTemplate>,
+    /// Expand `<!-- include: path -->` directives when parsing from a file, the referenced
+    /// changelogs are concatenated, see [`Changelog::parse_from_file`]
+    pub includes: bool,
+}
+
+/// Release date which isn't an ISO 8601 date, see [`ChangelogParseOptions::lenient_dates`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedDate {
+    /// Line of the release heading, starting from 1
+    pub line: usize,
+    /// Date as written in the source, e.g. `June 3, 2024`
+    pub original: String,
+    pub date: Date,
+}
+
+/// Options of [`Changelog::init`].
+#[derive(Debug, Clone, Default)]
+pub struct InitOptions {
+    pub url: Option<String>,
+    pub tag_prefix: Option<String>,
+    /// Add an initial `0.1.0` release of this date with an "Initial release" entry
+    pub initial_release: Option<Date>,
 }
 
 impl Changelog {
+    /// Create a new changelog builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let changelog = Changelog::builder()
+    ///     .title("Changelog".to_string())
+    ///     .url(Some("https://github.com/napalmpapalam/keep-a-changelog-rs".to_string()))
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(changelog.title().as_deref(), Some("Changelog"));
+    /// ```
+    pub fn builder() -> ChangelogBuilder {
+        ChangelogBuilder::default()
+    }
+
+    /// Create an empty changelog for the repository `url`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let changelog = Changelog::new("https://github.com/napalmpapalam/keep-a-changelog-rs/").unwrap();
+    /// assert_eq!(
+    ///     changelog.url().as_deref(),
+    ///     Some("https://github.com/napalmpapalam/keep-a-changelog-rs")
+    /// );
+    /// assert!(Changelog::new("not a url").is_err());
+    /// ```
+    pub fn new(url: impl Into<String>) -> std::result::Result<Self, BuildError> {
+        Self::builder().url(Some(url.into())).build()
+    }
+
+    /// Create a changelog with the default title and description and an empty Unreleased
+    /// release.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let changelog = Changelog::default_with_unreleased();
+    /// assert!(changelog.get_unreleased().is_some());
+    /// assert!(changelog.to_string().contains("## [Unreleased]"));
+    /// ```
+    pub fn default_with_unreleased() -> Self {
+        let unreleased = Release::builder()
+            .build()
+            .expect("Unreleased release is valid");
+        Self::builder()
+            .releases(vec![unreleased])
+            .build()
+            .expect("Default changelog is valid")
+    }
+
+    /// Create a new CHANGELOG.md file at `path` with the default title and description and an
+    /// empty Unreleased release, e.g. to adopt the changelog in an existing repository
+    ///
+    /// Fails if the file already exists.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use keep_a_changelog::{CalendarDate, Changelog, Date, InitOptions};
+    ///
+    /// let opts = InitOptions {
+    ///     url: Some("https://github.com/napalmpapalam/keep-a-changelog-rs".to_string()),
+    ///     initial_release: Date::new(2024, 4, 28),
+    ///     ..Default::default()
+    /// };
+    /// let changelog = Changelog::init("CHANGELOG.md", opts).unwrap();
+    /// assert_eq!(changelog.releases().len(), 2);
+    /// ```
+    pub fn init<P: AsRef<Path>>(path: P, opts: InitOptions) -> Result<Self> {
+        let path = path.as_ref();
+
+        let mut releases = vec![Release::builder()
+            .build()
+            .expect("Unreleased release is valid")];
+        if let Some(date) = opts.initial_release {
+            let mut release = Release::builder()
+                .version(Version::new(0, 1, 0))
+                .date(date)
+                .build()?;
+            release.added("Initial release".to_string());
+            releases.push(release);
+        }
+
+        let changelog = Self::builder()
+            .url(opts.url)
+            .tag_prefix(opts.tag_prefix)
+            .releases(releases)
+            .build()?;
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .wrap_err_with(|| format!("Failed to create {}", path.display()))?;
+        file.write_all(changelog.file_contents().as_bytes())?;
+        Ok(changelog)
+    }
+
     /// Parse CHANGELOG.md file
     ///
+    /// `<!-- include: path -->` directives aren't expanded, they're kept in
+    /// [`Changelog::includes`] and written back on output, use [`Changelog::parse_from_file`] with
+    /// [`ChangelogParseOptions::includes`] to expand them.
+    ///
     /// # Examples
     ///
     /// ```
@@ -139,23 +408,154 @@ impl Changelog {
     ///        url: Some("https://github.com/napalmpapalam/keep-a-changelog-rs".to_string()),
     ///        head: Some("master".to_string()),
     ///        tag_prefix: Some("v".to_string()),
+    ///        ..Default::default()
     ///    }),
     /// );
     ///
     /// assert!(changelog.is_ok());
     /// ```
-    ///
     pub fn parse(markdown: String, opts: Option<ChangelogParseOptions>) -> Result<Self> {
-        Parser::parse(markdown, opts)
+        include::parse(markdown, opts, None)
+    }
+
+    /// Parse CHANGELOG.md file reporting all problems at once
+    ///
+    /// Malformed releases and unknown sections are skipped and parsing continues, all problems
+    /// found are returned sorted by line. `<!-- include: path -->` directives aren't expanded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [1.0 beta] - 2024-06-01\n### Fixed\n- Bug fix\n\
+    ///     \n## [0.2.0] - 2024-13-01\n### Improved\n- Performance\n";
+    ///
+    /// let errors = Changelog::parse_recovering(markdown.to_string(), None).unwrap_err();
+    /// let lines = errors.iter().map(|e| e.line()).collect::<Vec<_>>();
+    /// assert_eq!(lines, vec![Some(2), Some(6), Some(7)]);
+    /// ```
+    pub fn parse_recovering(
+        markdown: String,
+        opts: Option<ChangelogParseOptions>,
+    ) -> std::result::Result<Self, Vec<ParseError>> {
+        Parser::parse_recovering(markdown, opts)
+    }
+
+    /// Parse a file with multiple `# ` headings into a changelog per heading
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [2.0.0] - 2024-01-10\n- Major release\n\
+    ///     \n# Vendored\n## [0.1.0] - 2023-01-10\n- Initial release\n";
+    ///
+    /// let changelogs = Changelog::parse_documents(markdown.to_string(), None).unwrap();
+    /// assert_eq!(changelogs.len(), 2);
+    /// assert_eq!(changelogs[1].title().as_deref(), Some("Vendored"));
+    /// ```
+    pub fn parse_documents(
+        markdown: String,
+        opts: Option<ChangelogParseOptions>,
+    ) -> Result<Vec<Self>> {
+        split_documents(&markdown)
+            .into_iter()
+            .map(|(_, document)| Self::parse(document, opts.clone()))
+            .collect()
     }
 
+    /// Parse CHANGELOG.md file from the path
+    ///
+    /// With [`ChangelogParseOptions::includes`] set, `<!-- include: path -->` directives are
+    /// expanded by concatenating the referenced changelogs, see [`Changelog::concat`]. Paths are
+    /// resolved against the directory of the file. The directives are written back on output
+    /// instead of the included releases.
     pub fn parse_from_file(path: &str, opts: Option<ChangelogParseOptions>) -> Result<Self> {
-        let path = Path::new(path);
+        Self::parse_path(Path::new(path), opts)
+    }
+
+    /// Parse many CHANGELOG.md files, results are in the order of `paths`
+    ///
+    /// Files are parsed in parallel with the `parallel` feature enabled. Errors are reported per
+    /// file, so one broken changelog doesn't fail the whole batch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let paths = [
+    ///     PathBuf::from("tests/data/early_changelog.md"),
+    ///     PathBuf::from("tests/data/missing.md"),
+    /// ];
+    /// let changelogs = Changelog::parse_many(&paths, None);
+    ///
+    /// assert!(changelogs[0].is_ok());
+    /// assert!(changelogs[1].is_err());
+    /// ```
+    pub fn parse_many(paths: &[PathBuf], opts: Option<ChangelogParseOptions>) -> Vec<Result<Self>> {
+        let parse = |path: &PathBuf| {
+            Self::parse_path(path, opts.clone())
+                .wrap_err_with(|| format!("Failed to parse {}", path.display()))
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            paths.par_iter().map(parse).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        paths.iter().map(parse).collect()
+    }
+
+    fn parse_path(path: &Path, opts: Option<ChangelogParseOptions>) -> Result<Self> {
         let mut markdown = String::new();
         File::open(path)?
             .read_to_string(&mut markdown)
             .wrap_err_with(|| "Failed to read CHANGELOG.md")?;
-        Parser::parse(markdown, opts)
+        let base_dir = opts
+            .as_ref()
+            .filter(|opts| opts.includes)
+            .map(|_| path.parent().unwrap_or(Path::new(".")));
+        include::parse(markdown, opts, base_dir)
+    }
+
+    /// Parse both sides of a CHANGELOG.md file containing git merge conflict markers
+    ///
+    /// Returns a tuple of changelogs where the first one keeps "our" side of every conflict and
+    /// the second one keeps "their" side, so the conflict could be resolved programmatically.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Added\n\
+    ///     <<<<<<< HEAD\n- Feature A\n=======\n- Feature B\n>>>>>>> feature-b\n";
+    ///
+    /// assert!(Changelog::parse(markdown.to_string(), None).is_err());
+    ///
+    /// let (ours, theirs) = Changelog::parse_conflict_sides(markdown.to_string(), None).unwrap();
+    /// assert!(ours.to_string().contains("- Feature A"));
+    /// assert!(theirs.to_string().contains("- Feature B"));
+    /// ```
+    pub fn parse_conflict_sides(
+        markdown: String,
+        opts: Option<ChangelogParseOptions>,
+    ) -> Result<(Self, Self)> {
+        let ours = Parser::parse(
+            resolve_conflicts(&markdown, ConflictSide::Ours),
+            opts.clone(),
+        )
+        .wrap_err_with(|| "Failed to parse our side of the merge conflict")?;
+        let theirs = Parser::parse(resolve_conflicts(&markdown, ConflictSide::Theirs), opts)
+            .wrap_err_with(|| "Failed to parse their side of the merge conflict")?;
+        Ok((ours, theirs))
     }
 
     pub fn save_to_file(&self, path: &str) -> Result<()> {
@@ -169,26 +569,164 @@ impl Changelog {
         Ok(())
     }
 
+    /// Save each release as a separate Markdown file in `dir`
+    ///
+    /// `naming` returns the file name of a release. Every file contains the release heading,
+    /// changes and the link definitions used by the release. The directory is created if it
+    /// doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let changelog = Changelog::parse_from_file("CHANGELOG.md", None).unwrap();
+    /// changelog
+    ///     .save_releases_to_dir("releases", |release| match release.version() {
+    ///         Some(version) => format!("{version}.md"),
+    ///         None => "unreleased.md".to_string(),
+    ///     })
+    ///     .unwrap();
+    /// ```
+    pub fn save_releases_to_dir<F>(&self, dir: impl AsRef<Path>, naming: F) -> Result<()>
+    where
+        F: Fn(&Release) -> String,
+    {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)
+            .wrap_err_with(|| format!("Failed to create directory: {}", dir.display()))?;
+
+        for release in self.releases().iter().filter(|r| !r.draft()) {
+            let path = dir.join(naming(release));
+            fs::write(&path, self.release_file_contents(release)?)
+                .wrap_err_with(|| format!("Failed to write release file: {}", path.display()))?;
+        }
+
+        Ok(())
+    }
+
+    fn release_file_contents(&self, release: &Release) -> Result<String> {
+        let mut contents = DisplayFn(|f: &mut fmt::Formatter| self.fmt_release(f, release))
+            .to_string()
+            .trim_end()
+            .to_string();
+        contents.push('\n');
+
+        let mut links = self
+            .links
+            .iter()
+            .filter(|link| contents.contains(&format!("[{}]", link.anchor())))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if self.url.is_some() {
+            if let Some(link) = release.compare_link(self)? {
+                links.retain(|l| l.anchor() != link.anchor());
+                links.push(link);
+            }
+        }
+
+        if !links.is_empty() {
+            contents.push('\n');
+            links
+                .iter()
+                .for_each(|link| contents.push_str(&format!("{link}\n")));
+        }
+
+        Ok(contents)
+    }
+
+    /// Update CHANGELOG.md file in place with minimal textual edits
+    ///
+    /// The existing file is re-parsed and compared against this changelog, only the header,
+    /// releases and trailing links which actually changed are re-rendered, all other regions of
+    /// the file are left byte-identical. If the file doesn't exist, it is created the same way as
+    /// with [`Changelog::save_to_file`].
+    pub fn apply_to_file(&self, path: &str) -> Result<()> {
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == ErrorKind::NotFound => return self.save_to_file(path),
+            Err(e) => return Err(e).wrap_err_with(|| "Failed to read CHANGELOG.md"),
+        };
+
+        let contents = patch::apply(&self.untransformed(), &source)?;
+
+        if contents != source {
+            fs::write(path, contents).wrap_err_with(|| "Failed to write CHANGELOG.md")?;
+        }
+
+        Ok(())
+    }
+
+    /// Add release to the changelog and insert it into CHANGELOG.md file
+    ///
+    /// Unlike [`Changelog::save_to_file`] the document is not re-rendered, only the release block
+    /// and its link definition are inserted at the right position and the link of the next newer
+    /// release (usually "Unreleased") is refreshed, which keeps releasing cheap for very large
+    /// changelogs. If the file doesn't exist, the whole changelog is saved.
+    pub fn append_release_to_file(&mut self, path: &str, release: Release) -> Result<()> {
+        self.add_release(release.clone());
+
+        if self.format.requires_full_render() {
+            // year headings, the history link or inline links of other releases may need to be
+            // updated, so render the whole file
+            return self.save_to_file(path);
+        }
+
+        let source = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) if e.kind() == ErrorKind::NotFound => return self.save_to_file(path),
+            Err(e) => return Err(e).wrap_err_with(|| "Failed to read CHANGELOG.md"),
+        };
+
+        let contents = patch::insert_release(&self.untransformed(), &source, &release)?;
+        fs::write(path, contents).wrap_err_with(|| "Failed to write CHANGELOG.md")?;
+        Ok(())
+    }
+
     /// Format the changelog as a string for output as a valid Markdown file
     ///
     /// To ensure compliance with the requirements of the Markdown standard any blank
     /// line at the end of the string needs to be removed.
     ///
-    fn file_contents(&self) -> String {
-        let contents = self.to_string();
+    pub(crate) fn file_contents(&self) -> String {
+        if self.format.transform.is_some() {
+            return self.untransformed().file_contents();
+        }
+
+        let contents = DisplayFn(|f: &mut fmt::Formatter| self.fmt_changelog(f, true)).to_string();
         let mut contents = contents.replace("\n\n\n", "\n\n");
         contents = contents.trim_end_matches('\n').to_string();
         contents.push('\n');
+
+        #[cfg(feature = "json")]
+        if self.format.sidecar {
+            contents.push('\n');
+            contents.push_str(&crate::sidecar::render(self, &contents));
+        }
+
         contents
     }
 
+    /// The changelog without the entry transform, CHANGELOG.md keeps the entries as written
+    fn untransformed(&self) -> Cow<'_, Self> {
+        match self.format.transform {
+            Some(_) => {
+                let mut changelog = self.clone();
+                changelog.format.transform = None;
+                Cow::Owned(changelog)
+            }
+            None => Cow::Borrowed(self),
+        }
+    }
+
     pub fn releases_mut(&mut self) -> &mut Vec<Release> {
         &mut self.releases
     }
 
     /// Find release by version
     pub fn find_release(&self, version: String) -> Result<Option<&Release>> {
-        let version = Version::parse(&version).wrap_err_with(|| {
+        let version = ReleaseVersion::parse(&version).wrap_err_with(|| {
             format!("Failed to parse version: {version} during finding release")
         })?;
 
@@ -198,9 +736,86 @@ impl Changelog {
             .find(|r| r.version() == &Some(version.clone())))
     }
 
+    /// Find all releases with versions matching the requirement, newest first
+    ///
+    /// Releases with versions not following semantic versioning never match.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{ChangeKind, Changelog, VersionReq};
+    ///
+    /// let markdown = "# Changelog\n## [2.0.0] - 2024-06-01\n### Added\n- Feature\n\
+    ///     \n## [1.2.1] - 2024-05-20\n### Security\n- Security fix\n\
+    ///     \n## [1.2.0] - 2024-05-18\n### Added\n- Feature\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let releases = changelog.find_releases_matching(&VersionReq::parse("^1.2").unwrap());
+    /// assert_eq!(releases.len(), 2);
+    ///
+    /// let security = releases
+    ///     .iter()
+    ///     .filter(|r| r.changes().iter().any(|(kind, _)| kind == ChangeKind::Security))
+    ///     .count();
+    /// assert_eq!(security, 1);
+    /// ```
+    pub fn find_releases_matching(&self, req: &VersionReq) -> Vec<&Release> {
+        self.releases()
+            .iter()
+            .filter(|r| {
+                r.version()
+                    .as_ref()
+                    .and_then(|v| v.semver())
+                    .is_some_and(|v| req.matches(v))
+            })
+            .collect()
+    }
+
+    /// Get the release preceding `version`, i.e. the release with the greatest lower version,
+    /// Unreleased is skipped
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, ReleaseVersion};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n## [1.1.0] - 2024-06-01\
+    ///     \n## [1.0.1] - 2024-05-20\n## [1.0.0] - 2024-05-18\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    /// let version = |release: Option<&keep_a_changelog::Release>| {
+    ///     release.and_then(|r| r.version().as_ref()).map(|v| v.to_string())
+    /// };
+    ///
+    /// let current = ReleaseVersion::parse("1.0.1").unwrap();
+    /// assert_eq!(version(changelog.previous_release(&current)).as_deref(), Some("1.0.0"));
+    /// assert_eq!(version(changelog.next_release(&current)).as_deref(), Some("1.1.0"));
+    /// assert!(changelog.next_release(&ReleaseVersion::parse("1.1.0").unwrap()).is_none());
+    /// ```
+    pub fn previous_release(&self, version: &ReleaseVersion) -> Option<&Release> {
+        self.versioned_releases()
+            .filter(|(v, _)| *v < version)
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, release)| release)
+    }
+
+    /// Get the release following `version`, i.e. the release with the least greater version,
+    /// Unreleased is skipped
+    pub fn next_release(&self, version: &ReleaseVersion) -> Option<&Release> {
+        self.versioned_releases()
+            .filter(|(v, _)| *v > version)
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, release)| release)
+    }
+
+    fn versioned_releases(&self) -> impl Iterator<Item = (&ReleaseVersion, &Release)> {
+        self.releases()
+            .iter()
+            .filter_map(|release| release.version().as_ref().map(|v| (v, release)))
+    }
+
     /// Find release by version and return mutable reference
     pub fn find_release_mut(&mut self, version: String) -> Result<Option<&mut Release>> {
-        let version = Version::parse(&version).wrap_err_with(|| {
+        let version = ReleaseVersion::parse(&version).wrap_err_with(|| {
             format!("Failed to parse version: {version} during finding release")
         })?;
 
@@ -218,6 +833,74 @@ impl Changelog {
             .find(|r| r.version().is_none() && r.date().is_none())
     }
 
+    /// Refresh the Unreleased release from the edited source
+    ///
+    /// Only the Unreleased block of `markdown` is tokenized and parsed, the rest of the model is
+    /// kept as is. Intended for editors and bots which only touch the top of the file, use
+    /// [`Changelog::parse`] if other regions could have changed. The Unreleased release is
+    /// removed if the source has none. Spans of the following releases and links are moved by
+    /// the size change of the Unreleased block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{ChangeKind, Changelog};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n## [0.1.0] - 2024-04-28\n- Initial release\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let markdown = markdown.replace("## [Unreleased]\n", "## [Unreleased]\n### Added\n- Feature\n");
+    /// changelog.reparse_unreleased(&markdown).unwrap();
+    ///
+    /// let unreleased = changelog.get_unreleased().unwrap();
+    /// let (kind, change) = unreleased.changes().iter().next().unwrap();
+    /// assert_eq!((kind, change.as_str()), (ChangeKind::Added, "Feature"));
+    /// ```
+    pub fn reparse_unreleased(&mut self, markdown: &str) -> Result<()> {
+        let unreleased = patch::parse_unreleased(self, markdown)
+            .wrap_err_with(|| "Failed to parse the Unreleased section")?;
+        let position = self
+            .releases
+            .iter()
+            .position(|r| r.version().is_none() && r.date().is_none());
+
+        // the following releases and links moved by the size change of the Unreleased block
+        let following = position.map_or(0, |position| position + 1);
+        let old_start = self.releases[following..]
+            .iter()
+            .filter_map(|release| release.span())
+            .chain(self.links.iter().filter_map(|link| link.span()))
+            .map(|span| (*span.lines.start(), span.bytes.start))
+            .min();
+
+        if let (Some((line, byte)), Some((new_line, new_byte))) =
+            (old_start, patch::following_start(markdown))
+        {
+            let lines = new_line as isize - line as isize;
+            let bytes = new_byte as isize - byte as isize;
+
+            for release in &mut self.releases[following..] {
+                release.shift_spans(lines, bytes);
+            }
+            for link in &mut self.links {
+                if link.span().is_some_and(|span| span.bytes.start >= byte) {
+                    link.shift_span(lines, bytes);
+                }
+            }
+        }
+
+        match (position, unreleased) {
+            (Some(position), Some(unreleased)) => self.releases[position] = unreleased,
+            (None, Some(unreleased)) => self.releases.insert(0, unreleased),
+            (Some(position), None) => {
+                self.releases.remove(position);
+            }
+            (None, None) => {}
+        }
+
+        Ok(())
+    }
+
     /// Same as get_unreleased but mutable
     pub fn get_unreleased_mut(&mut self) -> Option<&mut Release> {
         self.releases_mut()
@@ -225,37 +908,351 @@ impl Changelog {
             .find(|r| r.version().is_none() && r.date().is_none())
     }
 
-    /// Add release to changelog
-    /// It will add release to the beginning of the releases list and sort them by date
+    /// Add a change to the Unreleased release, the release is created if missing
     ///
     /// # Examples
     ///
     /// ```
-    /// use keep_a_changelog::{Changelog, ChangelogParseOptions, Release, NaiveDate, Version};
+    /// use keep_a_changelog::{ChangeKind, Changelog};
     ///
-    /// let markdown = "# Changelog\n## 0.1.0 - 2024-04-28\n- Initial release\n";
+    /// let mut changelog = Changelog::builder().build().unwrap();
+    /// changelog
+    ///     .add_unreleased_change(ChangeKind::Added, "New feature")
+    ///     .add_unreleased_change(ChangeKind::Fixed, "Bug fix");
     ///
-    /// let mut changelog = Changelog::parse(
-    ///    markdown.to_string(),
-    ///    Some(ChangelogParseOptions {
-    ///        url: Some("https://github.com/napalmpapalam/keep-a-changelog-rs".to_string()),
-    ///        head: Some("master".to_string()),
-    ///        tag_prefix: Some("v".to_string()),
-    ///    }),
-    /// ).unwrap();
+    /// assert_eq!(changelog.releases().len(), 1);
+    /// assert_eq!(changelog.get_unreleased().unwrap().changes().iter().count(), 2);
+    /// ```
+    pub fn add_unreleased_change(
+        &mut self,
+        kind: ChangeKind,
+        text: impl Into<String>,
+    ) -> &mut Self {
+        if self.get_unreleased().is_none() {
+            let unreleased = Release::builder()
+                .build()
+                .expect("Unreleased release is valid");
+            self.add_release(unreleased);
+        }
+
+        self.drop_placeholders();
+
+        let text = text.into();
+        if let Some(unreleased) = self.get_unreleased_mut() {
+            unreleased.add(kind.clone(), text.clone());
+            self.emit(ChangelogEvent::EntryAdded {
+                version: None,
+                kind,
+                text,
+            });
+        }
+        self
+    }
+
+    /// Merge changes of all releases newer than `version`
     ///
-    /// let release = Release::builder()
-    ///    .version(Version::parse("0.1.1").unwrap())
-    ///    .date(NaiveDate::from_ymd_opt(2024, 4, 30).unwrap())
-    ///    .build().unwrap();
+    /// Changes of each kind are ordered from the newest release to the oldest. Changes of the
+    /// Unreleased release are included only if `include_unreleased` is set.
+    ///
+    /// # Examples
     ///
-    /// changelog.add_release(release);
-    /// assert_eq!(changelog.releases().len(), 2);
     /// ```
+    /// use keep_a_changelog::{ChangeKind, Changelog, ReleaseVersion};
     ///
-    pub fn add_release(&mut self, release: Release) -> &mut Self {
-        self.releases.insert(0, release);
-        self.sort_releases()
+    /// let markdown = "# Changelog\n## [0.2.0] - 2024-05-20\n### Fixed\n- Second fix\n\
+    ///     \n## [0.1.1] - 2024-05-18\n### Fixed\n- First fix\n\
+    ///     \n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let changes = changelog.changes_since(&ReleaseVersion::parse("0.1.0").unwrap(), false);
+    /// let fixed = changes.iter().map(|(_, change)| change.as_str()).collect::<Vec<_>>();
+    /// assert_eq!(fixed, vec!["Second fix", "First fix"]);
+    /// ```
+    pub fn changes_since(&self, version: &ReleaseVersion, include_unreleased: bool) -> Changes {
+        self.releases()
+            .iter()
+            .filter(|r| match r.version() {
+                Some(v) => v > version,
+                None => include_unreleased,
+            })
+            .flat_map(|r| r.changes().iter())
+            .collect()
+    }
+
+    /// Suggest next version based on the Unreleased changes
+    ///
+    /// Breaking changes (see [`ChangeEntry::breaking`](crate::ChangeEntry)) bump the major
+    /// version, or the minor version while it is `0.y.z`. Added, changed, deprecated and removed
+    /// features bump the minor version and anything else bumps the patch version. Returns `None`
+    /// if there are no Unreleased changes or the releases don't follow semantic versioning, and
+    /// `0.1.0` if there are no releases yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, Version};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Changed\n- **BREAKING** Rename `parse`\n\
+    ///     \n## [1.2.3] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// assert_eq!(changelog.suggest_next_version(), Some(Version::new(2, 0, 0)));
+    /// ```
+    pub fn suggest_next_version(&self) -> Option<Version> {
+        let unreleased = self.get_unreleased()?;
+        if unreleased.changes().is_empty() {
+            return None;
+        }
+
+        let versions = self.releases().iter().filter_map(|r| r.version().as_ref());
+        let Some(latest) = versions.clone().filter_map(|v| v.semver()).max() else {
+            return match versions.count() {
+                0 => Some(Version::new(0, 1, 0)),
+                _ => None,
+            };
+        };
+
+        let breaking = !unreleased.breaking_changes().is_empty();
+        let feature = unreleased.changes().iter().any(|(kind, _)| {
+            matches!(
+                kind,
+                ChangeKind::Added
+                    | ChangeKind::Changed
+                    | ChangeKind::Deprecated
+                    | ChangeKind::Removed
+            )
+        });
+
+        let next = match (breaking, feature) {
+            (true, _) if latest.major > 0 => Version::new(latest.major + 1, 0, 0),
+            (true, _) | (false, true) => Version::new(latest.major, latest.minor + 1, 0),
+            (false, false) => Version::new(latest.major, latest.minor, latest.patch + 1),
+        };
+
+        Some(next)
+    }
+
+    /// Suggest version of a release made `today` following the calendar versioning `scheme`
+    ///
+    /// The `MICRO` segment starts from 0 and is incremented for every release within the same
+    /// period. Fails if the scheme has no `MICRO` segment and the version was already released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{CalendarDate, Changelog, Date};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Fixed\n- Bug fix\n\
+    ///     \n## [2024.06.0] - 2024-06-03\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    /// let scheme = "YYYY.0M.MICRO".parse().unwrap();
+    ///
+    /// let today = Date::new(2024, 6, 20).unwrap();
+    /// let version = changelog.suggest_next_calver(&scheme, today).unwrap();
+    /// assert_eq!(version.to_string(), "2024.06.1");
+    ///
+    /// let today = Date::new(2024, 7, 1).unwrap();
+    /// let version = changelog.suggest_next_calver(&scheme, today).unwrap();
+    /// assert_eq!(version.to_string(), "2024.07.0");
+    /// ```
+    pub fn suggest_next_calver(
+        &self,
+        scheme: &CalVerScheme,
+        today: Date,
+    ) -> Result<ReleaseVersion> {
+        let period = ReleaseVersion::parse(&scheme.format(today, 0))?;
+        let period = scheme.segments(&period).unwrap_or_default();
+        let period = &period[..period.len() - scheme.has_micro() as usize];
+
+        let micro = self
+            .versioned_releases()
+            .filter_map(|(version, _)| scheme.segments(version))
+            .filter(|segments| segments.starts_with(period))
+            .map(|segments| segments.last().copied().unwrap_or_default())
+            .max();
+
+        let version = match (micro, scheme.has_micro()) {
+            (Some(micro), true) => scheme.format(today, micro + 1),
+            (None, true) => scheme.format(today, 0),
+            (Some(_), false) => bail!("Release {} already exists", scheme.format(today, 0)),
+            (None, false) => scheme.format(today, 0),
+        };
+
+        ReleaseVersion::parse(&version)
+    }
+
+    /// Promote the Unreleased release to the given version and date
+    ///
+    /// A new empty Unreleased release is added on top, so the changelog is ready for the next
+    /// round of changes. With an [Unreleased template](Changelog::set_unreleased_template), the
+    /// placeholder entries are dropped from the promoted release and added to the new one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{CalendarDate, Changelog, Date, Version};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Added\n- New feature\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// changelog
+    ///     .promote_unreleased(
+    ///         Version::parse("0.1.0").unwrap(),
+    ///         Date::new(2024, 6, 1).unwrap(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(changelog.releases().len(), 2);
+    /// assert!(changelog.get_unreleased().unwrap().changes().is_empty());
+    /// assert!(changelog.find_release("0.1.0".to_string()).unwrap().is_some());
+    /// ```
+    pub fn promote_unreleased<V: Into<ReleaseVersion>>(
+        &mut self,
+        version: V,
+        date: Date,
+    ) -> Result<&mut Self> {
+        let version = version.into();
+        if self.find_release(version.to_string())?.is_some() {
+            bail!("Release {version} already exists");
+        }
+
+        self.drop_placeholders();
+        let unreleased = self
+            .get_unreleased_mut()
+            .ok_or_eyre("Missing Unreleased release")?;
+        unreleased.set_version(version.clone()).set_date(date);
+
+        let release = match &self.unreleased_template {
+            Some(template) => template.release(),
+            None => Release::builder().build()?,
+        };
+        self.add_release(release);
+        self.emit(ChangelogEvent::Promoted { version, date });
+        Ok(self)
+    }
+
+    /// Add release to changelog
+    /// It will add release to the beginning of the releases list and sort them by date
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{
+    ///     CalendarDate, Changelog, ChangelogParseOptions, Date, Release, Version,
+    /// };
+    ///
+    /// let markdown = "# Changelog\n## 0.1.0 - 2024-04-28\n- Initial release\n";
+    ///
+    /// let mut changelog = Changelog::parse(
+    ///    markdown.to_string(),
+    ///    Some(ChangelogParseOptions {
+    ///        url: Some("https://github.com/napalmpapalam/keep-a-changelog-rs".to_string()),
+    ///        head: Some("master".to_string()),
+    ///        tag_prefix: Some("v".to_string()),
+    ///        ..Default::default()
+    ///    }),
+    /// ).unwrap();
+    ///
+    /// let release = Release::builder()
+    ///    .version(Version::parse("0.1.1").unwrap())
+    ///    .date(Date::new(2024, 4, 30).unwrap())
+    ///    .build().unwrap();
+    ///
+    /// changelog.add_release(release);
+    /// assert_eq!(changelog.releases().len(), 2);
+    /// ```
+    ///
+    pub fn add_release(&mut self, release: Release) -> &mut Self {
+        let version = release.version().clone();
+        self.releases.insert(0, release);
+        self.sort_releases();
+        self.emit(ChangelogEvent::ReleaseAdded { version });
+        self
+    }
+
+    /// Squash releases with versions in `range` into a single release
+    ///
+    /// The squashed release gets `into_version`, the date of the newest squashed release, the
+    /// union of their changes and a note listing the squashed versions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, Version};
+    ///
+    /// let markdown = "# Changelog\n## [1.2.2] - 2024-06-02\n### Fixed\n- Second fix\n\
+    ///     \n## [1.2.1] - 2024-06-01\n### Fixed\n- First fix\n\
+    ///     \n## [1.2.0] - 2024-05-01\n### Added\n- Feature\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// changelog
+    ///     .squash_releases(
+    ///         Version::new(1, 2, 1)..=Version::new(1, 2, 2),
+    ///         Version::new(1, 3, 0),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// assert_eq!(changelog.releases().len(), 2);
+    /// let squashed = changelog.find_release("1.3.0".to_string()).unwrap().unwrap();
+    /// assert_eq!(squashed.description().as_deref(), Some("Includes 1.2.1–1.2.2."));
+    /// assert_eq!(squashed.date().unwrap().to_string(), "2024-06-02");
+    /// assert_eq!(squashed.changes().iter().count(), 2);
+    /// ```
+    pub fn squash_releases<R: RangeBounds<Version>>(
+        &mut self,
+        range: R,
+        into_version: Version,
+    ) -> Result<&mut Self> {
+        if !range.contains(&into_version) && self.find_release(into_version.to_string())?.is_some()
+        {
+            bail!("Release {into_version} already exists");
+        }
+
+        let in_range = |r: &Release| {
+            r.version()
+                .as_ref()
+                .and_then(|v| v.semver())
+                .is_some_and(|v| range.contains(v))
+        };
+        if !self.releases.iter().any(in_range) {
+            bail!("No releases to squash in the given range");
+        }
+
+        let (squashed, releases): (Vec<_>, Vec<_>) = std::mem::take(&mut self.releases)
+            .into_iter()
+            .partition(in_range);
+        self.releases = releases;
+
+        // releases are sorted from the newest to the oldest
+        let newest = squashed.first().unwrap();
+        let oldest = squashed.last().unwrap();
+
+        let note = match newest.version() == oldest.version() {
+            true => format!("Includes {}.", newest.version().as_ref().unwrap()),
+            false => format!(
+                "Includes {}–{}.",
+                oldest.version().as_ref().unwrap(),
+                newest.version().as_ref().unwrap()
+            ),
+        };
+        let date = squashed
+            .iter()
+            .filter(|r| r.date().is_some())
+            .max_by_key(|r| *r.date())
+            .map(|r| r.release_date());
+
+        let mut release = Release::builder()
+            .version(into_version)
+            .description(Some(note))
+            .build()?;
+        squashed.into_iter().for_each(|r| {
+            release.merge(r);
+        });
+        if let Some(date) = date {
+            release.set_release_date(date);
+        }
+
+        Ok(self.add_release(release))
     }
 
     fn sort_releases(&mut self) -> &mut Self {
@@ -281,15 +1278,30 @@ impl Changelog {
     ) -> Result<Option<Link>> {
         let repo_url = self.url().clone().ok_or_eyre("Missing repo URL")?;
 
+        if let Some(base) = current.compare_base() {
+            let head = match (current.version(), current.date()) {
+                (Some(version), Some(_)) => self.tag_name(version),
+                _ => self.head().clone(),
+            };
+            let anchor = current.anchor();
+            return Ok(Some(Link {
+                anchor,
+                url: get_compare_url(repo_url, base.clone(), head),
+                title: None,
+                span: Unspanned::default(),
+            }));
+        }
+
         if previous.is_none() {
             let version = current
                 .version()
-                .clone()
-                .ok_or_eyre("Missing version for current release")?
-                .to_string();
+                .as_ref()
+                .ok_or_eyre("Missing version for current release")?;
             return Ok(Some(Link {
-                anchor: version.clone(),
+                anchor: version.to_string(),
                 url: get_release_url(repo_url, self.tag_name(version)),
+                title: None,
+                span: Unspanned::default(),
             }));
         }
 
@@ -298,43 +1310,100 @@ impl Changelog {
         if current.date().is_none() || current.version().is_none() {
             let version = previous
                 .version()
-                .clone()
-                .ok_or_eyre("Missing version for previous release")?
-                .to_string();
+                .as_ref()
+                .ok_or_eyre("Missing version for previous release")?;
             return Ok(Some(Link {
-                anchor: "Unreleased".into(),
+                anchor: current.anchor(),
                 url: get_compare_url(repo_url, self.tag_name(version), self.head().clone()),
+                title: None,
+                span: Unspanned::default(),
             }));
         }
 
         let current_version = current
             .version()
-            .clone()
-            .ok_or_eyre("Missing version for current release")?
-            .to_string();
+            .as_ref()
+            .ok_or_eyre("Missing version for current release")?;
         let previous_version = previous
             .version()
-            .clone()
-            .ok_or_eyre("Missing version for previous release")?
-            .to_string();
+            .as_ref()
+            .ok_or_eyre("Missing version for previous release")?;
 
         Ok(Some(Link {
-            anchor: current_version.clone(),
+            anchor: current_version.to_string(),
             url: get_compare_url(
                 repo_url,
                 self.tag_name(previous_version),
                 self.tag_name(current_version),
             ),
+            title: None,
+            span: Unspanned::default(),
         }))
     }
 
-    fn tag_name(&self, version: String) -> String {
+    pub(crate) fn tag_name(&self, version: &ReleaseVersion) -> String {
+        if let Some(SharedResolver(resolver)) = &self.tag_name_resolver {
+            return resolver.tag_name(version);
+        }
         if let Some(tag_prefix) = self.tag_prefix() {
             return format!("{}{}", tag_prefix, version);
         }
         version.to_string()
     }
 
+    pub(crate) fn expand_links_and_footer(&mut self, expand: &dyn Fn(&str) -> String) {
+        self.links
+            .iter_mut()
+            .for_each(|link| link.url = expand(&link.url));
+        self.footer = self.footer.as_deref().map(expand);
+    }
+
+    /// Set rendering options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, FormatOptions};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Fixed\n- Typo\n- **parser:** Tabs\n- **cli:** Flag\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    /// changelog.set_format_options(FormatOptions {
+    ///     group_by_scope: true,
+    ///     ..Default::default()
+    /// });
+    ///
+    /// assert!(changelog
+    ///     .to_string()
+    ///     .contains("- **cli:** Flag\n- **parser:** Tabs\n- Typo\n"));
+    /// ```
+    pub fn set_format_options(&mut self, format: FormatOptions) -> &mut Self {
+        self.format = format;
+        self
+    }
+
+    /// Set tag name resolver used for generating release and compare links, takes precedence
+    /// over the tag prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, ReleaseVersion};
+    ///
+    /// let markdown = "# Changelog\n## [1.1.0] - 2024-05-01\n### Fixed\n- Bug fix\n\
+    ///     \n## [1.0.0] - 2024-04-28\n### Added\n- Initial release\n\
+    ///     \n[1.1.0]: https://github.com/user/repo/compare/v1.0.0...v1.1.0\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    /// changelog.set_tag_name_resolver(|v: &ReleaseVersion| format!("my-crate-v{v}"));
+    ///
+    /// assert!(changelog.to_string().contains(
+    ///     "[1.1.0]: https://github.com/user/repo/compare/my-crate-v1.0.0...my-crate-v1.1.0\n"
+    /// ));
+    /// ```
+    pub fn set_tag_name_resolver(&mut self, resolver: impl TagNameResolver + 'static) -> &mut Self {
+        self.tag_name_resolver = Some(SharedResolver(Arc::new(resolver)));
+        self
+    }
+
     /// Set compact option on.
     pub fn set_compact(&mut self) -> &mut Self {
         self.compact = true;
@@ -388,33 +1457,35 @@ impl Changelog {
         self
     }
 
-    /// Add a link to the list of links
+    /// Add a link to the list of links, replacing the link with the same anchor
     ///
     /// # Examples
     /// ```
     /// # fn main() {
+    /// use keep_a_changelog::changelog::ChangelogBuilder;
+    ///
     /// let mut changelog = ChangelogBuilder::default().build().unwrap();
     ///
-    /// changelog.add_link("[anchor]: https://example.com".to_string());
+    /// changelog.add_link("[anchor]:", "https://example.com");
     ///
     /// // Assert that the link was added correctly
     /// assert_eq!(changelog.links().len(), 1);
     /// assert_eq!(changelog.links().first().unwrap().anchor(), "anchor");
     /// assert_eq!(changelog.links().first().unwrap().url(),"https://example.com");
-    /// # }    
-    ///     
+    /// # }
+    /// ```
     pub fn add_link<S: Into<String>>(&mut self, anchor: S, url: S) -> &mut Self {
         let link = Link::new(anchor, url);
 
         if let Ok(link) = link {
-            self.links.push(link);
+            upsert_link(&mut self.links, link);
         };
         self
     }
 }
 
-impl Display for Changelog {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+impl Changelog {
+    pub(crate) fn fmt_header(&self, f: &mut fmt::Formatter, drafts: bool) -> fmt::Result {
         if let Some(md_lints) = self.lint.clone() {
             let mut lints = md_lints.iter().cloned().collect::<Vec<_>>();
             lints.sort();
@@ -438,42 +1509,171 @@ impl Display for Changelog {
         };
 
         writeln!(f, "{description}\n")?;
+        self.fmt_toc(f, drafts)?;
 
-        self.releases().iter().try_for_each(|release| {
-            let mut release = release.clone(); // clone the release so that we mutate if required
-            release.set_compact(self.compact);
-            write!(f, "{release}")
-        })?;
+        self.fmt_markers(f, &MarkerAnchor::Header)
+    }
 
-        let tag_regex = Regex::new(r"\d+\.\d+\.\d+((-rc|-x)\.\d+)?").unwrap();
+    /// Whether the changelog has the `marker` anywhere
+    pub(crate) fn has_marker(&self, marker: Marker) -> bool {
+        self.markers.iter().any(|(m, _)| *m == marker)
+    }
 
-        let mut is_non_compare_links = false;
+    /// Write the marker comments following the block with the `anchor`
+    pub(crate) fn fmt_markers(&self, f: &mut fmt::Formatter, anchor: &MarkerAnchor) -> fmt::Result {
+        self.markers
+            .iter()
+            .filter(|(marker, a)| *marker != Marker::ReleaseDate && a == anchor)
+            .try_for_each(|(marker, _)| writeln!(f, "{marker}"))
+    }
 
-        self.links
+    pub(crate) fn fmt_release(&self, f: &mut fmt::Formatter, release: &Release) -> fmt::Result {
+        let inline_link = if self.format.inline_links && self.url.is_some() {
+            release.compare_link(self).ok().flatten()
+        } else {
+            None
+        };
+
+        let mut release = release.clone(); // clone the release so that we mutate if required
+        release
+            .set_compact(self.compact)
+            .set_format(self.format.clone());
+        if inline_link.is_some() {
+            release.brackets = Some(true);
+        } else if release.brackets.is_none() {
+            release.brackets = self.releases.iter().find_map(|release| release.brackets);
+        }
+
+        let is_unreleased = release.version().is_none() && release.date().is_none();
+        let release_date = is_unreleased && self.has_marker(Marker::ReleaseDate);
+
+        if !release_date
+            && inline_link.is_none()
+            && !release.draft()
+            && release.compare_base().is_none()
+            && release.metadata().is_empty()
+        {
+            return write!(f, "{release}");
+        }
+
+        let mut rendered = String::new();
+        fmt::Write::write_fmt(&mut rendered, format_args!("{release}"))?;
+        let (heading, rest) = rendered.split_once('\n').unwrap_or((&rendered, ""));
+
+        let mut heading = heading.to_string();
+        if let Some(link) = inline_link {
+            heading = heading.replacen(']', &format!("]({})", link.url()), 1);
+        }
+        if release_date {
+            heading.push_str(RELEASE_DATE_SUFFIX);
+        }
+        if *release.draft() {
+            heading.push_str(&format!("\n<!-- {DRAFT_FLAG} -->"));
+        }
+        if let Some(base) = release.compare_base() {
+            heading.push_str(&format!("\n<!-- {COMPARE_BASE_FLAG_PREFIX} {base} -->"));
+        }
+        if !release.metadata().is_empty() {
+            let metadata = fmt_pairs(release.metadata());
+            heading.push_str(&format!("\n<!-- {METADATA_FLAG_PREFIX} {metadata} -->"));
+        }
+
+        write!(f, "{heading}\n{rest}")
+    }
+
+    /// Releases to render, limited by [`FormatOptions::max_releases`] and without empty releases
+    /// hidden by [`FormatOptions::hide_empty_unreleased`] and
+    /// [`FormatOptions::hide_empty_sections`]. Draft releases are rendered only if `drafts` is set.
+    pub(crate) fn rendered_releases(&self, drafts: bool) -> impl Iterator<Item = &Release> {
+        self.releases[..self.rendered_releases_end()]
             .iter()
-            .filter(|link| {
-                !tag_regex.is_match(link.anchor()) && !link.anchor().contains("Unreleased")
+            .filter(move |release| drafts || !release.draft())
+            .filter(|release| !self.is_included(release))
+            .filter(|release| {
+                let hide = match release.version() {
+                    None => self.format.hide_empty_unreleased,
+                    Some(_) => self.format.hide_empty_sections,
+                };
+                !hide || !release.changes().is_empty() || release.description().is_some()
             })
-            .try_for_each(|link| {
-                if !is_non_compare_links {
-                    is_non_compare_links = true;
-                }
+    }
+
+    /// End of the releases limited by [`FormatOptions::max_releases`].
+    fn rendered_releases_end(&self) -> usize {
+        let Some(max) = self.format.max_releases else {
+            return self.releases.len();
+        };
 
-                write!(f, "\n{link}")
-            })?;
+        self.releases
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| r.version().is_some())
+            .nth(max)
+            .map_or(self.releases.len(), |(idx, _)| idx)
+    }
+
+    fn fmt_history_link(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let url = self.format.history_url.clone().or_else(|| {
+            self.url
+                .as_ref()
+                .map(|url| get_file_url(url, &self.head, "CHANGELOG.md"))
+        });
+
+        match url {
+            Some(url) => writeln!(f, "See the [full history]({url}) for older releases.\n"),
+            None => writeln!(f, "See the full history for older releases.\n"),
+        }
+    }
+
+    pub(crate) fn fmt_trailer(&self, f: &mut fmt::Formatter, drafts: bool) -> fmt::Result {
+        let mut is_non_compare_links = false;
+
+        let mut links = self
+            .links
+            .iter()
+            .filter(|link| !link.is_release(self))
+            .collect::<Vec<_>>();
+        links.sort_by_key(|link| link.anchor().to_lowercase());
+
+        links.into_iter().try_for_each(|link| {
+            if !is_non_compare_links {
+                is_non_compare_links = true;
+            }
+
+            write!(f, "\n{link}")
+        })?;
 
         if is_non_compare_links {
             writeln!(f)?;
         }
 
-        self.releases
+        // markers following releases which aren't rendered anymore
+        let rendered = self
+            .rendered_releases(drafts)
+            .map(|release| MarkerAnchor::Release(release.anchor()))
+            .collect::<Vec<_>>();
+        self.markers
             .iter()
-            .filter_map(|release| {
-                release
-                    .compare_link(self)
-                    .expect("Failed to get compare link")
+            .filter(|(marker, anchor)| {
+                *marker != Marker::ReleaseDate
+                    && *anchor != MarkerAnchor::Header
+                    && !rendered.contains(anchor)
             })
-            .try_for_each(|link| writeln!(f, "{link}"))?;
+            .try_for_each(|(marker, _)| writeln!(f, "{marker}"))?;
+
+        self.includes
+            .iter()
+            .try_for_each(|include| writeln!(f, "{include}"))?;
+
+        if self.url.is_some() && !self.format.inline_links {
+            self.rendered_releases(drafts)
+                .filter_map(|release| {
+                    release
+                        .compare_link(self)
+                        .expect("Failed to get compare link")
+                })
+                .try_for_each(|link| writeln!(f, "{link}"))?;
+        }
 
         if let Some(footer) = self.footer.clone() {
             write!(f, "---\n{footer}\n")?;
@@ -483,17 +1683,55 @@ impl Display for Changelog {
     }
 }
 
+impl Changelog {
+    /// Render the changelog, draft releases are rendered only for CHANGELOG.md file contents.
+    fn fmt_changelog(&self, f: &mut fmt::Formatter, drafts: bool) -> fmt::Result {
+        self.fmt_header(f, drafts)?;
+
+        let mut year = None;
+        for release in self.rendered_releases(drafts) {
+            if self.format.group_by_year {
+                match release.date().map(|date| date.ymd().0) {
+                    Some(release_year) if year != Some(release_year) => {
+                        writeln!(f, "## {release_year}\n")?;
+                        year = Some(release_year);
+                    }
+                    _ => {}
+                }
+            }
+
+            self.fmt_release(f, release)?;
+            self.fmt_markers(f, &MarkerAnchor::Release(release.anchor()))?;
+        }
+
+        if self.rendered_releases_end() < self.releases.len() {
+            self.fmt_history_link(f)?;
+        }
+
+        self.fmt_trailer(f, drafts)
+    }
+}
+
+impl Display for Changelog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_changelog(f, false)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs;
 
-    use chrono::NaiveDate;
     use log::LevelFilter;
     use log4rs_test_utils::test_logging;
     use rstest::rstest;
     use uuid::Uuid;
 
     use super::*;
+    use crate::{
+        changes::ChangeEntry, conflict::MergeConflict, error::ParseError, format::EntryTransform,
+        format::HeadingStyle, release_date::ReleaseDate, validation::ValidationOptions,
+    };
 
     fn are_the_same(file_a: &str, file_b: &str) -> Result<bool> {
         let file_a_contents = fs::read_to_string(file_a)?;
@@ -616,9 +1854,7 @@ mod test {
 
         let mut changelog = ChangelogBuilder::default()
             .flag("test flag".to_string())
-            .url(Some(
-                "https://github.com/napalmpapalam/keep-a-changelog-rs".to_string(),
-            ))
+            .url("https://github.com/napalmpapalam/keep-a-changelog-rs".to_string())
             .build()?;
 
         if compact {
@@ -632,7 +1868,7 @@ mod test {
 
         let mut release = Release::builder()
             .version(Version::parse("0.1.0")?)
-            .date(NaiveDate::from_ymd_opt(2024, 4, 28).unwrap())
+            .date(Date::new(2024, 4, 28).unwrap())
             .build()?;
 
         release.added("Initial release".to_string());
@@ -643,7 +1879,7 @@ mod test {
 
         let mut release = Release::builder()
             .version(Version::parse("0.1.1")?)
-            .date(NaiveDate::from_ymd_opt(2024, 5, 18).unwrap())
+            .date(Date::new(2024, 5, 18).unwrap())
             .build()?;
 
         release.fixed("Parsing anchor links in the middle of the file".to_string());
@@ -655,7 +1891,7 @@ mod test {
 
         let mut release = Release::builder()
             .version(Version::parse("0.1.2")?)
-            .date(NaiveDate::from_ymd_opt(2024, 5, 20).unwrap())
+            .date(Date::new(2024, 5, 20).unwrap())
             .build()?;
 
         release.fixed("Default changelog description".to_string());
@@ -693,9 +1929,7 @@ mod test {
 
         let mut changelog = ChangelogBuilder::default()
             .flag("test flag".to_string())
-            .url(Some(
-                "https://github.com/napalmpapalam/keep-a-changelog-rs".to_string(),
-            ))
+            .url("https://github.com/napalmpapalam/keep-a-changelog-rs".to_string())
             .build()?;
 
         if compact {
@@ -709,7 +1943,7 @@ mod test {
 
         let mut release = Release::builder()
             .version(Version::parse("0.1.0")?)
-            .date(NaiveDate::from_ymd_opt(2024, 4, 28).unwrap())
+            .date(Date::new(2024, 4, 28).unwrap())
             .build()?;
 
         release.added("Initial release".to_string());
@@ -720,7 +1954,7 @@ mod test {
 
         let mut release = Release::builder()
             .version(Version::parse("0.1.1")?)
-            .date(NaiveDate::from_ymd_opt(2024, 5, 18).unwrap())
+            .date(Date::new(2024, 5, 18).unwrap())
             .build()?;
 
         release.fixed("Parsing anchor links in the middle of the file".to_string());
@@ -732,7 +1966,7 @@ mod test {
 
         let mut release = Release::builder()
             .version(Version::parse("0.1.2")?)
-            .date(NaiveDate::from_ymd_opt(2024, 5, 20).unwrap())
+            .date(Date::new(2024, 5, 20).unwrap())
             .build()?;
 
         release.fixed("Default changelog description".to_string());
@@ -775,6 +2009,8 @@ mod test {
     #[case("tests/data/initial_changelog_unreleased_compact.md")]
     #[case("tests/data/early_changelog_compact.md")]
     #[case("tests/data/early_changelog_multiple_sections_compact.md")]
+    #[case("tests/data/cargo_release_changelog.md")]
+    #[case("tests/data/year_grouped_changelog.md")]
     fn test_save_to_file(#[case] test_input_file: &str) -> Result<()> {
         test_logging::init_logging_once_for(vec![], LevelFilter::Debug, None);
 
@@ -799,6 +2035,541 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_changes_since() -> Result<()> {
+        let changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+
+        let changes = changelog.changes_since(&ReleaseVersion::parse("0.1.1")?, true);
+        let fixed = changes
+            .iter()
+            .filter(|(kind, _)| *kind == ChangeKind::Fixed)
+            .map(|(_, change)| change.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fixed,
+            vec![
+                "Bug fix to one old feature",
+                "Bug fix to another old feature",
+                "Default changelog description",
+                "Changelog builder error when title and description are not provided",
+            ]
+        );
+        assert_eq!(changes.iter().count(), 6);
+
+        let changes = changelog.changes_since(&ReleaseVersion::parse("0.1.2")?, false);
+        assert!(changes.is_empty());
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("### Fixed\n- Bug fix", "1.2.3", "1.2.4")]
+    #[case("### Added\n- Feature", "1.2.3", "1.3.0")]
+    #[case("### Breaking Changes\n- Drop API", "1.2.3", "2.0.0")]
+    #[case("### Fixed\n- **BREAKING** Strict parsing", "1.2.3", "2.0.0")]
+    #[case("### Breaking Changes\n- Drop API", "0.2.3", "0.3.0")]
+    fn test_suggest_next_version(
+        #[case] changes: &str,
+        #[case] latest: &str,
+        #[case] expected: &str,
+    ) -> Result<()> {
+        let markdown = format!(
+            "# Changelog\n## [Unreleased]\n{changes}\n\n## [{latest}] - 2024-04-28\n### Added\n- Initial release\n"
+        );
+        let changelog = Changelog::parse(markdown, None)?;
+
+        assert_eq!(
+            changelog.suggest_next_version(),
+            Some(Version::parse(expected)?)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_breaking_changes_round_trip() -> Result<()> {
+        let markdown = "# Changelog\n\nAll notable changes.\n\n## [Unreleased]\n\n### Breaking Changes\n\n- Drop API\n\n### Added\n\n- Feature\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        let unreleased = changelog.get_unreleased().unwrap();
+        assert_eq!(
+            unreleased.breaking_changes(),
+            vec![ChangeEntry::new(ChangeKind::Breaking, "Drop API")]
+        );
+        assert_eq!(changelog.file_contents(), markdown);
+        Ok(())
+    }
+
+    #[test]
+    fn test_squash_releases_errors() -> Result<()> {
+        let mut changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+
+        let result = changelog.squash_releases(
+            Version::new(0, 1, 1)..=Version::new(0, 1, 2),
+            Version::new(0, 1, 0),
+        );
+        assert!(result.is_err());
+
+        let result = changelog.squash_releases(Version::new(2, 0, 0).., Version::new(3, 0, 0));
+        assert!(result.is_err());
+        assert_eq!(changelog.releases().len(), 4);
+
+        changelog.squash_releases(
+            Version::new(0, 1, 1)..=Version::new(0, 1, 2),
+            Version::new(0, 1, 2),
+        )?;
+        assert_eq!(changelog.releases().len(), 3);
+        assert_eq!(
+            changelog.releases()[1].description().as_deref(),
+            Some("Includes 0.1.1–0.1.2.")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_max_releases() -> Result<()> {
+        let mut changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+        changelog.set_format_options(FormatOptions {
+            max_releases: Some(1),
+            ..Default::default()
+        });
+
+        let output = changelog.file_contents();
+        assert!(output.contains("## [Unreleased]\n"));
+        assert!(output.contains("## [0.1.2] - 2024-05-20\n"));
+        assert!(!output.contains("## [0.1.1]"));
+        assert!(output.contains("- Changelog builder error when title and description are not provided\n\nSee the [full history](https://github.com/napalmpapalam/keep-a-changelog-rs/blob/HEAD/CHANGELOG.md) for older releases.\n\n[Unreleased]:"));
+        assert!(!output.contains("[0.1.1]:"));
+        assert_eq!(changelog.releases().len(), 4);
+
+        changelog.set_format_options(FormatOptions {
+            max_releases: Some(3),
+            ..Default::default()
+        });
+        assert!(!changelog.file_contents().contains("full history"));
+
+        let gitlab = fs::read_to_string("tests/data/early_changelog_multiple_sections.md")?
+            .replace("https://github.com", "https://gitlab.com");
+        let mut changelog = Changelog::parse(gitlab, None)?;
+        changelog.set_format_options(FormatOptions {
+            max_releases: Some(1),
+            ..Default::default()
+        });
+        assert!(changelog.file_contents().contains("See the [full history](https://gitlab.com/napalmpapalam/keep-a-changelog-rs/-/blob/HEAD/CHANGELOG.md) for older releases."));
+        Ok(())
+    }
+
+    #[test]
+    fn test_save_releases_to_dir() -> Result<()> {
+        let changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+        let dir = format!("tests/tmp/releases_{}", Uuid::new_v4());
+
+        changelog.save_releases_to_dir(&dir, |release| match release.version() {
+            Some(version) => format!("{version}.md"),
+            None => "unreleased.md".to_string(),
+        })?;
+
+        assert_eq!(fs::read_dir(&dir)?.count(), 4);
+        assert_eq!(
+            fs::read_to_string(format!("{dir}/0.1.1.md"))?,
+            "## [0.1.1] - 2024-05-18\n\n### Fixed\n\n- Parsing anchor links in the middle of the file\n- Error readability\n\n[0.1.1]: https://github.com/napalmpapalam/keep-a-changelog-rs/compare/0.1.0...0.1.1\n"
+        );
+        assert!(fs::read_to_string(format!("{dir}/unreleased.md"))?
+            .ends_with("\n[Unreleased]: https://github.com/napalmpapalam/keep-a-changelog-rs/compare/0.1.2...HEAD\n"));
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_init() -> Result<()> {
+        let dir = format!("tests/tmp/init_{}", Uuid::new_v4());
+        fs::create_dir_all(&dir)?;
+        let path = format!("{dir}/CHANGELOG.md");
+        let opts = InitOptions {
+            url: Some("https://github.com/owner/repo".to_string()),
+            tag_prefix: Some("v".to_string()),
+            initial_release: Date::new(2024, 4, 28),
+        };
+
+        Changelog::init(&path, opts.clone())?;
+        assert!(Changelog::init(&path, opts).is_err());
+
+        let contents = fs::read_to_string(&path)?;
+        assert!(contents.starts_with(&format!("# Changelog\n\n{CHANGELOG_DESCRIPTION}\n\n")));
+        assert!(contents.contains(
+            "## [Unreleased]\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n"
+        ));
+        assert!(contents.ends_with(
+            "[Unreleased]: https://github.com/owner/repo/compare/v0.1.0...HEAD\n[0.1.0]: https://github.com/owner/repo/releases/tag/v0.1.0\n"
+        ));
+
+        let changelog = Changelog::parse_from_file(&path, None)?;
+        assert!(changelog.validate(&ValidationOptions::default()).is_empty());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::trailing_slash(
+        "https://github.com/owner/repo/",
+        Some("https://github.com/owner/repo")
+    )]
+    #[case::scheme("ftp://example.com/repo", None)]
+    #[case::relative("owner/repo", None)]
+    #[case::no_host("https:///repo", None)]
+    fn test_builder_url(#[case] url: &str, #[case] expected: Option<&str>) {
+        let changelog = ChangelogBuilder::default()
+            .url(Some(url.to_string()))
+            .build();
+
+        assert_eq!(
+            changelog.ok().and_then(|c| c.url().clone()).as_deref(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_builder_validation() -> Result<()> {
+        let release = |version: Option<&str>| -> Result<Release> {
+            let mut builder = Release::builder();
+            builder.date(Date::new(2024, 1, 1).unwrap());
+            if let Some(version) = version {
+                builder.version(Version::parse(version)?);
+            }
+            Ok(builder.build()?)
+        };
+
+        let error = ChangelogBuilder::default()
+            .releases(vec![
+                release(Some("1.0.0"))?,
+                release(Some("1.0.0"))?,
+                release(None)?,
+            ])
+            .head(String::new())
+            .tag_prefix("release v".to_string())
+            .build()
+            .unwrap_err();
+
+        assert_eq!(
+            error.problems(),
+            [
+                BuildProblem::DuplicateVersion(Version::new(1, 0, 0).into()),
+                BuildProblem::MissingVersion(Date::new(2024, 1, 1).unwrap()),
+                BuildProblem::EmptyHead,
+                BuildProblem::InvalidTagPrefix("release v".to_string()),
+            ]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::regular("# Changelog\n\nNotes.\n\n## [1.0.0] - 2024-06-01\n\n### Fixed\n\n- Bug fix\n\n### Contributors\n\n- @octocat\n- Jane Doe\n")]
+    #[case::compact("<!-- markdownlint-disable MD022 MD032 -->\n# Changelog\nNotes.\n\n## [1.0.0] - 2024-06-01\n### Contributors\n- @octocat\n- Jane Doe\n")]
+    fn test_contributors_round_trip(#[case] markdown: &str) -> Result<()> {
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+        let release = changelog.releases().first().unwrap();
+
+        assert_eq!(release.contributors(), &["@octocat", "Jane Doe"]);
+        assert!(!release.changes().iter().any(|(_, c)| c.contains("octocat")));
+        assert_eq!(changelog.file_contents(), markdown);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_link_definitions() -> Result<()> {
+        let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial [docs]\n\n[docs]: <https://example.com/docs> \"Documentation\"\n[Unreleased]:\n    <https://github.com/owner/repo/compare/0.1.0...HEAD>\n    'Unreleased changes'\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        assert_eq!(
+            changelog.url().as_deref(),
+            Some("https://github.com/owner/repo")
+        );
+        assert_eq!(changelog.links().len(), 2);
+        assert_eq!(
+            changelog.links()[0].to_string(),
+            "[docs]: https://example.com/docs \"Documentation\""
+        );
+        assert_eq!(
+            changelog.links()[1].title().as_deref(),
+            Some("Unreleased changes")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_non_http_links() -> Result<()> {
+        let markdown = "# Changelog\n## [Unreleased]\n### Changed\n- See [migration guide][migration], questions to [security]\n\n[migration]: ./docs/MIGRATION.md\n[security]: mailto:security@example.com\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        let links = changelog
+            .links()
+            .iter()
+            .map(|link| link.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            vec![
+                "[migration]: ./docs/MIGRATION.md",
+                "[security]: mailto:security@example.com"
+            ]
+        );
+        assert!(changelog.to_string().ends_with(
+            "\n[migration]: ./docs/MIGRATION.md\n[security]: mailto:security@example.com\n"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_links_output_order() -> Result<()> {
+        let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n\n[zeta]: https://example.com/zeta\n[0.1.0]: https://github.com/owner/repo/releases/tag/0.1.0\n[Alpha]: https://example.com/old\n[alpha]: https://example.com/alpha\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        assert_eq!(changelog.links().len(), 3);
+        assert!(changelog
+            .to_string()
+            .ends_with("\n[alpha]: https://example.com/alpha\n[zeta]: https://example.com/zeta\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_inline_links_round_trip() -> Result<()> {
+        let markdown = "# Changelog\n\n## [Unreleased](https://github.com/owner/repo/compare/0.2.0...HEAD)\n\n### Added\n\n- Feature\n\n## [0.2.0](https://github.com/owner/repo/compare/0.1.0...0.2.0) - 2024-05-01\n\n### Fixed\n\n- Bug fix\n\n## [0.1.0](https://github.com/owner/repo/releases/tag/0.1.0) - 2024-04-28\n\n### Added\n\n- Initial release\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        assert!(changelog.format().inline_links);
+        assert_eq!(
+            changelog.url().as_deref(),
+            Some("https://github.com/owner/repo")
+        );
+
+        let output = changelog.file_contents();
+        assert!(output.ends_with(&markdown["# Changelog\n\n".len()..]));
+        assert!(!output.contains("]: "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_links_skip_yanked() -> Result<()> {
+        let markdown = "# Changelog\n## [0.3.0] - 2024-06-01\n### Fixed\n- Bug fix\n## [0.2.0] - 2024-05-01 [YANKED]\n### Added\n- Feature\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+        let mut changelog = Changelog::parse(
+            markdown.to_string(),
+            Some(ChangelogParseOptions {
+                url: Some("https://github.com/owner/repo".to_string()),
+                ..Default::default()
+            }),
+        )?;
+
+        let compare_url = |changelog: &Changelog| -> Result<String> {
+            let link = changelog.releases()[0].compare_link(changelog)?;
+            Ok(link.map(|link| link.url).unwrap_or_default())
+        };
+
+        assert_eq!(
+            compare_url(&changelog)?,
+            "https://github.com/owner/repo/compare/0.2.0...0.3.0"
+        );
+
+        changelog.set_format_options(FormatOptions {
+            skip_yanked: true,
+            ..Default::default()
+        });
+        assert_eq!(
+            compare_url(&changelog)?,
+            "https://github.com/owner/repo/compare/0.1.0...0.3.0"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tag_name_resolver() -> Result<()> {
+        let release = |version: &str, date: (i32, u32, u32)| -> Result<Release> {
+            Ok(Release::builder()
+                .version(Version::parse(version)?)
+                .date(Date::new(date.0, date.1, date.2).unwrap())
+                .build()?)
+        };
+
+        let changelog = ChangelogBuilder::default()
+            .url(Some("https://github.com/owner/repo".to_string()))
+            .tag_prefix(Some("v".to_string()))
+            .tag_name_resolver(|version: &ReleaseVersion| format!("release-{version}"))
+            .releases(vec![
+                release("1.0.0", (2024, 4, 28))?,
+                release("1.1.0", (2024, 6, 1))?,
+            ])
+            .build()?;
+
+        let urls = changelog
+            .releases()
+            .iter()
+            .map(|r| r.compare_link(&changelog).map(|l| l.unwrap().url))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(
+            urls,
+            [
+                "https://github.com/owner/repo/compare/release-1.0.0...release-1.1.0",
+                "https://github.com/owner/repo/releases/tag/release-1.0.0",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_entry_transform() -> Result<()> {
+        let markdown = "# Changelog\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release #1\n\n### Security\n\n- Escape titles for ACME Corp\n";
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+        let transform = EntryTransform::new(|kind, text| match kind {
+            ChangeKind::Security => text.replace("ACME Corp", "a customer"),
+            _ => text.replace("#1", "([#1](https://github.com/owner/repo/issues/1))"),
+        });
+        changelog.set_format_options(FormatOptions {
+            transform: Some(transform),
+            ..Default::default()
+        });
+
+        let output = changelog.to_string();
+        assert!(
+            output.contains("- Initial release ([#1](https://github.com/owner/repo/issues/1))\n")
+        );
+        assert!(output.contains("- Escape titles for a customer\n"));
+        let contents = changelog.file_contents();
+        assert!(contents.contains("- Initial release #1\n"));
+        assert!(contents.contains("- Escape titles for ACME Corp\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_hide_empty_releases() -> Result<()> {
+        let markdown = "# Changelog\n\n## [Unreleased]\n\n## [0.2.0] - 2024-05-01\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n";
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+        assert!(changelog
+            .to_string()
+            .contains("## [Unreleased]\n\n## [0.2.0]"));
+
+        changelog.set_format_options(FormatOptions {
+            hide_empty_unreleased: true,
+            ..Default::default()
+        });
+        let output = changelog.to_string();
+        assert!(!output.contains("## [Unreleased]"));
+        assert!(output.contains("## [0.2.0] - 2024-05-01\n\n## [0.1.0]"));
+
+        changelog.set_format_options(FormatOptions {
+            hide_empty_unreleased: true,
+            hide_empty_sections: true,
+            ..Default::default()
+        });
+        let output = changelog.to_string();
+        assert!(!output.contains("## [0.2.0]"));
+        assert!(output.contains("## [0.1.0] - 2024-04-28"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_draft_release() -> Result<()> {
+        let markdown = "# Changelog\n\n## [0.2.0] - 2024-05-01\n<!-- draft -->\n\n### Added\n\n- Embargoed feature\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n\n[0.2.0]: https://github.com/owner/repo/compare/0.1.0...0.2.0\n[0.1.0]: https://github.com/owner/repo/releases/tag/0.1.0\n";
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        assert!(changelog.releases()[0].draft());
+        assert!(changelog
+            .file_contents()
+            .ends_with(&markdown["# Changelog\n\n".len()..]));
+
+        let output = changelog.to_string();
+        assert!(!output.contains("0.2.0"));
+        assert!(!changelog.to_rst().contains("0.2.0"));
+
+        changelog.releases_mut()[0].set_draft(false);
+        assert!(changelog
+            .to_string()
+            .contains("## [0.2.0] - 2024-05-01\n\n### Added"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_release_metadata() -> Result<()> {
+        let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n<!-- kac: build=42 sha=abc123 -->\n### Added\n- Initial release\n";
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        let release = &mut changelog.releases_mut()[0];
+        assert_eq!(
+            release.metadata().get("sha").map(String::as_str),
+            Some("abc123")
+        );
+        release
+            .metadata_mut()
+            .insert("approved by".to_string(), "Jane \"JD\" Doe".to_string());
+
+        let output = changelog.to_string();
+        assert!(output.contains("## [0.1.0] - 2024-04-28\n<!-- kac: \"approved by\"=\"Jane \\\"JD\\\" Doe\" build=42 sha=abc123 -->\n"));
+        assert_eq!(
+            Changelog::parse(output, None)?.releases()[0]
+                .metadata()
+                .len(),
+            3
+        );
+
+        let invalid = markdown.replace("build=42", "build=\"42");
+        assert!(Changelog::parse(invalid, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_base() -> Result<()> {
+        let markdown = "# Changelog\n\n## [Unreleased]\n<!-- compare-base: main -->\n\n### Added\n\n- Feature\n\n## [1.1.0] - 2024-05-01\n<!-- compare-base: fork-point -->\n\n### Fixed\n\n- Bug fix\n\n## [1.0.0] - 2024-04-28\n\n### Added\n\n- Initial release\n\n[Unreleased]: https://github.com/owner/repo/compare/main...HEAD\n[1.1.0]: https://github.com/owner/repo/compare/fork-point...v1.1.0\n[1.0.0]: https://github.com/owner/repo/releases/tag/v1.0.0\n";
+        let mut changelog = Changelog::parse(
+            markdown.to_string(),
+            Some(ChangelogParseOptions {
+                tag_prefix: Some("v".to_string()),
+                ..Default::default()
+            }),
+        )?;
+
+        assert_eq!(
+            changelog.releases()[1].compare_base().as_deref(),
+            Some("fork-point")
+        );
+        assert!(changelog
+            .file_contents()
+            .ends_with(&markdown["# Changelog\n\n".len()..]));
+
+        changelog.releases_mut()[1].set_compare_base("v1.0.0-rewritten");
+        let link = changelog.releases()[1].compare_link(&changelog)?.unwrap();
+        assert_eq!(
+            link.url(),
+            "https://github.com/owner/repo/compare/v1.0.0-rewritten...v1.1.0"
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::reference("## [Unreleased]\n### Added\n- Feature\n## [1.2.3] - 2024-05-01\n### Fixed\n- Bug fix\n\n[Unreleased]: https://github.com/owner/repo/compare/v1.2.3...develop\n[1.2.3]: https://github.com/owner/repo/releases/tag/v1.2.3\n")]
+    #[case::inline("## [Unreleased](https://gitlab.com/owner/repo/-/compare/v1.2.3...develop)\n### Added\n- Feature\n## [1.2.3](https://gitlab.com/owner/repo/-/releases/tag/v1.2.3) - 2024-05-01\n### Fixed\n- Bug fix\n")]
+    fn test_infer_head(#[case] releases: &str) -> Result<()> {
+        let markdown = format!("# Changelog\n{releases}");
+        let opts = ChangelogParseOptions {
+            tag_prefix: Some("v".to_string()),
+            ..Default::default()
+        };
+        let changelog = Changelog::parse(markdown.clone(), Some(opts.clone()))?;
+        assert_eq!(changelog.head(), "develop");
+        assert!(changelog.to_string().contains("compare/v1.2.3...develop"));
+
+        let changelog = Changelog::parse(
+            markdown,
+            Some(ChangelogParseOptions {
+                head: Some("main".to_string()),
+                ..opts
+            }),
+        )?;
+        assert_eq!(changelog.head(), "main");
+        Ok(())
+    }
+
     #[test]
     fn test_add_link() {
         // Create a new ChangelogBuilder instance
@@ -816,4 +2587,381 @@ mod test {
             "https://example.com"
         );
     }
+
+    #[test]
+    fn test_parse_merge_conflict() {
+        let markdown = "# Changelog\n\
+            ## [Unreleased]\n\
+            ### Added\n\
+            <<<<<<< HEAD\n\
+            - Feature A\n\
+            =======\n\
+            - Feature B\n\
+            >>>>>>> feature-b\n";
+
+        let err = Changelog::parse(markdown.to_string(), None).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("ParseError");
+        assert_eq!(
+            err,
+            &ParseError::MergeConflict(vec![MergeConflict {
+                start: 4,
+                separator: 6,
+                end: 8,
+            }])
+        );
+
+        let (ours, theirs) = Changelog::parse_conflict_sides(markdown.to_string(), None).unwrap();
+        let entries = |changelog: &Changelog| changelog.get_unreleased().unwrap().to_string();
+        assert!(entries(&ours).contains("- Feature A"));
+        assert!(!entries(&ours).contains("- Feature B"));
+        assert!(entries(&theirs).contains("- Feature B"));
+        assert!(!entries(&theirs).contains("- Feature A"));
+    }
+
+    #[test]
+    fn test_parse_multiple_documents() -> Result<()> {
+        let markdown = "# Changelog\n\n## [2.0.0] - 2024-01-10\n\n- Major release\n\n\
+            # Vendored\n\n## [0.1.0] - 2023-01-10\n\n- Initial release\n";
+
+        let err = Changelog::parse(markdown.to_string(), None).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().expect("ParseError");
+        assert_eq!(err, &ParseError::MultipleDocuments(vec![1, 7]));
+
+        let changelog = Changelog::parse(
+            markdown.to_string(),
+            Some(ChangelogParseOptions {
+                first_document_only: true,
+                ..Default::default()
+            }),
+        )?;
+        assert_eq!(changelog.title().as_deref(), Some("Changelog"));
+        assert_eq!(changelog.releases().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_many() -> Result<()> {
+        let paths = fs::read_dir("tests/data")?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let changelogs = Changelog::parse_many(&paths, None);
+        assert_eq!(changelogs.len(), paths.len());
+        for (path, changelog) in paths.iter().zip(changelogs) {
+            let expected = Changelog::parse_from_file(path.to_str().unwrap(), None)?;
+            assert!(changelog?.semantic_eq(&expected));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_non_semver_versions() -> Result<()> {
+        let markdown = "# Changelog\n\nNotes.\n\n## [Unreleased]\n\n\
+            ## [2024.06.1] - 2024-06-01\n\n### Fixed\n\n- Bug fix\n\n\
+            ## [42] - 2024-05-01\n\n### Added\n\n- Feature\n\n\
+            [Unreleased]: https://github.com/owner/repo/compare/2024.06.1...HEAD\n\
+            [2024.06.1]: https://github.com/owner/repo/compare/42...2024.06.1\n\
+            [42]: https://github.com/owner/repo/releases/tag/42\n";
+
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+        assert_eq!(changelog.to_string(), markdown);
+        assert!(matches!(
+            changelog.releases()[1].version(),
+            Some(ReleaseVersion::CalVer(_))
+        ));
+        assert!(changelog.find_release("42".to_string())?.is_some());
+        assert_eq!(changelog.suggest_next_version(), None);
+
+        changelog.promote_unreleased(
+            ReleaseVersion::parse("2024.06.2")?,
+            Date::new(2024, 6, 1).unwrap(),
+        )?;
+        let versions = changelog
+            .releases()
+            .iter()
+            .map(|r| r.version().as_ref().map(|v| v.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            vec![
+                None,
+                Some("2024.06.2".to_string()),
+                Some("2024.06.1".to_string()),
+                Some("42".to_string())
+            ]
+        );
+
+        let opts = ChangelogParseOptions {
+            strict_versions: true,
+            ..Default::default()
+        };
+        assert!(Changelog::parse(markdown.to_string(), Some(opts.clone())).is_err());
+
+        // the sidecar doesn't bypass the checks of the parser
+        #[cfg(feature = "json")]
+        {
+            let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+            changelog.set_format_options(FormatOptions {
+                sidecar: true,
+                ..Default::default()
+            });
+            assert!(Changelog::parse(changelog.file_contents(), Some(opts)).is_err());
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::single_digits("2024-6-3")]
+    #[case::slashes("2024/06/03")]
+    #[case::month_name("June 3, 2024")]
+    #[case::day_first("3 Jun 2024")]
+    fn test_lenient_dates(#[case] date: &str) -> Result<()> {
+        let markdown = format!("# Changelog\n\n## [1.0.0] - {date}\n\n### Added\n\n- Feature\n");
+        let opts = ChangelogParseOptions {
+            lenient_dates: true,
+            ..Default::default()
+        };
+
+        let changelog = Changelog::parse(markdown, Some(opts.clone()))?;
+        assert!(changelog.to_string().contains("## [1.0.0] - 2024-06-03\n"));
+        assert_eq!(
+            changelog.normalized_dates(),
+            &[NormalizedDate {
+                line: 3,
+                original: date.to_string(),
+                date: Date::new(2024, 6, 3).unwrap(),
+            }]
+        );
+
+        let iso = Changelog::parse(
+            "# Changelog\n## [1.0.0] - 2024-06-03\n".to_string(),
+            Some(opts),
+        )?;
+        assert!(iso.normalized_dates().is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_recovering() {
+        let markdown = "# Changelog\n\n## [1.0] - 2024-06-01\n\n### Fixed\n\n- Bug fix\n\n\
+            ## [0.2.0] - 2024-13-01\n\n### Improved\n\n- Performance\n\n\
+            ## Version 0.1.0\n\n## [0.0.1] - 2024-01-01\n\n### Added\n\n- Initial release\n";
+
+        let opts = ChangelogParseOptions {
+            strict_versions: true,
+            ..Default::default()
+        };
+        let errors =
+            Changelog::parse_recovering(markdown.to_string(), Some(opts.clone())).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ParseError::InvalidVersion {
+                    line: 3,
+                    version: "1.0".to_string()
+                },
+                ParseError::InvalidDate {
+                    line: 9,
+                    date: "2024-13-01".to_string()
+                },
+                ParseError::UnknownSection {
+                    line: 11,
+                    heading: "Improved".to_string()
+                },
+                ParseError::InvalidRelease {
+                    line: 15,
+                    heading: "Version 0.1.0".to_string()
+                },
+            ]
+        );
+
+        let err = Changelog::parse(markdown.to_string(), Some(opts)).unwrap_err();
+        assert_eq!(err.downcast_ref::<ParseError>(), Some(&errors[0]));
+    }
+
+    #[rstest]
+    #[case::formatted(false, "## [0.1.1] - 2024-05-01\n\nNo notable changes.\n\n## [0.1.0]")]
+    #[case::compact(true, "## [0.1.1] - 2024-05-01\nNo notable changes.\n\n## [0.1.0]")]
+    fn test_no_notable_changes(#[case] compact: bool, #[case] expected: &str) -> Result<()> {
+        let mut changelog = Changelog::parse(
+            "# Changelog\n## [Unreleased]\n### Fixed\n- Unused fix\n\
+            \n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n"
+                .to_string(),
+            Some(ChangelogParseOptions {
+                url: Some("https://github.com/owner/repo".to_string()),
+                ..Default::default()
+            }),
+        )?;
+        match compact {
+            true => changelog.set_compact(),
+            false => changelog.unset_compact(),
+        };
+        changelog
+            .get_unreleased_mut()
+            .unwrap()
+            .set_no_notable_changes();
+
+        let version = ReleaseVersion::parse("0.1.1")?;
+        let date = Date::new(2024, 5, 1).unwrap();
+        assert_eq!(
+            changelog.assert_releasable(version.clone(), Some(date)),
+            Ok(())
+        );
+        changelog.promote_unreleased(version, date)?;
+
+        let rendered = changelog.to_string();
+        assert!(rendered.contains(expected));
+
+        let parsed = Changelog::parse(rendered.clone(), None)?;
+        assert!(parsed.releases()[1].is_empty_release());
+        assert_eq!(parsed.to_string(), rendered);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::strict(false)]
+    #[case::lenient(true)]
+    fn test_month_dates(#[case] lenient_dates: bool) -> Result<()> {
+        let markdown = "# Changelog\n## [0.3.0] - 2019-03-14\n### Fixed\n- Bug fix\n\
+            \n## [0.2.0] - 2019-02\n### Added\n- Plugins\n\
+            \n## [0.1.0] - 2018-12\n### Added\n- Initial release\n";
+        let opts = ChangelogParseOptions {
+            lenient_dates,
+            ..Default::default()
+        };
+        let mut changelog = Changelog::parse(markdown.to_string(), Some(opts))?;
+        assert!(changelog.normalized_dates().is_empty());
+
+        let dates = changelog
+            .releases()
+            .iter()
+            .map(|release| release.release_date())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dates,
+            [
+                ReleaseDate::Day(Date::new(2019, 3, 14).unwrap()),
+                ReleaseDate::Month(2019, 2),
+                ReleaseDate::Month(2018, 12),
+            ]
+        );
+        assert!(changelog.to_string().contains("## [0.1.0] - 2018-12\n"));
+
+        changelog.squash_releases(
+            Version::new(0, 1, 0)..=Version::new(0, 2, 0),
+            Version::new(0, 2, 0),
+        )?;
+        let squashed = changelog.find_release("0.2.0".to_string())?.unwrap();
+        assert_eq!(squashed.release_date(), ReleaseDate::Month(2019, 2));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::preserve(
+        HeadingStyle::Preserve,
+        "## Unreleased\n\n## 0.2.0 - 2024-06-01\n\n### Fixed\n\n- Bug fix\n\n## [0.1.0] - 2024-04-28\n"
+    )]
+    #[case::bracketed(
+        HeadingStyle::Bracketed,
+        "## [Unreleased]\n\n## [0.2.0] - 2024-06-01\n\n### Fixed\n\n- Bug fix\n\n## [0.1.0] - 2024-04-28\n"
+    )]
+    #[case::plain(
+        HeadingStyle::Plain,
+        "## Unreleased\n\n## 0.2.0 - 2024-06-01\n\n### Fixed\n\n- Bug fix\n\n## 0.1.0 - 2024-04-28\n"
+    )]
+    fn test_heading_style(
+        #[case] heading_style: HeadingStyle,
+        #[case] expected: &str,
+    ) -> Result<()> {
+        let markdown = "# Changelog\n\n## Unreleased\n\n### Fixed\n\n- Bug fix\n\n\
+            ## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n";
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+        changelog.set_format_options(FormatOptions {
+            heading_style,
+            ..Default::default()
+        });
+
+        let date = Date::new(2024, 6, 1).unwrap();
+        changelog.promote_unreleased(Version::new(0, 2, 0), date)?;
+        assert!(changelog.to_string().contains(expected));
+        Ok(())
+    }
+
+    #[test]
+    fn test_setext_headings() -> Result<()> {
+        let markdown = "Changelog\n=========\n\nAll notable changes.\n\n\
+            [0.1.0] - 2024-04-28\n--------------------\n\n### Added\n\n- Initial release\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        assert_eq!(changelog.releases().len(), 1);
+        assert_eq!(
+            changelog.to_string(),
+            "# Changelog\n\nAll notable changes.\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n\
+                - Initial release\n\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_release_eq_ignores_render_settings() -> Result<()> {
+        let markdown = "# Changelog\n## [1.0.0] - 2024-01-01\n### Added\n- Initial release\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+        let release = &changelog.releases()[0];
+
+        let mut rendered = release.clone();
+        rendered.set_compact(true).set_format(FormatOptions {
+            heading_style: HeadingStyle::Plain,
+            ..Default::default()
+        });
+        assert_eq!(&rendered, release);
+        assert_ne!(rendered.to_string(), release.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_closed_headings() -> Result<()> {
+        let markdown =
+            "# Changelog #\n\n## [1.0.0] - 2024-01-01 ##\n\n### Added ###\n\n- Initial release\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        let release = &changelog.releases()[0];
+        assert_eq!(release.anchor(), "1.0.0");
+        assert_eq!(release.date(), &Date::new(2024, 1, 1));
+        let rendered = changelog.to_string();
+        assert!(rendered.starts_with("# Changelog\n"));
+        assert!(rendered.contains("## [1.0.0] - 2024-01-01\n\n### Added\n\n- Initial release\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tbd_release() -> Result<()> {
+        let markdown = "# Changelog\n## [Unreleased]\n### Fixed\n- Bug fix\n\
+            \n## [1.2.0] - 2024-04-28\n### Added\n- Themes\n\
+            \n[unreleased]: https://github.com/owner/repo/compare/1.2.0...HEAD\n\
+            [1.2.0]: https://github.com/owner/repo/releases/tag/1.2.0\n";
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        let mut planned = Release::builder().version(Version::new(1, 3, 0)).build()?;
+        planned.added("Plugins".to_string());
+        changelog.add_release(planned);
+
+        let versions = changelog
+            .releases()
+            .iter()
+            .map(|release| release.anchor())
+            .collect::<Vec<_>>();
+        assert_eq!(versions, ["Unreleased", "1.3.0", "1.2.0"]);
+
+        let rendered = changelog.to_string();
+        assert!(rendered.contains("## [1.3.0] - TBD\n"));
+        assert!(rendered.contains("[1.3.0]: https://github.com/owner/repo/compare/1.2.0...HEAD\n"));
+        assert!(
+            rendered.contains("[Unreleased]: https://github.com/owner/repo/compare/1.2.0...HEAD\n")
+        );
+
+        let parsed = Changelog::parse(rendered.replace("- TBD", "- Unreleased"), None)?;
+        assert!(parsed.releases()[1].is_tbd());
+        assert_eq!(parsed.to_string(), rendered);
+        Ok(())
+    }
 }
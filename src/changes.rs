@@ -1,17 +1,25 @@
 use std::{
+    collections::BTreeMap,
     fmt::{self, Display, Formatter},
     str::FromStr,
+    sync::OnceLock,
 };
 
+use derive_builder::Builder;
+use derive_getters::Getters;
 use eyre::{bail, Error};
+use regex::Regex;
 
-use crate::utils::substring;
+use crate::{format::FormatOptions, utils::substring};
 
 /// Represents a change kind.
 ///
-/// This is used to categorize changes in a changelog.
+/// This is used to categorize changes in a changelog. New kinds may be added in minor releases.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
 pub enum ChangeKind {
+    /// Dedicated `### Breaking Changes` section
+    Breaking,
     Added,
     Changed,
     Deprecated,
@@ -25,6 +33,7 @@ impl FromStr for ChangeKind {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_lowercase().as_str() {
+            "breaking changes" | "breaking" => Ok(Self::Breaking),
             "added" => Ok(Self::Added),
             "changed" => Ok(Self::Changed),
             "deprecated" => Ok(Self::Deprecated),
@@ -36,21 +45,269 @@ impl FromStr for ChangeKind {
     }
 }
 
+impl ChangeKind {
+    /// All change kinds, in the order the sections are rendered.
+    pub const ALL: [ChangeKind; 7] = [
+        ChangeKind::Breaking,
+        ChangeKind::Added,
+        ChangeKind::Changed,
+        ChangeKind::Deprecated,
+        ChangeKind::Removed,
+        ChangeKind::Fixed,
+        ChangeKind::Security,
+    ];
+
+    /// Canonical section title, e.g. `Breaking Changes` for [`ChangeKind::Breaking`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::ChangeKind;
+    ///
+    /// let headings = ChangeKind::ALL.iter().map(|kind| kind.heading()).collect::<Vec<_>>();
+    /// assert_eq!(headings[0], "Breaking Changes");
+    /// assert_eq!(ChangeKind::Fixed.to_string(), "Fixed");
+    /// ```
+    pub fn heading(&self) -> &'static str {
+        match self {
+            ChangeKind::Breaking => "Breaking Changes",
+            ChangeKind::Added => "Added",
+            ChangeKind::Changed => "Changed",
+            ChangeKind::Deprecated => "Deprecated",
+            ChangeKind::Removed => "Removed",
+            ChangeKind::Fixed => "Fixed",
+            ChangeKind::Security => "Security",
+        }
+    }
+}
+
+impl Display for ChangeKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.heading())
+    }
+}
+
+impl AsRef<str> for ChangeKind {
+    fn as_ref(&self) -> &str {
+        self.heading()
+    }
+}
+
+/// Prefix marking an entry as a breaking change, e.g. `**BREAKING** Drop support for Rust 1.60`.
+pub const BREAKING_PREFIX: &str = "**BREAKING**";
+
+/// Trailing comment marking an entry as internal, e.g. `Tune the cache size <!-- internal -->`.
+pub const INTERNAL_MARKER: &str = "<!-- internal -->";
+
+/// Audience of a change entry, see [`Changelog::render_public`](crate::Changelog::render_public).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Visibility {
+    #[default]
+    Public,
+    /// The entry ends with [`INTERNAL_MARKER`]
+    Internal,
+}
+
+/// Represents a single change entry with its kind and attributes derived from its text.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{ChangeEntry, ChangeKind, Visibility};
+///
+/// let entry = ChangeEntry::new(ChangeKind::Changed, "**BREAKING** Rename `parse` to `from_str`");
+/// assert!(*entry.breaking());
+///
+/// let entry = ChangeEntry::new(ChangeKind::Breaking, "Drop support for Rust 1.60");
+/// assert!(*entry.breaking());
+///
+/// let entry = ChangeEntry::new(ChangeKind::Fixed, "**parser:** handle nested lists");
+/// assert_eq!(entry.scope().as_deref(), Some("parser"));
+///
+/// let entry = ChangeEntry::new(ChangeKind::Changed, "Tune the cache size <!-- internal -->");
+/// assert_eq!(*entry.visibility(), Visibility::Internal);
+///
+/// let entry = ChangeEntry::new(ChangeKind::Added, "[ops] [api] **jobs:** retry failed jobs");
+/// assert_eq!(entry.audiences(), &["ops", "api"]);
+/// assert_eq!(entry.scope().as_deref(), Some("jobs"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Getters)]
+pub struct ChangeEntry {
+    kind: ChangeKind,
+    text: String,
+    /// Whether the entry is in the Breaking Changes section or prefixed with `**BREAKING**`
+    breaking: bool,
+    /// Scope of the entry written as `**scope:** change`, e.g. `**parser:** handle nested lists`
+    scope: Option<String>,
+    /// Whether the entry is left out of the public changelog
+    visibility: Visibility,
+    /// Lowercased audience tags written as leading bracket tokens, e.g. `[ops] [api] change`,
+    /// see [`Changelog::view`](crate::Changelog::view)
+    audiences: Vec<String>,
+}
+
+impl ChangeEntry {
+    pub fn new<S: Into<String>>(kind: ChangeKind, text: S) -> Self {
+        let text = text.into();
+        let breaking =
+            kind == ChangeKind::Breaking || text.trim_start().starts_with(BREAKING_PREFIX);
+        let scope = parse_scope(&text);
+        let (audiences, _) = split_audiences(strip_breaking(&text));
+        let visibility = match text.trim_end().ends_with(INTERNAL_MARKER) {
+            true => Visibility::Internal,
+            false => Visibility::Public,
+        };
+
+        Self {
+            kind,
+            text,
+            breaking,
+            scope,
+            visibility,
+            audiences,
+        }
+    }
+}
+
+fn parse_scope(text: &str) -> Option<String> {
+    static SCOPE: OnceLock<Regex> = OnceLock::new();
+    let scope = SCOPE.get_or_init(|| Regex::new(r"^\*\*([^*:]+):\*\*\s").unwrap());
+
+    let (_, text) = split_audiences(strip_breaking(text));
+    scope
+        .captures(text)
+        .map(|captures| captures[1].trim().to_string())
+}
+
+/// Text after the `**BREAKING**` prefix
+fn strip_breaking(text: &str) -> &str {
+    let text = text.trim_start();
+    text.strip_prefix(BREAKING_PREFIX)
+        .unwrap_or(text)
+        .trim_start()
+}
+
+/// Leading audience tags, e.g. `[ops] [api] change`, and the text after them
+///
+/// Tags are at least two characters long, so task list items like `[x] done` aren't tagged, and
+/// must be followed by whitespace, so links like `[docs](url)` aren't tags either.
+pub(crate) fn split_audiences(text: &str) -> (Vec<String>, &str) {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    let tag = TAG.get_or_init(|| Regex::new(r"^\[([A-Za-z][\w-]+)\](?:\s+|$)").unwrap());
+
+    let mut audiences = vec![];
+    let mut text = text;
+    while let Some(captures) = tag.captures(text) {
+        audiences.push(captures[1].to_lowercase());
+        text = &text[captures[0].len()..];
+    }
+    (audiences, text)
+}
+
 /// Represents a set of changes.
 ///
 /// This is used to represent a set of changes in a changelog.
-#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, Builder)]
 pub struct Changes {
+    #[builder(setter(custom), default)]
+    breaking: Vec<String>,
+    #[builder(setter(custom), default)]
     added: Vec<String>,
+    #[builder(setter(custom), default)]
     changed: Vec<String>,
+    #[builder(setter(custom), default)]
     deprecated: Vec<String>,
+    #[builder(setter(custom), default)]
     removed: Vec<String>,
+    #[builder(setter(custom), default)]
     fixed: Vec<String>,
+    #[builder(setter(custom), default)]
     security: Vec<String>,
+    #[builder(setter(skip))]
     compact: bool,
+    #[builder(setter(skip))]
+    format: FormatOptions,
+}
+
+impl ChangesBuilder {
+    /// Add a change to the builder based on its kind.
+    pub fn add<S: Into<String>>(&mut self, kind: ChangeKind, change: S) -> &mut Self {
+        let changes = match kind {
+            ChangeKind::Breaking => &mut self.breaking,
+            ChangeKind::Added => &mut self.added,
+            ChangeKind::Changed => &mut self.changed,
+            ChangeKind::Deprecated => &mut self.deprecated,
+            ChangeKind::Removed => &mut self.removed,
+            ChangeKind::Fixed => &mut self.fixed,
+            ChangeKind::Security => &mut self.security,
+        };
+
+        changes.get_or_insert_with(Vec::new).push(change.into());
+        self
+    }
+
+    /// Add multiple classified changes at once, preserving their order.
+    pub fn changes<I, S>(&mut self, changes: I) -> &mut Self
+    where
+        I: IntoIterator<Item = (ChangeKind, S)>,
+        S: Into<String>,
+    {
+        for (kind, change) in changes {
+            self.add(kind, change);
+        }
+        self
+    }
+
+    pub fn breaking<S: Into<String>>(&mut self, change: S) -> &mut Self {
+        self.add(ChangeKind::Breaking, change)
+    }
+
+    pub fn added<S: Into<String>>(&mut self, change: S) -> &mut Self {
+        self.add(ChangeKind::Added, change)
+    }
+
+    pub fn changed<S: Into<String>>(&mut self, change: S) -> &mut Self {
+        self.add(ChangeKind::Changed, change)
+    }
+
+    pub fn deprecated<S: Into<String>>(&mut self, change: S) -> &mut Self {
+        self.add(ChangeKind::Deprecated, change)
+    }
+
+    pub fn removed<S: Into<String>>(&mut self, change: S) -> &mut Self {
+        self.add(ChangeKind::Removed, change)
+    }
+
+    pub fn fixed<S: Into<String>>(&mut self, change: S) -> &mut Self {
+        self.add(ChangeKind::Fixed, change)
+    }
+
+    pub fn security<S: Into<String>>(&mut self, change: S) -> &mut Self {
+        self.add(ChangeKind::Security, change)
+    }
 }
 
 impl Changes {
+    /// Create a new set of changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, ChangeKind};
+    ///
+    /// let changes = Changes::builder()
+    ///     .added("New feature")
+    ///     .fixed("Bug fix")
+    ///     .changes(vec![(ChangeKind::Security, "Security fix")])
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert!(!changes.is_empty());
+    /// ```
+    pub fn builder() -> ChangesBuilder {
+        ChangesBuilder::default()
+    }
+
     /// Add a change to the set based on its kind.
     ///
     /// # Parameters
@@ -67,6 +324,7 @@ impl Changes {
     /// ```
     pub fn add(&mut self, kind: ChangeKind, change: String) {
         match kind {
+            ChangeKind::Breaking => self.breaking.push(change),
             ChangeKind::Added => self.added.push(change),
             ChangeKind::Changed => self.changed.push(change),
             ChangeKind::Deprecated => self.deprecated.push(change),
@@ -76,8 +334,85 @@ impl Changes {
         }
     }
 
+    /// Iterate over all changes with their kinds, in the order they are rendered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, ChangeKind};
+    ///
+    /// let changes = Changes::builder().fixed("Bug fix").added("New feature").build().unwrap();
+    /// let kinds = changes.iter().map(|(kind, _)| kind).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(kinds, vec![ChangeKind::Added, ChangeKind::Fixed]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (ChangeKind, &String)> {
+        [
+            (ChangeKind::Breaking, &self.breaking),
+            (ChangeKind::Added, &self.added),
+            (ChangeKind::Changed, &self.changed),
+            (ChangeKind::Deprecated, &self.deprecated),
+            (ChangeKind::Removed, &self.removed),
+            (ChangeKind::Fixed, &self.fixed),
+            (ChangeKind::Security, &self.security),
+        ]
+        .into_iter()
+        .flat_map(|(kind, changes)| changes.iter().map(move |change| (kind.clone(), change)))
+    }
+
+    /// Iterate over the non-empty sections of changes with their kinds, in the order they are
+    /// rendered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, ChangeKind};
+    ///
+    /// let changes = Changes::builder().fixed("Bug fix").fixed("Typo").build().unwrap();
+    /// let sections = changes.sections().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(sections.len(), 1);
+    /// assert_eq!(sections[0], (ChangeKind::Fixed, &["Bug fix".to_string(), "Typo".to_string()][..]));
+    /// ```
+    pub fn sections(&self) -> impl Iterator<Item = (ChangeKind, &[String])> {
+        [
+            (ChangeKind::Breaking, &self.breaking),
+            (ChangeKind::Added, &self.added),
+            (ChangeKind::Changed, &self.changed),
+            (ChangeKind::Deprecated, &self.deprecated),
+            (ChangeKind::Removed, &self.removed),
+            (ChangeKind::Fixed, &self.fixed),
+            (ChangeKind::Security, &self.security),
+        ]
+        .into_iter()
+        .filter(|(_, changes)| !changes.is_empty())
+        .map(|(kind, changes)| (kind, changes.as_slice()))
+    }
+
+    /// Iterate over all changes as [`ChangeEntry`], in the order they are rendered.
+    pub fn entries(&self) -> impl Iterator<Item = ChangeEntry> + '_ {
+        self.iter()
+            .map(|(kind, change)| ChangeEntry::new(kind, change.clone()))
+    }
+
+    /// Same as `iter` but mutable
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (ChangeKind, &mut String)> {
+        [
+            (ChangeKind::Breaking, &mut self.breaking),
+            (ChangeKind::Added, &mut self.added),
+            (ChangeKind::Changed, &mut self.changed),
+            (ChangeKind::Deprecated, &mut self.deprecated),
+            (ChangeKind::Removed, &mut self.removed),
+            (ChangeKind::Fixed, &mut self.fixed),
+            (ChangeKind::Security, &mut self.security),
+        ]
+        .into_iter()
+        .flat_map(|(kind, changes)| changes.iter_mut().map(move |change| (kind.clone(), change)))
+    }
+
     pub fn is_empty(&self) -> bool {
-        self.added.is_empty()
+        self.breaking.is_empty()
+            && self.added.is_empty()
             && self.changed.is_empty()
             && self.deprecated.is_empty()
             && self.removed.is_empty()
@@ -85,23 +420,142 @@ impl Changes {
             && self.security.is_empty()
     }
 
+    /// Group changes by scope, see [`ChangeEntry::scope`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changes;
+    ///
+    /// let changes = Changes::builder()
+    ///     .fixed("**parser:** handle nested lists")
+    ///     .added("**cli:** add `--dry-run`")
+    ///     .fixed("**parser:** accept tabs")
+    ///     .fixed("Typo in docs")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// let groups = changes.by_scope();
+    /// assert_eq!(groups[&Some("parser".to_string())].len(), 2);
+    /// assert_eq!(groups[&Some("cli".to_string())].len(), 1);
+    /// assert_eq!(groups[&None].len(), 1);
+    /// ```
+    pub fn by_scope(&self) -> BTreeMap<Option<String>, Vec<ChangeEntry>> {
+        let mut groups: BTreeMap<Option<String>, Vec<ChangeEntry>> = BTreeMap::new();
+
+        self.entries()
+            .for_each(|entry| groups.entry(entry.scope.clone()).or_default().push(entry));
+
+        groups
+    }
+
     pub(crate) fn set_compact(&mut self, value: bool) -> &mut Self {
         self.compact = value;
         self
     }
+
+    pub(crate) fn set_format(&mut self, format: FormatOptions) -> &mut Self {
+        self.format = format;
+        self
+    }
+}
+
+impl PartialEq for Changes {
+    /// Changes are equal if they have the same entries, the render settings are ignored
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for Changes {}
+
+impl From<Vec<(ChangeKind, String)>> for Changes {
+    /// Create a set of changes from a list of classified change messages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, ChangeKind};
+    ///
+    /// let changes = Changes::from(vec![
+    ///     (ChangeKind::Added, "New feature".to_string()),
+    ///     (ChangeKind::Fixed, "Bug fix".to_string()),
+    /// ]);
+    ///
+    /// assert!(!changes.is_empty());
+    /// ```
+    fn from(changes: Vec<(ChangeKind, String)>) -> Self {
+        changes.into_iter().collect()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(ChangeKind, S)> for Changes {
+    /// Collect classified change messages into a set of changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, ChangeKind};
+    ///
+    /// let commits = ["feat: New feature", "fix: Bug fix"];
+    /// let changes: Changes = commits
+    ///     .iter()
+    ///     .filter_map(|commit| commit.split_once(": "))
+    ///     .map(|(kind, message)| match kind {
+    ///         "feat" => (ChangeKind::Added, message),
+    ///         _ => (ChangeKind::Fixed, message),
+    ///     })
+    ///     .collect();
+    ///
+    /// assert_eq!(changes.iter().count(), 2);
+    /// ```
+    fn from_iter<I: IntoIterator<Item = (ChangeKind, S)>>(iter: I) -> Self {
+        let mut changes = Self::default();
+        changes.extend(iter);
+        changes
+    }
+}
+
+impl<S: Into<String>> Extend<(ChangeKind, S)> for Changes {
+    /// Add classified change messages to the set of changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, ChangeKind};
+    ///
+    /// let mut changes = Changes::builder().added("New feature").build().unwrap();
+    /// changes.extend([(ChangeKind::Fixed, "Bug fix"), (ChangeKind::Fixed, "Another fix")]);
+    ///
+    /// assert_eq!(changes.iter().count(), 3);
+    /// ```
+    fn extend<I: IntoIterator<Item = (ChangeKind, S)>>(&mut self, iter: I) {
+        iter.into_iter()
+            .for_each(|(kind, change)| self.add(kind, change.into()));
+    }
 }
 
 impl Display for Changes {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let mut first_printed = false;
 
+        if !self.breaking.is_empty() {
+            ensure_newline(f, &mut first_printed)?;
+            writeln!(f, "### Breaking Changes")?;
+            if !self.compact {
+                writeln!(f)?;
+            }
+            print_changes(f, ChangeKind::Breaking, &self.breaking, &self.format)?;
+            writeln!(f)?;
+        }
+
         if !self.added.is_empty() {
             ensure_newline(f, &mut first_printed)?;
             writeln!(f, "### Added")?;
             if !self.compact {
                 writeln!(f)?;
             }
-            print_changes(f, &self.added)?;
+            print_changes(f, ChangeKind::Added, &self.added, &self.format)?;
             writeln!(f)?;
         }
 
@@ -111,7 +565,7 @@ impl Display for Changes {
             if !self.compact {
                 writeln!(f)?;
             }
-            print_changes(f, &self.changed)?;
+            print_changes(f, ChangeKind::Changed, &self.changed, &self.format)?;
             writeln!(f)?;
         }
 
@@ -121,7 +575,7 @@ impl Display for Changes {
             if !self.compact {
                 writeln!(f)?;
             }
-            print_changes(f, &self.deprecated)?;
+            print_changes(f, ChangeKind::Deprecated, &self.deprecated, &self.format)?;
             writeln!(f)?;
         }
 
@@ -131,7 +585,7 @@ impl Display for Changes {
             if !self.compact {
                 writeln!(f)?;
             }
-            print_changes(f, &self.removed)?;
+            print_changes(f, ChangeKind::Removed, &self.removed, &self.format)?;
             writeln!(f)?;
         }
 
@@ -141,7 +595,7 @@ impl Display for Changes {
             if !self.compact {
                 writeln!(f)?;
             }
-            print_changes(f, &self.fixed)?;
+            print_changes(f, ChangeKind::Fixed, &self.fixed, &self.format)?;
             writeln!(f)?;
         }
 
@@ -151,7 +605,7 @@ impl Display for Changes {
             if !self.compact {
                 writeln!(f)?;
             }
-            print_changes(f, &self.security)?;
+            print_changes(f, ChangeKind::Security, &self.security, &self.format)?;
             writeln!(f)?;
         }
 
@@ -169,8 +623,26 @@ fn ensure_newline(f: &mut Formatter, first_printed: &mut bool) -> fmt::Result {
     Ok(())
 }
 
-fn print_changes(f: &mut Formatter, changes: &[String]) -> fmt::Result {
-    changes.iter().try_for_each(|change| {
+fn print_changes(
+    f: &mut Formatter,
+    kind: ChangeKind,
+    changes: &[String],
+    format: &FormatOptions,
+) -> fmt::Result {
+    let mut changes = changes.iter().collect::<Vec<_>>();
+    if format.group_by_scope {
+        // scoped entries first, sorted by scope, keeping the original order within a scope
+        changes.sort_by_cached_key(|change| {
+            let scope = parse_scope(change).map(|scope| scope.to_lowercase());
+            (scope.is_none(), scope)
+        });
+    }
+
+    changes.into_iter().try_for_each(|change| {
+        let change = match &format.transform {
+            Some(transform) => transform.apply(&kind, change),
+            None => change.clone(),
+        };
         let mut title = change
             .split('\n')
             .map(|line| format!("  {line}").trim_end().to_string())
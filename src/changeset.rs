@@ -0,0 +1,145 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use eyre::{bail, Context, Result};
+
+use crate::{ChangeKind, Changelog};
+
+/// Frontmatter delimiter of a changeset file
+const FRONTMATTER_DELIMITER: &str = "---";
+
+impl Changelog {
+    /// Fold [Changesets](https://github.com/changesets/changesets) from the `.changeset`
+    /// directory `dir` into Unreleased changes
+    ///
+    /// Each `*.md` file with a frontmatter of package bump types becomes an entry with its
+    /// summary. The highest bump of the changeset picks the change kind: `major` is a breaking
+    /// change, `minor` is added and `patch` is fixed. Changesets without bumps are changed.
+    /// Other files, e.g. the `README.md` of the directory, are skipped.
+    ///
+    /// Returns paths of the ingested changesets, so that they can be removed.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let mut changelog = Changelog::parse_from_file("CHANGELOG.md", None).unwrap();
+    /// for path in changelog.ingest_changesets(".changeset").unwrap() {
+    ///     std::fs::remove_file(path).unwrap();
+    /// }
+    /// changelog.save_to_file("CHANGELOG.md").unwrap();
+    /// ```
+    pub fn ingest_changesets<P: AsRef<Path>>(&mut self, dir: P) -> Result<Vec<PathBuf>> {
+        let dir = dir.as_ref();
+        let mut paths = fs::read_dir(dir)
+            .wrap_err_with(|| format!("Failed to read changesets from {}", dir.display()))?
+            .map(|entry| Ok(entry?.path()))
+            .collect::<Result<Vec<_>>>()?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "md"));
+        paths.sort();
+
+        let mut ingested = vec![];
+        for path in paths {
+            let source = fs::read_to_string(&path)?;
+            let changeset = parse_changeset(&source)
+                .wrap_err_with(|| format!("Invalid changeset {}", path.display()))?;
+
+            if let Some((kind, summary)) = changeset {
+                self.add_unreleased_change(kind, summary);
+                ingested.push(path);
+            }
+        }
+
+        Ok(ingested)
+    }
+}
+
+/// Parse change kind and summary of a changeset, `None` if it has no frontmatter or summary
+fn parse_changeset(source: &str) -> Result<Option<(ChangeKind, String)>> {
+    let mut lines = source.trim_start().lines();
+    if lines.next().map(str::trim_end) != Some(FRONTMATTER_DELIMITER) {
+        return Ok(None);
+    }
+
+    let mut bump = None;
+    for line in lines.by_ref() {
+        let line = line.trim();
+        if line == FRONTMATTER_DELIMITER {
+            break;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        let Some((_, kind)) = line.rsplit_once(':') else {
+            bail!("Invalid package bump: {line}");
+        };
+        let rank = match kind.trim().trim_matches(['"', '\'']) {
+            "none" => 0,
+            "patch" => 1,
+            "minor" => 2,
+            "major" => 3,
+            other => bail!("Unknown bump type: {other}"),
+        };
+        bump = bump.max(Some(rank));
+    }
+
+    let summary = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+    if summary.is_empty() {
+        return Ok(None);
+    }
+
+    let kind = match bump {
+        Some(3) => ChangeKind::Breaking,
+        Some(2) => ChangeKind::Added,
+        Some(1) => ChangeKind::Fixed,
+        _ => ChangeKind::Changed,
+    };
+    Ok(Some((kind, summary)))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_ingest_changesets() -> Result<()> {
+        let dir = format!("tests/tmp/changesets_{}", Uuid::new_v4());
+        fs::create_dir_all(&dir)?;
+        fs::write(format!("{dir}/README.md"), "# Changesets\n")?;
+        fs::write(format!("{dir}/config.json"), "{}\n")?;
+        fs::write(
+            format!("{dir}/brave-cats-dance.md"),
+            "---\n\"pkg-a\": patch\n\"pkg-b\": minor\n---\n\nAdd `--dry-run` flag\n",
+        )?;
+        fs::write(
+            format!("{dir}/quiet-dogs-run.md"),
+            "---\npkg-a: patch\n---\n\nFix parsing of tabs\n",
+        )?;
+
+        let mut changelog = Changelog::parse_from_file("tests/data/early_changelog.md", None)?;
+        let ingested = changelog.ingest_changesets(&dir)?;
+        assert_eq!(ingested.len(), 2);
+
+        let unreleased = changelog.get_unreleased().unwrap();
+        assert!(unreleased
+            .changes()
+            .iter()
+            .any(|(kind, c)| kind == ChangeKind::Added && c == "Add `--dry-run` flag"));
+        assert!(unreleased
+            .changes()
+            .iter()
+            .any(|(kind, c)| kind == ChangeKind::Fixed && c == "Fix parsing of tabs"));
+
+        fs::write(format!("{dir}/bad.md"), "---\npkg-a: huge\n---\n\nOops\n")?;
+        assert!(changelog.ingest_changesets(&dir).is_err());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,354 @@
+//! Checks for pre-commit hooks and CI gates, see [`Changelog::check_file`] and
+//! [`Changelog::requires_entry`].
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use eyre::{Context, Result};
+#[cfg(feature = "json")]
+use serde_json::{json, Value};
+
+use crate::{
+    diagnostics::{parse_error_spans, violation_spans, Diagnostic, Lines, Severity},
+    style::StylePolicy,
+    utils::glob_match,
+    Changelog, ChangelogParseOptions, ValidationOptions,
+};
+
+/// Options for [`Changelog::check_file`], parse errors are always reported.
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    pub parse: Option<ChangelogParseOptions>,
+    /// Validation rules, violations are errors
+    pub validation: ValidationOptions,
+    /// Style policy of the entries, violations are warnings
+    pub style: Option<StylePolicy>,
+    /// The file must be formatted the way it's saved, which keeps it markdownlint clean
+    pub check_format: bool,
+    /// The Unreleased section must have at least one entry, e.g. for pull request gates
+    pub require_unreleased_entry: bool,
+}
+
+/// Policy of [`Changelog::requires_entry`].
+#[derive(Debug, Clone, Default)]
+pub struct EntryPolicy {
+    /// Glob patterns of paths which don't need a changelog entry, e.g. `docs/**`, `tests/**` or
+    /// `**/*.md`. `*` and `?` match within a path segment, `**` matches any number of segments.
+    pub exclude: Vec<String>,
+}
+
+/// Result of [`Changelog::check_file`].
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+    pub path: PathBuf,
+    /// Problems sorted by position in the file
+    pub diagnostics: Vec<Diagnostic>,
+    source: String,
+}
+
+impl CheckReport {
+    /// The check passed if there are no errors, warnings are allowed
+    pub fn passed(&self) -> bool {
+        !self
+            .diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    /// Line number of the diagnostic, starting from 1
+    pub fn line(&self, diagnostic: &Diagnostic) -> usize {
+        let start = diagnostic.range.start.min(self.source.len());
+        self.source[..start].matches('\n').count() + 1
+    }
+
+    /// Machine-readable report, requires the `json` feature
+    ///
+    /// ```json
+    /// {
+    ///   "path": "CHANGELOG.md",
+    ///   "passed": false,
+    ///   "diagnostics": [
+    ///     {
+    ///       "line": 9,
+    ///       "range": [240, 254],
+    ///       "severity": "error",
+    ///       "code": "check::empty_unreleased",
+    ///       "message": "Unreleased section has no entries"
+    ///     }
+    ///   ]
+    /// }
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Value {
+        let diagnostics = self
+            .diagnostics
+            .iter()
+            .map(|d| {
+                json!({
+                    "line": self.line(d),
+                    "range": [d.range.start, d.range.end],
+                    "severity": match d.severity {
+                        Severity::Error => "error",
+                        Severity::Warning => "warning",
+                    },
+                    "code": d.code,
+                    "message": d.message,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        json!({
+            "path": self.path.display().to_string(),
+            "passed": self.passed(),
+            "diagnostics": diagnostics,
+        })
+    }
+}
+
+impl Changelog {
+    /// Check CHANGELOG.md file at `path`: parse it, validate it and check the style, formatting
+    /// and Unreleased entries as configured in `opts`
+    ///
+    /// Problems are collected into the report instead of failing, errors are returned only if
+    /// the file can't be read.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{check::CheckOptions, Changelog};
+    ///
+    /// let opts = CheckOptions {
+    ///     require_unreleased_entry: true,
+    ///     ..Default::default()
+    /// };
+    /// let report = Changelog::check_file("tests/data/early_changelog.md", opts).unwrap();
+    ///
+    /// assert!(!report.passed());
+    /// assert_eq!(report.diagnostics[0].code, "check::empty_unreleased");
+    /// ```
+    pub fn check_file<P: AsRef<Path>>(path: P, opts: CheckOptions) -> Result<CheckReport> {
+        let path = path.as_ref();
+        let source = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+
+        Ok(CheckReport {
+            path: path.to_path_buf(),
+            diagnostics: check(&source, &opts),
+            source,
+        })
+    }
+}
+
+impl Changelog {
+    /// Whether a change set touching `paths_changed` must add an Unreleased entry
+    ///
+    /// An entry is required if any of the paths isn't excluded by the `policy`. Paths are
+    /// relative to the repository root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    ///
+    /// use keep_a_changelog::{check::EntryPolicy, Changelog};
+    ///
+    /// let policy = EntryPolicy {
+    ///     exclude: vec!["docs/**".to_string(), "tests/**".to_string(), "*.md".to_string()],
+    /// };
+    ///
+    /// let docs = [PathBuf::from("docs/guide.md"), PathBuf::from("README.md")];
+    /// assert!(!Changelog::requires_entry(&docs, &policy));
+    ///
+    /// let code = [PathBuf::from("src/lib.rs"), PathBuf::from("tests/parse.rs")];
+    /// assert!(Changelog::requires_entry(&code, &policy));
+    /// ```
+    pub fn requires_entry(paths_changed: &[PathBuf], policy: &EntryPolicy) -> bool {
+        paths_changed.iter().any(|path| {
+            let path = path
+                .components()
+                .filter_map(|c| match c {
+                    Component::Normal(name) => Some(name.to_string_lossy()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("/");
+            !policy
+                .exclude
+                .iter()
+                .any(|pattern| glob_match(pattern, &path))
+        })
+    }
+}
+
+fn check(source: &str, opts: &CheckOptions) -> Vec<Diagnostic> {
+    let lines = Lines::new(source);
+    let range = |spans: Vec<(std::ops::Range<usize>, &str)>| {
+        spans.into_iter().next().map_or(0..0, |(range, _)| range)
+    };
+
+    let changelog = match Changelog::parse_recovering(source.to_string(), opts.parse.clone()) {
+        Ok(changelog) => changelog,
+        Err(errors) => {
+            return errors
+                .into_iter()
+                .map(|error| Diagnostic {
+                    range: range(parse_error_spans(&error, &lines)),
+                    severity: Severity::Error,
+                    code: error.code(),
+                    message: error.to_string(),
+                })
+                .collect();
+        }
+    };
+
+    let mut diagnostics = vec![];
+    for violation in changelog.validate(&opts.validation) {
+        diagnostics.push(Diagnostic {
+            range: range(violation_spans(&violation, &lines)),
+            severity: Severity::Error,
+            code: violation.code(),
+            message: violation.to_string(),
+        });
+    }
+
+    if let Some(policy) = &opts.style {
+        for violation in changelog.check_style(policy) {
+            diagnostics.push(Diagnostic {
+                range: range(violation_spans(&violation, &lines)),
+                severity: Severity::Warning,
+                code: violation.code(),
+                message: violation.to_string(),
+            });
+        }
+    }
+
+    for normalized in changelog.normalized_dates() {
+        diagnostics.push(Diagnostic {
+            range: lines.range(normalized.line).unwrap_or(0..0),
+            severity: Severity::Warning,
+            code: "check::normalized_date",
+            message: format!(
+                "Date {} is normalized to {}",
+                normalized.original, normalized.date
+            ),
+        });
+    }
+
+    let has_entries = changelog
+        .get_unreleased()
+        .is_some_and(|unreleased| !unreleased.changes().is_empty());
+    if opts.require_unreleased_entry && !has_entries {
+        diagnostics.push(Diagnostic {
+            range: lines
+                .release(None)
+                .and_then(|line| lines.range(line))
+                .unwrap_or(0..0),
+            severity: Severity::Error,
+            code: "check::empty_unreleased",
+            message: "Unreleased section has no entries".to_string(),
+        });
+    }
+
+    if opts.check_format {
+        if let Some(range) = first_difference(source, &changelog.file_contents()) {
+            diagnostics.push(Diagnostic {
+                range,
+                severity: Severity::Error,
+                code: "check::format",
+                message: "File is not formatted, save the changelog to format it".to_string(),
+            });
+        }
+    }
+
+    diagnostics.sort_by_key(|d| (d.range.start, d.severity));
+    diagnostics
+}
+
+/// Byte range of the first line of `source` which differs from `expected`
+fn first_difference(source: &str, expected: &str) -> Option<std::ops::Range<usize>> {
+    if source == expected {
+        return None;
+    }
+
+    let mut offset = 0;
+    let mut expected = expected.split('\n');
+    for line in source.split('\n') {
+        if expected.next() != Some(line) {
+            return Some(offset..offset + line.len());
+        }
+        offset += line.len() + 1;
+    }
+
+    Some(source.len()..source.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::excluded(&["docs/guide.md", "./tests/data/a.md", "CHANGELOG.md"], false)]
+    #[case::source(&["docs/guide.md", "src/lib.rs"], true)]
+    #[case::nested_markdown(&["src/docs/notes.md"], false)]
+    #[case::no_changes(&[], false)]
+    fn test_requires_entry(#[case] paths: &[&str], #[case] expected: bool) {
+        let policy = EntryPolicy {
+            exclude: vec![
+                "docs/**".to_string(),
+                "tests/**".to_string(),
+                "**/*.md".to_string(),
+            ],
+        };
+        let paths = paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+
+        assert_eq!(Changelog::requires_entry(&paths, &policy), expected);
+    }
+
+    #[test]
+    fn test_check() {
+        let source =
+            "# Changelog\n## [Unreleased]\n## [0.1.0] - 2024-04-28\n### Added\n- initial release\n";
+        let opts = CheckOptions {
+            style: Some(StylePolicy::default()),
+            check_format: true,
+            require_unreleased_entry: true,
+            ..Default::default()
+        };
+
+        let codes = check(source, &opts)
+            .iter()
+            .map(|d| (d.code, &source[d.range.clone()]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            [
+                ("check::format", "# Changelog"),
+                ("check::empty_unreleased", "## [Unreleased]"),
+                ("style::capitalize", "- initial release"),
+            ]
+        );
+
+        let lenient = CheckOptions {
+            parse: Some(ChangelogParseOptions {
+                lenient_dates: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let diagnostics = check(&source.replace("2024-04-28", "April 28th, 2024"), &lenient);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "check::normalized_date");
+        assert_eq!(
+            diagnostics[0].message,
+            "Date April 28th, 2024 is normalized to 2024-04-28"
+        );
+
+        let invalid = source.replace("2024-04-28", "2024-13-28");
+        let diagnostics = check(&invalid, &opts);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "parse::invalid_date");
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt::{self, Display, Formatter};
+
+const MARKER_OURS: &str = "<<<<<<<";
+const MARKER_BASE: &str = "|||||||";
+const MARKER_SEPARATOR: &str = "=======";
+const MARKER_THEIRS: &str = ">>>>>>>";
+
+/// Represents a single git merge conflict region found in a changelog.
+///
+/// Line numbers are 1-based and point at the conflict marker lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// Line of the `<<<<<<<` marker
+    pub start: usize,
+    /// Line of the `=======` marker
+    pub separator: usize,
+    /// Line of the `>>>>>>>` marker
+    pub end: usize,
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "lines {}-{}", self.start, self.end)
+    }
+}
+
+/// Side of a merge conflict to keep when resolving it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    /// The lines between `<<<<<<<` and `=======` (the current branch)
+    Ours,
+    /// The lines between `=======` and `>>>>>>>` (the incoming branch)
+    Theirs,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Region {
+    Common,
+    Ours,
+    Base,
+    Theirs,
+}
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    match line.strip_prefix(marker) {
+        Some(rest) => rest.is_empty() || rest.starts_with(' '),
+        None => false,
+    }
+}
+
+/// Find all complete conflict regions in the markdown.
+pub(crate) fn find_conflicts(markdown: &str) -> Vec<MergeConflict> {
+    let mut conflicts = vec![];
+    let mut start: Option<usize> = None;
+    let mut separator: Option<usize> = None;
+
+    for (idx, line) in markdown.lines().enumerate() {
+        let ln = idx + 1;
+        let line = line.trim_end();
+
+        if is_marker(line, MARKER_OURS) {
+            start = Some(ln);
+            separator = None;
+        } else if line == MARKER_SEPARATOR && start.is_some() {
+            separator = Some(ln);
+        } else if is_marker(line, MARKER_THEIRS) {
+            if let (Some(start), Some(separator)) = (start, separator) {
+                conflicts.push(MergeConflict {
+                    start,
+                    separator,
+                    end: ln,
+                });
+            }
+            start = None;
+            separator = None;
+        }
+    }
+
+    conflicts
+}
+
+/// Resolve every conflict region in the markdown by keeping the given side.
+pub(crate) fn resolve_conflicts(markdown: &str, side: ConflictSide) -> String {
+    let mut region = Region::Common;
+    let mut lines: Vec<&str> = vec![];
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_end();
+
+        match region {
+            Region::Common if is_marker(trimmed, MARKER_OURS) => {
+                region = Region::Ours;
+                continue;
+            }
+            Region::Ours if is_marker(trimmed, MARKER_BASE) => {
+                region = Region::Base;
+                continue;
+            }
+            Region::Ours | Region::Base if trimmed == MARKER_SEPARATOR => {
+                region = Region::Theirs;
+                continue;
+            }
+            Region::Theirs if is_marker(trimmed, MARKER_THEIRS) => {
+                region = Region::Common;
+                continue;
+            }
+            _ => {}
+        }
+
+        let keep = match region {
+            Region::Common => true,
+            Region::Ours => side == ConflictSide::Ours,
+            Region::Base => false,
+            Region::Theirs => side == ConflictSide::Theirs,
+        };
+
+        if keep {
+            lines.push(line);
+        }
+    }
+
+    let mut resolved = lines.join("\n");
+    resolved.push('\n');
+    resolved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFLICTED: &str = "# Changelog\n\
+        ## [Unreleased]\n\
+        ### Added\n\
+        <<<<<<< HEAD\n\
+        - Feature A\n\
+        ||||||| base\n\
+        =======\n\
+        - Feature B\n\
+        >>>>>>> feature-b\n\
+        - Common feature\n";
+
+    #[test]
+    fn test_find_conflicts() {
+        let conflicts = find_conflicts(CONFLICTED);
+        assert_eq!(
+            conflicts,
+            vec![MergeConflict {
+                start: 4,
+                separator: 7,
+                end: 9,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_find_conflicts_ignores_lone_separator() {
+        assert!(find_conflicts("Changelog\n=======\n").is_empty());
+    }
+
+    #[test]
+    fn test_resolve_conflicts() {
+        let ours = resolve_conflicts(CONFLICTED, ConflictSide::Ours);
+        assert!(ours.contains("- Feature A"));
+        assert!(!ours.contains("- Feature B"));
+        assert!(ours.contains("- Common feature"));
+
+        let theirs = resolve_conflicts(CONFLICTED, ConflictSide::Theirs);
+        assert!(!theirs.contains("- Feature A"));
+        assert!(theirs.contains("- Feature B"));
+        assert!(theirs.contains("- Common feature"));
+    }
+}
@@ -6,6 +6,29 @@ pub static CHANGELOG_DESCRIPTION: &str =
 
 pub static CHANGELOG_TITLE: &str = "Changelog";
 
+/// Heading of the per-release contributors section, e.g. `### Contributors`
+pub const CONTRIBUTORS_HEADING: &str = "Contributors";
+
+/// Only content of a release without notable changes, see
+/// [`Release::is_empty_release`](crate::Release::is_empty_release)
+pub const NO_NOTABLE_CHANGES: &str = "No notable changes.";
+
+/// Date of a planned release, e.g. `## [1.3.0] - TBD`
+pub const TBD_DATE: &str = "TBD";
+
+/// Marker of a draft release, placed below the release heading
+pub const DRAFT_FLAG: &str = "draft";
+
+/// Prefix of the release compare base comment, e.g. `<!-- compare-base: v1.0.0-rewritten -->`
+pub const COMPARE_BASE_FLAG_PREFIX: &str = "compare-base:";
+
+/// Prefix of the release metadata comment, e.g. `<!-- kac: sha=abc123 -->`
+pub const METADATA_FLAG_PREFIX: &str = "kac:";
+
+/// Markers around the table of contents, see [`FormatOptions::toc`](crate::FormatOptions::toc)
+pub const TOC_FLAG: &str = "toc";
+pub const TOC_END_FLAG: &str = "tocstop";
+
 pub const PREFIX_HR: &str = "---";
 pub const PREFIX_H1: &str = "# ";
 pub const PREFIX_H2: &str = "## ";
@@ -0,0 +1,454 @@
+//! Best-effort conversion of free-form changelogs, e.g. HISTORY.md or NEWS files, see
+//! [`convert`].
+use std::sync::OnceLock;
+
+use eyre::Result;
+use regex::Regex;
+
+use crate::{CalendarDate, ChangeKind, Changelog, Changes, Date, Release, ReleaseVersion};
+
+const MONTHS: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Result of [`convert`].
+#[derive(Debug, Clone)]
+pub struct Conversion {
+    pub changelog: Changelog,
+    /// Lines which couldn't be classified, in the order of the source
+    pub unclassified: Vec<Unclassified>,
+}
+
+/// Line of the source which couldn't be classified.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unclassified {
+    /// Line number, starting from 1
+    pub line: usize,
+    pub text: String,
+    /// The line is dropped, otherwise it's an entry outside of a known section added as changed
+    pub dropped: bool,
+}
+
+/// Convert a loosely structured changelog into a [`Changelog`]
+///
+/// Release headings are recognized in various styles, e.g. `## v1.2.3 (2024-06-01)`,
+/// `Version 1.2.3 - June 1, 2024` or setext headings, releases without a date are dropped.
+/// Sections are recognized by common titles, e.g. `Features` or `Bug fixes:`, entries outside
+/// of sections are added as changed. Text between a release heading and its first entry is the
+/// release description.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{convert, ChangeKind};
+///
+/// let news = "History\n=======\n\nv1.1.0 / 2024-06-01\n-------------------\n\n\
+///     Bug fixes:\n\n* Fix crash on empty input\n\n\
+///     v1.0.0 / 2024-05-01\n-------------------\n\n* Initial release\n";
+/// let conversion = convert::convert(news).unwrap();
+///
+/// let releases = conversion.changelog.releases();
+/// assert_eq!(releases.len(), 2);
+/// let (kind, entry) = releases[0].changes().iter().next().unwrap();
+/// assert_eq!((kind, entry.as_str()), (ChangeKind::Fixed, "Fix crash on empty input"));
+/// let (kind, entry) = releases[1].changes().iter().next().unwrap();
+/// assert_eq!((kind, entry.as_str()), (ChangeKind::Changed, "Initial release"));
+/// assert_eq!(conversion.unclassified.len(), 1);
+/// ```
+pub fn convert(source: &str) -> Result<Conversion> {
+    let mut converter = Converter::default();
+    let lines = source.lines().collect::<Vec<_>>();
+
+    let mut idx = 0;
+    while idx < lines.len() {
+        let line = lines[idx].trim_end();
+        let underlined = lines
+            .get(idx + 1)
+            .is_some_and(|next| regexes().underline.is_match(next.trim_end()));
+
+        if underlined && !line.trim().is_empty() {
+            converter.heading(idx + 1, line.trim(), true);
+            idx += 2;
+            continue;
+        }
+
+        converter.line(idx + 1, line);
+        idx += 1;
+    }
+    converter.finish_release();
+
+    let changelog = Changelog::builder().releases(converter.releases).build()?;
+    Ok(Conversion {
+        changelog,
+        unclassified: converter.unclassified,
+    })
+}
+
+struct Regexes {
+    heading: Regex,
+    underline: Regex,
+    bullet: Regex,
+    heading_version: Regex,
+    line_version: Regex,
+    unreleased: Regex,
+    iso_date: Regex,
+    text_date: Regex,
+    ordinal: Regex,
+}
+
+fn regexes() -> &'static Regexes {
+    static REGEXES: OnceLock<Regexes> = OnceLock::new();
+    REGEXES.get_or_init(|| {
+        let version = r"v?(\d+\.\d+(?:\.\d+)*(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?)";
+
+        Regexes {
+            heading: Regex::new(r"^#{1,6}\s+(.*?)\s*#*$").unwrap(),
+            underline: Regex::new(r"^(?:=+|-{3,})$").unwrap(),
+            bullet: Regex::new(r"^(\s*)(?:[-*+]|\d+[.)])\s+(.*)$").unwrap(),
+            heading_version: Regex::new(&format!(r"(?i)(?:^|[\s\[(])(?:version\s+)?{version}"))
+                .unwrap(),
+            line_version: Regex::new(&format!(r"(?i)^\[?(?:version\s+|release\s+)?{version}"))
+                .unwrap(),
+            unreleased: Regex::new(r"(?i)^\[?(?:unreleased|upcoming|in development)\b").unwrap(),
+            iso_date: Regex::new(r"(\d{4})[-/.](\d{1,2})[-/.](\d{1,2})").unwrap(),
+            text_date: Regex::new(
+                r"([A-Za-z]+\.?\s+\d{1,2}(?:st|nd|rd|th)?,?\s+\d{4}|\d{1,2}\s+[A-Za-z]+\.?\s+\d{4})",
+            )
+            .unwrap(),
+            ordinal: Regex::new(r"(\d)(?:st|nd|rd|th)\b").unwrap(),
+        }
+    })
+}
+
+/// Change kind of a section title, e.g. `Bug fixes` or `New features`
+fn section_kind(title: &str) -> Option<ChangeKind> {
+    let title = title
+        .trim()
+        .trim_end_matches(':')
+        .trim_matches(['*', '_'])
+        .to_lowercase();
+
+    if let Ok(kind) = title.parse() {
+        return Some(kind);
+    }
+
+    match title.as_str() {
+        "breaking"
+        | "breaking change"
+        | "backwards incompatible changes"
+        | "incompatible changes" => Some(ChangeKind::Breaking),
+        "features" | "new features" | "feature" | "new" | "additions" | "enhancements"
+        | "improvements" => Some(ChangeKind::Added),
+        "changes" | "change" | "other" | "other changes" | "misc" | "miscellaneous" => {
+            Some(ChangeKind::Changed)
+        }
+        "deprecations" => Some(ChangeKind::Deprecated),
+        "removals" | "removed features" => Some(ChangeKind::Removed),
+        "fixes" | "bug fixes" | "bugfixes" | "bugs" | "fix" => Some(ChangeKind::Fixed),
+        "security fixes" | "vulnerabilities" => Some(ChangeKind::Security),
+        _ => None,
+    }
+}
+
+/// Version and date of a release heading, the version is `None` for Unreleased
+fn release_heading(text: &str, heading: bool) -> Option<(Option<ReleaseVersion>, Option<Date>)> {
+    let regexes = regexes();
+    let text = text.trim();
+
+    if regexes.unreleased.is_match(text) {
+        return Some((None, None));
+    }
+
+    let regex = match heading {
+        true => &regexes.heading_version,
+        false => &regexes.line_version,
+    };
+    let captures = regex.captures(text)?;
+    let version = ReleaseVersion::parse(&captures[1]).ok()?;
+
+    let rest = &text[captures.get(0).map_or(0, |m| m.end())..];
+    Some((Some(version), parse_date(rest)))
+}
+
+/// Parse the first date found in the text, e.g. `2024/06/03` or `June 3rd, 2024`
+pub(crate) fn parse_date(text: &str) -> Option<Date> {
+    let regexes = regexes();
+
+    if let Some(captures) = regexes.iso_date.captures(text) {
+        let part = |idx: usize| captures[idx].parse().ok();
+        return Date::new(part(1)?, part(2)? as u32, part(3)? as u32);
+    }
+
+    let date = regexes.text_date.captures(text)?[1].replace(['.', ','], "");
+    let date = regexes.ordinal.replace_all(&date, "$1");
+    let (month, day, year) = match date.split_whitespace().collect::<Vec<_>>()[..] {
+        [first, second, year] => match first.parse() {
+            Ok(day) => (month_number(second)?, day, year),
+            Err(_) => (month_number(first)?, second.parse().ok()?, year),
+        },
+        _ => return None,
+    };
+    Date::new(year.parse().ok()?, month, day)
+}
+
+/// Number of the month of its English name or its three-letter abbreviation, e.g. `Jun`
+fn month_number(name: &str) -> Option<u32> {
+    let name = name.to_lowercase();
+    let position = MONTHS
+        .iter()
+        .position(|month| *month == name || month[..3] == name)?;
+    Some(position as u32 + 1)
+}
+
+#[derive(Default)]
+struct Converter {
+    releases: Vec<Release>,
+    unclassified: Vec<Unclassified>,
+    /// Release being converted, `None` before the first release and for dropped releases
+    release: Option<Release>,
+    /// Whether a release heading was found, lines before it are the preamble
+    in_release: bool,
+    kind: Option<ChangeKind>,
+    changes: Vec<(ChangeKind, String)>,
+    description: Vec<String>,
+}
+
+impl Converter {
+    fn line(&mut self, ln: usize, line: &str) {
+        let regexes = regexes();
+
+        if line.trim().is_empty() || regexes.underline.is_match(line) {
+            return;
+        }
+
+        if let Some(captures) = regexes.heading.captures(line) {
+            return self.heading(ln, &captures[1], true);
+        }
+
+        if let Some(captures) = regexes.bullet.captures(line) {
+            let nested = !captures[1].is_empty();
+            return match self.changes.last_mut() {
+                Some((_, entry)) if nested => {
+                    entry.push('\n');
+                    entry.push_str(line.trim());
+                }
+                _ => self.entry(ln, &captures[2]),
+            };
+        }
+
+        if line.starts_with(char::is_whitespace) {
+            if let Some((_, entry)) = self.changes.last_mut() {
+                entry.push('\n');
+                entry.push_str(line.trim());
+                return;
+            }
+        }
+
+        // plain lines are release headings only with a date, e.g. `1.2.3 (2024-06-01)`
+        let release = matches!(release_heading(line, false), Some((None, _) | (_, Some(_))));
+        if release || line.trim_end().ends_with(':') {
+            return self.heading(ln, line, false);
+        }
+
+        if self.release.is_some() && self.changes.is_empty() && self.kind.is_none() {
+            self.description.push(line.trim().to_string());
+        } else {
+            self.skip(ln, line);
+        }
+    }
+
+    fn heading(&mut self, ln: usize, text: &str, heading: bool) {
+        if let Some(kind) = section_kind(text) {
+            self.kind = Some(kind);
+            return;
+        }
+
+        if let Some((version, date)) = release_heading(text, heading) {
+            self.finish_release();
+            self.in_release = true;
+
+            let duplicate = self
+                .releases
+                .iter()
+                .any(|release| release.version() == &version);
+            let mut builder = Release::builder();
+            builder.yanked(text.to_lowercase().contains("yanked"));
+            match (version, date) {
+                (None, _) if !duplicate => {}
+                (Some(version), Some(date)) if !duplicate => {
+                    builder.version(version).date(date);
+                }
+                _ => return self.skip(ln, text),
+            }
+            self.release = builder.build().ok();
+            return;
+        }
+
+        // the title of the document is replaced with the default one
+        if heading && !self.in_release && self.unclassified.is_empty() && self.kind.is_none() {
+            return;
+        }
+
+        self.kind = None;
+        self.skip(ln, text);
+    }
+
+    fn entry(&mut self, ln: usize, text: &str) {
+        if self.release.is_none() {
+            return self.skip(ln, text);
+        }
+
+        let kind = self.kind.clone().unwrap_or_else(|| {
+            self.unclassified.push(Unclassified {
+                line: ln,
+                text: text.to_string(),
+                dropped: false,
+            });
+            ChangeKind::Changed
+        });
+        self.changes.push((kind, text.trim().to_string()));
+    }
+
+    fn skip(&mut self, ln: usize, text: &str) {
+        self.unclassified.push(Unclassified {
+            line: ln,
+            text: text.trim().to_string(),
+            dropped: true,
+        });
+    }
+
+    fn finish_release(&mut self) {
+        let changes = std::mem::take(&mut self.changes);
+        let description = std::mem::take(&mut self.description);
+        self.kind = None;
+
+        let Some(mut release) = self.release.take() else {
+            return;
+        };
+        if !description.is_empty() {
+            release.set_description(description.join("\n"));
+        }
+        release.set_changes(Changes::from(changes));
+        self.releases.push(release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::prefix("v1.2.3 (2024-06-01)", Some("1.2.3"), Some((2024, 6, 1)))]
+    #[case::version_word("Version 1.2.3 - June 1st, 2024", Some("1.2.3"), Some((2024, 6, 1)))]
+    #[case::brackets("[1.2.3] - 2024/06/01", Some("1.2.3"), Some((2024, 6, 1)))]
+    #[case::day_first("1.2 (1 Jun 2024)", Some("1.2"), Some((2024, 6, 1)))]
+    #[case::ordinal("Release 1.2.3, August 1st 2024", Some("1.2.3"), Some((2024, 8, 1)))]
+    #[case::no_date("Release 1.2.3", Some("1.2.3"), None)]
+    fn test_release_heading(
+        #[case] text: &str,
+        #[case] version: Option<&str>,
+        #[case] date: Option<(i32, u32, u32)>,
+    ) {
+        let (parsed_version, parsed_date) = release_heading(text, false).unwrap();
+        assert_eq!(parsed_version.map(|v| v.to_string()).as_deref(), version);
+        assert_eq!(parsed_date, date.and_then(|(y, m, d)| Date::new(y, m, d)));
+    }
+
+    #[test]
+    fn test_convert() -> Result<()> {
+        let source = [
+            "# Project history",
+            "",
+            "## Unreleased",
+            "",
+            "- Work in progress",
+            "",
+            "## 2.0.0 (2024-06-01)",
+            "",
+            "A major release.",
+            "",
+            "### Breaking",
+            "",
+            "* Drop Python 3.7",
+            "",
+            "### New features",
+            "",
+            "1. Async API",
+            "   with streaming",
+            "",
+            "### Thanks",
+            "",
+            "- Someone",
+            "",
+            "## 1.1.0",
+            "",
+            "- Undated release",
+            "",
+            "## 1.0.0 - May 1, 2024",
+            "",
+            "- Initial release",
+        ]
+        .join("\n");
+
+        let Conversion {
+            changelog,
+            unclassified,
+        } = convert(&source)?;
+        let entries = |idx: usize| {
+            changelog.releases()[idx]
+                .changes()
+                .iter()
+                .map(|(kind, entry)| (kind, entry.clone()))
+                .collect::<Vec<_>>()
+        };
+
+        let releases = changelog.releases();
+        assert_eq!(releases.len(), 3);
+        assert_eq!(
+            entries(0),
+            [(ChangeKind::Changed, "Work in progress".to_string())]
+        );
+        assert_eq!(
+            releases[1].description().as_deref(),
+            Some("A major release.")
+        );
+        assert_eq!(
+            entries(1),
+            [
+                (ChangeKind::Breaking, "Drop Python 3.7".to_string()),
+                (ChangeKind::Added, "Async API\nwith streaming".to_string()),
+                (ChangeKind::Changed, "Someone".to_string()),
+            ]
+        );
+        assert_eq!(releases[2].date(), &Date::new(2024, 5, 1));
+
+        assert_eq!(
+            unclassified
+                .iter()
+                .map(|u| (u.line, u.dropped))
+                .collect::<Vec<_>>(),
+            [
+                (5, false),
+                (20, true),
+                (22, false),
+                (24, true),
+                (26, true),
+                (30, false)
+            ]
+        );
+        Ok(())
+    }
+}
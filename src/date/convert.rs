@@ -0,0 +1,81 @@
+//! Conversions of `chrono` release dates from and to [`time::Date`], for projects using both
+//! crates.
+use chrono::{Datelike, NaiveDate};
+use eyre::{eyre, OptionExt, Result};
+use time::Date;
+
+use crate::{Changelog, Release, ReleaseVersion};
+
+/// Convert a `time` date into the date stored in releases
+pub fn from_time(date: Date) -> Result<NaiveDate> {
+    NaiveDate::from_yo_opt(date.year(), date.ordinal().into())
+        .ok_or_eyre(format!("Date {date} is out of range"))
+}
+
+/// Convert a release date into a `time` date, fails for years beyond ±9999
+pub fn to_time(date: NaiveDate) -> Result<Date> {
+    let ordinal = date.ordinal() as u16;
+    Date::from_ordinal_date(date.year(), ordinal)
+        .map_err(|e| eyre!("Date {date} is out of range: {e}"))
+}
+
+impl Release {
+    /// Release date as a `time` date
+    pub fn time_date(&self) -> Result<Option<Date>> {
+        self.date().map(to_time).transpose()
+    }
+
+    pub fn set_time_date(&mut self, date: Date) -> Result<&mut Self> {
+        Ok(self.set_date(from_time(date)?))
+    }
+}
+
+impl Changelog {
+    /// Promote the Unreleased release to the given version and `time` date, see
+    /// [`Changelog::promote_unreleased`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, Version};
+    /// use time::{Date, Month};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Added\n- New feature\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let date = Date::from_calendar_date(2024, Month::June, 1).unwrap();
+    /// changelog
+    ///     .promote_unreleased_on(Version::parse("0.1.0").unwrap(), date)
+    ///     .unwrap();
+    ///
+    /// let release = changelog.find_release("0.1.0".to_string()).unwrap().unwrap();
+    /// assert_eq!(release.time_date().unwrap(), Some(date));
+    /// ```
+    pub fn promote_unreleased_on<V: Into<ReleaseVersion>>(
+        &mut self,
+        version: V,
+        date: Date,
+    ) -> Result<&mut Self> {
+        self.promote_unreleased(version, from_time(date)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::Month;
+
+    use super::*;
+
+    #[test]
+    fn test_time_round_trip() -> Result<()> {
+        let date = Date::from_calendar_date(2024, Month::December, 31)?;
+        assert_eq!(
+            from_time(date)?,
+            NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+        );
+        assert_eq!(to_time(from_time(date)?)?, date);
+
+        assert!(to_time(NaiveDate::from_ymd_opt(10_000, 1, 1).unwrap()).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    str::FromStr,
+};
+
+use eyre::{bail, Error, Result};
+
+use super::{epoch_days, unix_days, CalendarDate};
+
+/// ISO 8601 calendar date stored as its validated `YYYY-MM-DD` string
+///
+/// Releases are dated with it when both the `chrono` and the `date-time` features are disabled,
+/// for builds which only round-trip changelogs and don't want a date library.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{date::IsoDate, CalendarDate};
+///
+/// let date: IsoDate = "2024-02-29".parse().unwrap();
+/// assert_eq!(date.as_str(), "2024-02-29");
+/// assert_eq!((date.year(), date.month(), date.day()), (2024, 2, 29));
+/// assert_eq!(date.add_days(1), IsoDate::new(2024, 3, 1));
+///
+/// assert!("2023-02-29".parse::<IsoDate>().is_err());
+/// assert!("2024-6-1".parse::<IsoDate>().is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IsoDate([u8; 10]);
+
+impl IsoDate {
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("ISO date is ASCII")
+    }
+
+    pub fn year(&self) -> i32 {
+        self.number(0..4) as i32
+    }
+
+    pub fn month(&self) -> u32 {
+        self.number(5..7)
+    }
+
+    pub fn day(&self) -> u32 {
+        self.number(8..10)
+    }
+
+    fn number(&self, range: std::ops::Range<usize>) -> u32 {
+        self.0[range]
+            .iter()
+            .fold(0, |number, digit| number * 10 + u32::from(digit - b'0'))
+    }
+}
+
+impl CalendarDate for IsoDate {
+    fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+        let days = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        if !(0..=9999).contains(&year) || day == 0 || day > days {
+            return None;
+        }
+
+        let mut bytes = [0; 10];
+        bytes.copy_from_slice(format!("{year:04}-{month:02}-{day:02}").as_bytes());
+        Some(Self(bytes))
+    }
+
+    fn ymd(&self) -> (i32, u32, u32) {
+        (self.year(), self.month(), self.day())
+    }
+
+    fn today() -> Self {
+        from_epoch_days(unix_days().into()).expect("current date is in range")
+    }
+
+    fn add_days(&self, days: u32) -> Option<Self> {
+        from_epoch_days(epoch_days(*self) + i64::from(days))
+    }
+}
+
+/// Date of the days since 1970-01-01
+fn from_epoch_days(days: i64) -> Option<IsoDate> {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month + 2) / 5 + 1;
+    let month = if month < 10 { month + 3 } else { month - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    IsoDate::new(i32::try_from(year).ok()?, month as u32, day as u32)
+}
+
+impl Display for IsoDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl Debug for IsoDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_tuple("IsoDate").field(&self.as_str()).finish()
+    }
+}
+
+impl FromStr for IsoDate {
+    type Err = Error;
+
+    /// Parse `YYYY-MM-DD` date, unlike [`CalendarDate::parse_ymd`] the month and the day must
+    /// have two digits
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+            bail!("Invalid date: {s}, expected YYYY-MM-DD");
+        }
+        match Self::parse_ymd(s) {
+            Some(date) => Ok(date),
+            None => bail!("Invalid date: {s}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::valid("2024-06-01", true)]
+    #[case::leap_day("2000-02-29", true)]
+    #[case::century("1900-02-29", false)]
+    #[case::month("2024-13-01", false)]
+    #[case::day("2024-04-31", false)]
+    #[case::zero_day("2024-04-00", false)]
+    #[case::single_digits("2024-6-1", false)]
+    #[case::sign("+024-06-01", false)]
+    fn test_parse(#[case] input: &str, #[case] valid: bool) {
+        let date = input.parse::<IsoDate>();
+        assert_eq!(date.is_ok(), valid);
+        if let Ok(date) = date {
+            assert_eq!(date.to_string(), input);
+        }
+    }
+
+    #[rstest]
+    #[case(0, "1970-01-01")]
+    #[case(-1, "1969-12-31")]
+    #[case(11_016, "2000-02-29")]
+    #[case(19_875, "2024-06-01")]
+    fn test_from_epoch_days(#[case] days: i64, #[case] expected: &str) {
+        let date = from_epoch_days(days).unwrap();
+        assert_eq!(date.as_str(), expected);
+        assert_eq!(epoch_days(date), days);
+    }
+
+    #[test]
+    fn test_order() {
+        let dates =
+            ["2024-06-01", "2023-12-31", "2024-01-15"].map(|date| date.parse::<IsoDate>().unwrap());
+        assert_eq!(dates.iter().max(), Some(&dates[0]));
+        assert_eq!(dates.iter().min(), Some(&dates[1]));
+    }
+}
@@ -0,0 +1,232 @@
+//! Date type of releases, see [`Date`].
+//!
+//! Releases are dated with [`chrono::NaiveDate`] by default. With the `chrono` feature disabled
+//! and the `date-time` feature enabled they're dated with [`time::Date`] instead, so only one
+//! date library ends up in the dependency tree. With both disabled they're dated with
+//! [`IsoDate`] strings and no date library is needed.
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::RangeInclusive,
+};
+
+#[cfg(all(feature = "chrono", feature = "date-time"))]
+mod convert;
+mod iso;
+
+#[cfg(all(feature = "chrono", feature = "date-time"))]
+pub use convert::{from_time, to_time};
+pub use iso::IsoDate;
+
+/// Date of the releases, [`chrono::NaiveDate`] with the `chrono` feature
+#[cfg(feature = "chrono")]
+pub type Date = chrono::NaiveDate;
+
+/// Date of the releases, [`time::Date`] with the `date-time` feature and without `chrono`
+#[cfg(all(feature = "date-time", not(feature = "chrono")))]
+pub type Date = time::Date;
+
+/// Date of the releases, [`IsoDate`] without the `chrono` and the `date-time` features
+#[cfg(not(any(feature = "chrono", feature = "date-time")))]
+pub type Date = IsoDate;
+
+/// Calendar operations the library needs from the [`Date`] type, implemented for each date
+/// backend
+///
+/// Dates are displayed as `YYYY-MM-DD` by every backend.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{CalendarDate, Date};
+///
+/// let date = Date::new(2024, 6, 1).unwrap();
+/// assert_eq!(date.ymd(), (2024, 6, 1));
+/// assert_eq!(date.to_string(), "2024-06-01");
+///
+/// assert_eq!(Date::parse_ymd("2024-6-1"), Some(date));
+/// assert_eq!(date.add_days(30), Date::new(2024, 7, 1));
+/// assert!(Date::new(2024, 2, 30).is_none());
+/// ```
+pub trait CalendarDate: Copy + Ord + Hash + Debug + Display {
+    /// Date of the year, month and day, `None` if there is no such date
+    fn new(year: i32, month: u32, day: u32) -> Option<Self>;
+
+    /// Year, month and day of the date
+    fn ymd(&self) -> (i32, u32, u32);
+
+    /// Current date, the local one with `chrono` and the UTC one otherwise
+    fn today() -> Self;
+
+    /// Date `days` after this one, `None` if it's out of range
+    fn add_days(&self, days: u32) -> Option<Self>;
+
+    /// Parse `YYYY-MM-DD` date, the month and the day may be written with a single digit
+    fn parse_ymd(s: &str) -> Option<Self> {
+        let mut parts = s.splitn(3, '-');
+        let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+
+        let digits = |part: &str, len: RangeInclusive<usize>| {
+            len.contains(&part.len()) && part.bytes().all(|b| b.is_ascii_digit())
+        };
+        if !digits(year, 4..=4) || !digits(month, 1..=2) || !digits(day, 1..=2) {
+            return None;
+        }
+
+        Self::new(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CalendarDate for chrono::NaiveDate {
+    fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        Self::from_ymd_opt(year, month, day)
+    }
+
+    fn ymd(&self) -> (i32, u32, u32) {
+        use chrono::Datelike;
+
+        (self.year(), self.month(), self.day())
+    }
+
+    fn today() -> Self {
+        chrono::Local::now().date_naive()
+    }
+
+    fn add_days(&self, days: u32) -> Option<Self> {
+        self.checked_add_days(chrono::Days::new(days.into()))
+    }
+}
+
+#[cfg(feature = "date-time")]
+impl CalendarDate for time::Date {
+    fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        let month = time::Month::try_from(u8::try_from(month).ok()?).ok()?;
+        Self::from_calendar_date(year, month, u8::try_from(day).ok()?).ok()
+    }
+
+    fn ymd(&self) -> (i32, u32, u32) {
+        (
+            self.year(),
+            u8::from(self.month()).into(),
+            self.day().into(),
+        )
+    }
+
+    fn today() -> Self {
+        /// Julian day of 1970-01-01
+        const UNIX_EPOCH: i32 = 2_440_588;
+
+        Self::from_julian_day(UNIX_EPOCH + unix_days()).expect("current date is in range")
+    }
+
+    fn add_days(&self, days: u32) -> Option<Self> {
+        self.checked_add(time::Duration::days(days.into()))
+    }
+}
+
+/// Days since 1970-01-01 of the current UTC date
+pub(crate) fn unix_days() -> i32 {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    (secs / 86_400) as i32
+}
+
+/// Days since 1970-01-01 of the date, negative before it
+pub(crate) fn epoch_days(date: impl CalendarDate) -> i64 {
+    let (year, month, day) = date.ymd();
+    days_from_civil(year, month, day)
+}
+
+/// Days since 1970-01-01 of the year, month and day
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from((month + 9) % 12);
+    let day_of_year = (153 * month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// ISO 8601 weekday of the date, from 1 for Monday to 7 for Sunday
+pub(crate) fn weekday(date: impl CalendarDate) -> u32 {
+    weekday_of(epoch_days(date))
+}
+
+/// ISO 8601 week number of the date
+pub(crate) fn iso_week(date: impl CalendarDate) -> u32 {
+    let (year, month, day) = date.ymd();
+    let ordinal = days_from_civil(year, month, day) - days_from_civil(year, 1, 1) + 1;
+    match (ordinal - i64::from(weekday(date)) + 10) / 7 {
+        0 => weeks_in_year(year - 1),
+        week if week > i64::from(weeks_in_year(year)) => 1,
+        week => week as u32,
+    }
+}
+
+fn weekday_of(days: i64) -> u32 {
+    (days + 3).rem_euclid(7) as u32 + 1
+}
+
+/// Years starting on Thursday and leap years starting on Wednesday have 53 ISO weeks
+fn weeks_in_year(year: i32) -> u32 {
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    match weekday_of(days_from_civil(year, 1, 1)) {
+        4 => 53,
+        3 if leap => 53,
+        _ => 52,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("2024-06-01", Some((2024, 6, 1)))]
+    #[case("2024-6-3", Some((2024, 6, 3)))]
+    #[case("2024-02-30", None)]
+    #[case("2024-06", None)]
+    #[case("24-06-01", None)]
+    #[case("2024-06-01T00:00", None)]
+    #[case("June 3, 2024", None)]
+    fn test_parse_ymd(#[case] input: &str, #[case] expected: Option<(i32, u32, u32)>) {
+        assert_eq!(Date::parse_ymd(input).map(|date| date.ymd()), expected);
+    }
+
+    #[rstest]
+    #[case(1970, 1, 1, 0)]
+    #[case(1969, 12, 31, -1)]
+    #[case(2000, 3, 1, 11_017)]
+    #[case(2024, 6, 1, 19_875)]
+    fn test_days_from_civil(
+        #[case] year: i32,
+        #[case] month: u32,
+        #[case] day: u32,
+        #[case] expected: i64,
+    ) {
+        assert_eq!(days_from_civil(year, month, day), expected);
+    }
+
+    #[rstest]
+    #[case(2024, 6, 1, 6, 22)]
+    #[case(2021, 1, 3, 7, 53)]
+    #[case(2020, 12, 31, 4, 53)]
+    #[case(2024, 12, 30, 1, 1)]
+    #[case(2026, 1, 1, 4, 1)]
+    fn test_iso_week(
+        #[case] year: i32,
+        #[case] month: u32,
+        #[case] day: u32,
+        #[case] expected_weekday: u32,
+        #[case] expected_week: u32,
+    ) {
+        let date = Date::new(year, month, day).unwrap();
+        assert_eq!(weekday(date), expected_weekday);
+        assert_eq!(iso_week(date), expected_week);
+    }
+}
@@ -0,0 +1,276 @@
+//! Diagnostics with byte ranges of CHANGELOG.md, e.g. for editor or LSP integration.
+use std::ops::Range;
+
+use crate::{style::StylePolicy, Changelog, ParseError, Violation};
+
+/// Severity of a [`Diagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A problem in CHANGELOG.md located by its byte range in the source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// Byte range in the source, empty at the start of the source if the problem isn't related
+    /// to a specific line
+    pub range: Range<usize>,
+    pub severity: Severity,
+    /// Stable code of the problem, e.g. `parse::invalid_date`
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl Changelog {
+    /// Get diagnostics of CHANGELOG.md `source`
+    ///
+    /// Parse errors are reported as errors, entries breaking the default [`StylePolicy`] as
+    /// warnings. Diagnostics are sorted by position in the source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{diagnostics::Severity, Changelog};
+    ///
+    /// let source = "# Changelog\n## [0.1.0] - 2024-06-01\n### Fixed\n- fix parser\n";
+    /// let diagnostics = Changelog::diagnostics(source);
+    ///
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].severity, Severity::Warning);
+    /// assert_eq!(diagnostics[0].code, "style::capitalize");
+    /// assert_eq!(&source[diagnostics[0].range.clone()], "- fix parser");
+    /// ```
+    pub fn diagnostics(source: &str) -> Vec<Diagnostic> {
+        let lines = Lines::new(source);
+
+        let mut diagnostics = match Changelog::parse_recovering(source.to_string(), None) {
+            Ok(changelog) => changelog
+                .check_style(&StylePolicy::default())
+                .into_iter()
+                .map(|violation| Diagnostic {
+                    range: violation_spans(&violation, &lines)
+                        .into_iter()
+                        .next()
+                        .map_or(0..0, |(range, _)| range),
+                    severity: Severity::Warning,
+                    code: violation.code(),
+                    message: violation.to_string(),
+                })
+                .collect::<Vec<_>>(),
+            Err(errors) => errors
+                .into_iter()
+                .map(|error| Diagnostic {
+                    range: parse_error_spans(&error, &lines)
+                        .into_iter()
+                        .next()
+                        .map_or(0..0, |(range, _)| range),
+                    severity: Severity::Error,
+                    code: error.code(),
+                    message: error.to_string(),
+                })
+                .collect(),
+        };
+
+        diagnostics.sort_by_key(|d| (d.range.start, d.severity));
+        diagnostics
+    }
+}
+
+impl ParseError {
+    /// Stable code of the error, e.g. `parse::invalid_date`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseError::MergeConflict(_) => "parse::merge_conflict",
+            ParseError::MultipleDocuments(_) => "parse::multiple_documents",
+            ParseError::InvalidRelease { .. } => "parse::invalid_release",
+            ParseError::InvalidVersion { .. } => "parse::invalid_version",
+            ParseError::InvalidDate { .. } => "parse::invalid_date",
+            ParseError::UnknownSection { .. } => "parse::unknown_section",
+            ParseError::InvalidMetadata { .. } => "parse::invalid_metadata",
+            ParseError::UnexpectedContent { .. } => "parse::unexpected_content",
+            ParseError::InvalidEntry { .. } => "parse::invalid_entry",
+            ParseError::Other(_) => "parse::other",
+        }
+    }
+}
+
+impl Violation {
+    /// Stable code of the violation, e.g. `style::capitalize`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Violation::StaleUnreleased { .. } => "validate::stale_unreleased",
+            Violation::Template { .. } => "template::mismatch",
+            Violation::ReleaseNotFound { .. } => "release::not_found",
+            Violation::EmptyRelease { .. } => "release::empty",
+            Violation::ReleaseDate { .. } => "release::date",
+            Violation::ReleaseLink { .. } => "release::link",
+            Violation::Style { rule, .. } => match rule {
+                crate::style::StyleRule::Capitalize => "style::capitalize",
+                crate::style::StyleRule::TrailingPeriod => "style::trailing_period",
+                crate::style::StyleRule::ImperativeMood { .. } => "style::imperative_mood",
+                crate::style::StyleRule::MaxLength { .. } => "style::max_length",
+            },
+        }
+    }
+}
+
+/// Labeled byte ranges of the lines the error refers to.
+pub(crate) fn parse_error_spans(
+    error: &ParseError,
+    lines: &Lines,
+) -> Vec<(Range<usize>, &'static str)> {
+    let label = |line: usize, label: &'static str| {
+        lines
+            .range(line)
+            .map(|range| (range, label))
+            .into_iter()
+            .collect()
+    };
+
+    match error {
+        ParseError::MergeConflict(conflicts) => conflicts
+            .iter()
+            .filter_map(|c| {
+                let start = lines.range(c.start)?;
+                let end = lines.range(c.end)?;
+                Some((start.start..end.end, "conflict"))
+            })
+            .collect(),
+        ParseError::MultipleDocuments(headings) => headings
+            .iter()
+            .filter_map(|line| Some((lines.range(*line)?, "document")))
+            .collect(),
+        ParseError::InvalidRelease { line, .. } => label(*line, "invalid release heading"),
+        ParseError::InvalidVersion { line, .. } => label(*line, "invalid version"),
+        ParseError::InvalidDate { line, .. } => label(*line, "invalid date"),
+        ParseError::UnknownSection { line, .. } => label(*line, "unknown section"),
+        ParseError::InvalidMetadata { line, .. } => label(*line, "invalid metadata"),
+        ParseError::UnexpectedContent { line, .. } => label(*line, "unexpected content"),
+        ParseError::InvalidEntry { line, .. } => label(*line, "invalid entry"),
+        ParseError::Other(_) => vec![],
+    }
+}
+
+/// Labeled byte range of the release or entry the violation refers to.
+pub(crate) fn violation_spans(
+    violation: &Violation,
+    lines: &Lines,
+) -> Vec<(Range<usize>, &'static str)> {
+    let span = match violation {
+        Violation::StaleUnreleased { .. } => lines
+            .release(None)
+            .and_then(|line| lines.range(line))
+            .map(|range| (range, "stale release")),
+        Violation::Style {
+            version,
+            kind,
+            index,
+            ..
+        }
+        | Violation::Template {
+            version,
+            kind,
+            index,
+            ..
+        } => lines
+            .release(version.as_ref().map(|v| v.to_string()).as_deref())
+            .and_then(|release| lines.entry(release, kind.heading(), *index))
+            .and_then(|line| lines.range(line))
+            .map(|range| (range, "entry")),
+        Violation::ReleaseNotFound { .. } => None,
+        Violation::EmptyRelease { version }
+        | Violation::ReleaseDate { version, .. }
+        | Violation::ReleaseLink { version, .. } => lines
+            .release(Some(&version.to_string()))
+            .or_else(|| lines.release(None))
+            .and_then(|line| lines.range(line))
+            .map(|range| (range, "release")),
+    };
+
+    span.into_iter().collect()
+}
+
+/// Lines of the source, numbered as by the tokenizer, i.e. from the first non-blank line.
+pub(crate) struct Lines<'a> {
+    lines: Vec<(usize, &'a str)>,
+}
+
+impl<'a> Lines<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        let mut offset = source.len() - source.trim_start().len();
+        let lines = source
+            .trim()
+            .split('\n')
+            .map(|line| {
+                let start = offset;
+                offset += line.len() + 1;
+                (start, line.trim_end_matches('\r'))
+            })
+            .collect();
+
+        Self { lines }
+    }
+
+    /// Byte range of the line, without the line break
+    pub(crate) fn range(&self, line: usize) -> Option<Range<usize>> {
+        let (offset, text) = self.lines.get(line.checked_sub(1)?)?;
+        Some(*offset..offset + text.len())
+    }
+
+    fn text(&self, line: usize) -> &str {
+        self.lines.get(line - 1).map_or("", |(_, text)| text)
+    }
+
+    /// Line of the heading of the release with `version`, Unreleased if `None`
+    pub(crate) fn release(&self, version: Option<&str>) -> Option<usize> {
+        (1..=self.lines.len()).find(|line| {
+            let text = self.text(*line).to_lowercase();
+            text.starts_with("## ")
+                && match version {
+                    Some(version) => text.contains(&format!("[{version}]")),
+                    None => text.contains("unreleased"),
+                }
+        })
+    }
+
+    /// Line of the `index`-th entry of the `heading` section of the release at `release` line
+    fn entry(&self, release: usize, heading: &str, index: usize) -> Option<usize> {
+        let section = (release + 1..=self.lines.len())
+            .take_while(|line| !self.text(*line).starts_with("## "))
+            .find(|line| {
+                self.text(*line)
+                    .strip_prefix("### ")
+                    .is_some_and(|h| h.trim().eq_ignore_ascii_case(heading))
+            })?;
+
+        (section + 1..=self.lines.len())
+            .take_while(|line| !self.text(*line).starts_with('#'))
+            .filter(|line| self.text(*line).starts_with(['-', '*']))
+            .nth(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostics() {
+        let source = "\n# Changelog\n\n## [0.2.0] - 2024-13-01\n\n### Improved\n\n- Speed\n";
+        let diagnostics = Changelog::diagnostics(source);
+
+        let ranges = diagnostics
+            .iter()
+            .map(|d| (d.code, &source[d.range.clone()]))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![
+                ("parse::invalid_date", "## [0.2.0] - 2024-13-01"),
+                ("parse::unknown_section", "### Improved"),
+            ]
+        );
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+    }
+}
@@ -0,0 +1,188 @@
+use std::{fmt::Write, fs, io::ErrorKind};
+
+use eyre::{Context, Result};
+use similar::{capture_diff_slices, Algorithm, DiffTag};
+
+use crate::Changelog;
+
+/// Unchanged lines around every change in a hunk
+const CONTEXT: usize = 3;
+
+impl Changelog {
+    /// Unified diff between the file at `path` and the contents [`Changelog::save_to_file`]
+    /// would write there, e.g. for a `--dry-run` preview
+    ///
+    /// The diff is empty if saving wouldn't change the file. A missing file is diffed as an
+    /// empty one, other read errors are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{ChangeKind, Changelog};
+    ///
+    /// let path = "tests/data/early_changelog.md";
+    /// let mut changelog = Changelog::parse_from_file(path, None).unwrap();
+    /// assert_eq!(changelog.diff_against_file(path).unwrap(), "");
+    ///
+    /// changelog
+    ///     .get_unreleased_mut()
+    ///     .unwrap()
+    ///     .add(ChangeKind::Added, "Dry-run preview".to_string());
+    ///
+    /// let diff = changelog.diff_against_file(path).unwrap();
+    /// assert!(diff.starts_with(&format!("--- a/{path}\n+++ b/{path}\n@@ -8,6 +8,10 @@\n")));
+    /// assert!(diff.contains("\n+### Added\n+\n+- Dry-run preview\n"));
+    /// ```
+    pub fn diff_against_file(&self, path: &str) -> Result<String> {
+        let old = match fs::read_to_string(path) {
+            Ok(old) => old,
+            Err(e) if e.kind() == ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Failed to read {path}")),
+        };
+        Ok(unified_diff(path, &old, &self.file_contents()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Edit {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    let old = old.split_inclusive('\n').collect::<Vec<_>>();
+    let new = new.split_inclusive('\n').collect::<Vec<_>>();
+    let edits = edits(&old, &new);
+
+    let changes = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, Edit::Equal(..)))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // Group changes which are close enough to share their context lines
+    let mut hunks = vec![];
+    let mut start = changes[0];
+    let mut end = changes[0];
+    for &idx in &changes[1..] {
+        if idx - end > 2 * CONTEXT {
+            hunks.push((start, end));
+            start = idx;
+        }
+        end = idx;
+    }
+    hunks.push((start, end));
+
+    let mut diff = format!("--- a/{path}\n+++ b/{path}\n");
+    for (start, end) in hunks {
+        let hunk = &edits[start.saturating_sub(CONTEXT)..(end + CONTEXT + 1).min(edits.len())];
+
+        // Position of the hunk in both files, i.e. the number of lines before it
+        let (old_pos, new_pos) = edits[..start.saturating_sub(CONTEXT)]
+            .iter()
+            .rev()
+            .find_map(|edit| match edit {
+                Edit::Equal(i, j) => Some((i + 1, j + 1)),
+                _ => None,
+            })
+            .unwrap_or((0, 0));
+        let old_len = hunk
+            .iter()
+            .filter(|edit| !matches!(edit, Edit::Insert(_)))
+            .count();
+        let new_len = hunk
+            .iter()
+            .filter(|edit| !matches!(edit, Edit::Delete(_)))
+            .count();
+
+        let _ = writeln!(
+            diff,
+            "@@ -{} +{} @@",
+            range(old_pos, old_len),
+            range(new_pos, new_len)
+        );
+        for edit in hunk {
+            let (prefix, line) = match *edit {
+                Edit::Equal(i, _) => (' ', old[i]),
+                Edit::Delete(i) => ('-', old[i]),
+                Edit::Insert(j) => ('+', new[j]),
+            };
+            diff.push(prefix);
+            diff.push_str(line);
+            if !line.ends_with('\n') {
+                diff.push_str("\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    diff
+}
+
+/// Hunk range of `len` lines after the first `pos` lines, e.g. `7,6`, `7` for a single line
+fn range(pos: usize, len: usize) -> String {
+    match len {
+        0 => format!("{pos},0"),
+        1 => format!("{}", pos + 1),
+        _ => format!("{},{len}", pos + 1),
+    }
+}
+
+/// Shortest edit script turning `old` into `new`, computed with the linear space variant of
+/// the Myers algorithm
+fn edits(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let mut edits = vec![];
+    for op in capture_diff_slices(Algorithm::Myers, old, new) {
+        let (tag, old_range, new_range) = op.as_tag_tuple();
+        match tag {
+            DiffTag::Equal => {
+                edits.extend(old_range.zip(new_range).map(|(i, j)| Edit::Equal(i, j)))
+            }
+            // replacements are written as deletions followed by insertions
+            _ => {
+                edits.extend(old_range.map(Edit::Delete));
+                edits.extend(new_range.map(Edit::Insert));
+            }
+        }
+    }
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::unchanged("a\nb\n", "a\nb\n", "")]
+    #[case::created("", "a\nb\n", "--- a/F\n+++ b/F\n@@ -0,0 +1,2 @@\n+a\n+b\n")]
+    #[case::replaced(
+        "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n",
+        "1\n2\nx\n4\n5\n6\n7\n8\n9\n10\n11\n",
+        "--- a/F\n+++ b/F\n@@ -1,6 +1,6 @@\n 1\n 2\n-3\n+x\n 4\n 5\n 6\n@@ -9,4 +9,3 @@\n 9\n 10\n 11\n-12\n"
+    )]
+    #[case::no_newline(
+        "a\nb",
+        "a\nb\n",
+        "--- a/F\n+++ b/F\n@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+b\n"
+    )]
+    fn test_unified_diff(#[case] old: &str, #[case] new: &str, #[case] expected: &str) {
+        assert_eq!(unified_diff("F", old, new), expected);
+    }
+
+    #[test]
+    fn test_diff_against_file() -> Result<()> {
+        let changelog = Changelog::parse_from_file("tests/data/early_changelog.md", None)?;
+
+        let diff = changelog.diff_against_file("tests/data/missing.md")?;
+        assert!(diff
+            .starts_with("--- a/tests/data/missing.md\n+++ b/tests/data/missing.md\n@@ -0,0 +1,"));
+        assert!(changelog.diff_against_file("tests/data").is_err());
+        Ok(())
+    }
+}
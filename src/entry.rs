@@ -0,0 +1,269 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::utils::{get_commit_url, get_issue_url, get_pull_request_url};
+
+const COMMIT_SHORT_LEN: usize = 7;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reference {
+    PullRequest(u64),
+    Issue(u64),
+    Commit(String),
+}
+
+/// Helper for building a correctly formatted Markdown change entry.
+///
+/// Plain text is escaped, code spans are fenced with enough backticks and pull request, issue
+/// and commit references are appended as a trailing parenthetical, linked to the repository if
+/// its URL is provided.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::Entry;
+///
+/// let entry = Entry::new("Fix nested lists in")
+///     .code_span("Parser::parse")
+///     .pr(123)
+///     .commit("abc1234def");
+///
+/// assert_eq!(entry.to_string(), "Fix nested lists in `Parser::parse` (#123, abc1234)");
+///
+/// let entry = Entry::new("Fix nested lists")
+///     .url("https://github.com/napalmpapalam/keep-a-changelog-rs")
+///     .pr(123);
+///
+/// assert_eq!(
+///     entry.to_string(),
+///     "Fix nested lists ([#123](https://github.com/napalmpapalam/keep-a-changelog-rs/pull/123))"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Entry {
+    text: String,
+    url: Option<String>,
+    references: Vec<Reference>,
+}
+
+impl Entry {
+    /// Create a new entry from plain text, Markdown special characters are escaped.
+    pub fn new<S: AsRef<str>>(text: S) -> Self {
+        Self {
+            text: escape(text.as_ref()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new entry from already formatted Markdown, the text is kept as is.
+    pub fn raw<S: Into<String>>(markdown: S) -> Self {
+        Self {
+            text: markdown.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set the repository URL used to link pull requests, issues and commits.
+    pub fn url<S: Into<String>>(mut self, url: S) -> Self {
+        self.url = Some(url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Append plain text, Markdown special characters are escaped.
+    pub fn text<S: AsRef<str>>(mut self, text: S) -> Self {
+        self.push(&escape(text.as_ref()));
+        self
+    }
+
+    /// Append an inline code span.
+    pub fn code_span<S: AsRef<str>>(mut self, code: S) -> Self {
+        self.push(&code_span(code.as_ref()));
+        self
+    }
+
+    /// Append an inline link, the link text is escaped.
+    pub fn link<S: AsRef<str>>(mut self, text: S, url: S) -> Self {
+        let url = url.as_ref();
+        let link = if url.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+            format!("[{}](<{url}>)", escape(text.as_ref()))
+        } else {
+            format!("[{}]({url})", escape(text.as_ref()))
+        };
+        self.push(&link);
+        self
+    }
+
+    /// Reference a pull request (or merge request on GitLab).
+    pub fn pr(mut self, number: u64) -> Self {
+        self.references.push(Reference::PullRequest(number));
+        self
+    }
+
+    /// Reference an issue.
+    pub fn issue(mut self, number: u64) -> Self {
+        self.references.push(Reference::Issue(number));
+        self
+    }
+
+    /// Reference a commit, the SHA is shortened in the rendered text.
+    pub fn commit<S: Into<String>>(mut self, sha: S) -> Self {
+        self.references.push(Reference::Commit(sha.into()));
+        self
+    }
+
+    fn push(&mut self, value: &str) {
+        if !self.text.is_empty() && !self.text.ends_with(char::is_whitespace) {
+            self.text.push(' ');
+        }
+        self.text.push_str(value);
+    }
+
+    fn format_reference(&self, reference: &Reference) -> String {
+        let (text, url) = match (reference, &self.url) {
+            (Reference::PullRequest(number), Some(url)) => {
+                (format!("#{number}"), get_pull_request_url(url, *number))
+            }
+            (Reference::Issue(number), Some(url)) => {
+                (format!("#{number}"), get_issue_url(url, *number))
+            }
+            (Reference::Commit(sha), Some(url)) => {
+                (code_span(short_sha(sha)), get_commit_url(url, sha))
+            }
+            (Reference::PullRequest(number) | Reference::Issue(number), None) => {
+                return format!("#{number}")
+            }
+            (Reference::Commit(sha), None) => return short_sha(sha).to_string(),
+        };
+
+        format!("[{text}]({url})")
+    }
+}
+
+impl Display for Entry {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)?;
+
+        if !self.references.is_empty() {
+            let references = self
+                .references
+                .iter()
+                .map(|reference| self.format_reference(reference))
+                .collect::<Vec<_>>()
+                .join(", ");
+            write!(f, " ({references})")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<Entry> for String {
+    fn from(entry: Entry) -> Self {
+        entry.to_string()
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    match sha.char_indices().nth(COMMIT_SHORT_LEN) {
+        Some((idx, _)) => &sha[..idx],
+        None => sha,
+    }
+}
+
+/// Escape Markdown special characters in plain text.
+fn escape(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut result = String::with_capacity(text.len());
+
+    for (idx, ch) in chars.iter().enumerate() {
+        let escape = match ch {
+            '\\' | '`' | '*' | '[' | ']' | '<' | '>' => true,
+            // intraword underscores do not start emphasis, so identifiers are kept readable
+            '_' => {
+                let prev = idx.checked_sub(1).and_then(|i| chars.get(i));
+                let next = chars.get(idx + 1);
+                !(prev.is_some_and(|c| c.is_alphanumeric())
+                    && next.is_some_and(|c| c.is_alphanumeric()))
+            }
+            _ => false,
+        };
+
+        if escape {
+            result.push('\\');
+        }
+        result.push(*ch);
+    }
+
+    result
+}
+
+/// Wrap code in a code span using a backtick fence longer than any backtick run inside it.
+fn code_span(code: &str) -> String {
+    let mut longest_run = 0;
+    let mut run = 0;
+
+    for ch in code.chars() {
+        if ch == '`' {
+            run += 1;
+            longest_run = longest_run.max(run);
+        } else {
+            run = 0;
+        }
+    }
+
+    let fence = "`".repeat(longest_run + 1);
+
+    if code.starts_with('`') || code.ends_with('`') {
+        return format!("{fence} {code} {fence}");
+    }
+
+    format!("{fence}{code}{fence}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("Use *bold* [x] <tag>"),
+            "Use \\*bold\\* \\[x\\] \\<tag\\>"
+        );
+        assert_eq!(escape("snake_case _emphasis_"), "snake_case \\_emphasis\\_");
+    }
+
+    #[test]
+    fn test_code_span() {
+        assert_eq!(code_span("Parser::parse"), "`Parser::parse`");
+        assert_eq!(code_span("a`b"), "``a`b``");
+        assert_eq!(code_span("`tick"), "`` `tick ``");
+    }
+
+    #[test]
+    fn test_entry_with_gitlab_references() {
+        let entry = Entry::new("Fix parsing")
+            .url("https://gitlab.com/owner/repo/")
+            .pr(1)
+            .issue(2)
+            .commit("0123456789abcdef");
+
+        assert_eq!(
+            entry.to_string(),
+            "Fix parsing ([#1](https://gitlab.com/owner/repo/-/merge_requests/1), \
+            [#2](https://gitlab.com/owner/repo/-/issues/2), \
+            [`0123456`](https://gitlab.com/owner/repo/-/commit/0123456789abcdef))"
+        );
+    }
+
+    #[test]
+    fn test_raw_entry_with_link() {
+        let entry = Entry::raw("**parser:**")
+            .text("support")
+            .link("CommonMark", "https://commonmark.org");
+
+        assert_eq!(
+            entry.to_string(),
+            "**parser:** support [CommonMark](https://commonmark.org)"
+        );
+    }
+}
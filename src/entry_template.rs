@@ -0,0 +1,240 @@
+//! Required formats of the entries per change kind, see [`TemplateRegistry`].
+use std::collections::HashMap;
+
+use eyre::{Context, OptionExt, Result};
+use regex::Regex;
+
+use crate::{
+    event::ChangelogEvent, validation::Violation, ChangeKind, Changelog, Release, ReleaseVersion,
+};
+
+/// Patterns the entries of a change kind must match, e.g. `CVE-\d+: .+` for Security entries
+///
+/// The whole entry must match the pattern. Entries are checked when the changelog is parsed with
+/// [`ChangelogParseOptions::templates`](crate::ChangelogParseOptions::templates) and when they
+/// are added with [`Changelog::add_entry`].
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{ChangeKind, TemplateRegistry};
+///
+/// let mut templates = TemplateRegistry::new();
+/// templates.register(ChangeKind::Security, r"CVE-\d+-\d+: .+").unwrap();
+///
+/// assert!(templates.matches(&ChangeKind::Security, "CVE-2024-1234: Escape link titles"));
+/// assert!(!templates.matches(&ChangeKind::Security, "Escape link titles"));
+/// assert!(templates.matches(&ChangeKind::Fixed, "Any fix"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TemplateRegistry {
+    templates: HashMap<ChangeKind, (String, Regex)>,
+}
+
+impl TemplateRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Require the entries of `kind` to match `pattern`, replaces the previous pattern of `kind`
+    pub fn register(&mut self, kind: ChangeKind, pattern: &str) -> Result<&mut Self> {
+        let regex = Regex::new(&format!("^(?s:{pattern})$"))
+            .wrap_err_with(|| format!("Invalid template of {kind} entries: `{pattern}`"))?;
+        self.templates.insert(kind, (pattern.to_string(), regex));
+        Ok(self)
+    }
+
+    /// Pattern registered for `kind`
+    pub fn pattern(&self, kind: &ChangeKind) -> Option<&str> {
+        self.templates
+            .get(kind)
+            .map(|(pattern, _)| pattern.as_str())
+    }
+
+    /// Whether the entry matches the template of `kind`, entries of kinds without a template
+    /// always match
+    pub fn matches(&self, kind: &ChangeKind, text: &str) -> bool {
+        self.templates
+            .get(kind)
+            .is_none_or(|(_, regex)| regex.is_match(text))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+}
+
+impl Changelog {
+    /// Replace the entry templates, enforced by [`Changelog::add_entry`] and
+    /// [`Changelog::check_templates`]
+    pub fn set_templates(&mut self, templates: TemplateRegistry) -> &mut Self {
+        self.templates = templates;
+        self
+    }
+
+    /// Add an entry to the release with `version`, Unreleased if `None`
+    ///
+    /// Fails with [`Violation::Template`] if the entry doesn't match the
+    /// [template](Changelog::set_templates) of its kind. The Unreleased release is created if
+    /// it's missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{ChangeKind, Changelog, TemplateRegistry, Violation};
+    ///
+    /// let mut templates = TemplateRegistry::new();
+    /// templates.register(ChangeKind::Security, r"CVE-\d+-\d+: .+").unwrap();
+    ///
+    /// let mut changelog = Changelog::default_with_unreleased();
+    /// changelog.set_templates(templates);
+    ///
+    /// let error = changelog
+    ///     .add_entry(None, ChangeKind::Security, "Escape link titles")
+    ///     .unwrap_err();
+    /// assert!(matches!(error.downcast_ref(), Some(Violation::Template { .. })));
+    ///
+    /// changelog
+    ///     .add_entry(None, ChangeKind::Security, "CVE-2024-1234: Escape link titles")
+    ///     .unwrap();
+    /// ```
+    pub fn add_entry(
+        &mut self,
+        version: Option<ReleaseVersion>,
+        kind: ChangeKind,
+        text: impl Into<String>,
+    ) -> Result<&mut Self> {
+        let text = text.into();
+        if let Some(pattern) = self.templates.pattern(&kind) {
+            if !self.templates.matches(&kind, &text) {
+                let release = match &version {
+                    Some(version) => self.find_release(version.to_string())?,
+                    None => self.get_unreleased(),
+                };
+                return Err(Violation::Template {
+                    index: release.map_or(0, |release| entries_of(release, &kind)),
+                    version,
+                    kind,
+                    pattern: pattern.to_string(),
+                }
+                .into());
+            }
+        }
+
+        match version {
+            Some(version) => {
+                self.find_release_mut(version.to_string())?
+                    .ok_or_eyre(format!("Release {version} not found"))?
+                    .add(kind.clone(), text.clone());
+                self.emit(ChangelogEvent::EntryAdded {
+                    version: Some(version),
+                    kind,
+                    text,
+                });
+                Ok(self)
+            }
+            None => Ok(self.add_unreleased_change(kind, text)),
+        }
+    }
+
+    /// Check all entries against the [templates](Changelog::set_templates)
+    pub fn check_templates(&self) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for release in self.releases() {
+            for (kind, entries) in release.changes().sections() {
+                for (index, entry) in entries.iter().enumerate() {
+                    if !self.templates.matches(&kind, entry) {
+                        violations.push(Violation::Template {
+                            version: release.version().clone(),
+                            kind: kind.clone(),
+                            index,
+                            pattern: self
+                                .templates
+                                .pattern(&kind)
+                                .unwrap_or_default()
+                                .to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        violations
+    }
+}
+
+/// Number of the entries of `kind` in the release
+fn entries_of(release: &Release, kind: &ChangeKind) -> usize {
+    release.changes().iter().filter(|(k, _)| k == kind).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChangelogParseOptions, ParseError};
+
+    fn templates() -> TemplateRegistry {
+        let mut templates = TemplateRegistry::new();
+        templates
+            .register(ChangeKind::Security, r"CVE-\d+-\d+: .+")
+            .unwrap();
+        templates
+    }
+
+    #[test]
+    fn test_parse_templates() -> Result<()> {
+        let markdown =
+            "# Changelog\n## [Unreleased]\n### Security\n- CVE-2024-1234: Escape link titles\n\
+            \n## [0.1.0] - 2024-04-28\n### Security\n- Escape headings\n";
+        let opts = ChangelogParseOptions {
+            templates: Some(templates()),
+            ..Default::default()
+        };
+
+        let error = Changelog::parse(markdown.to_string(), Some(opts.clone())).unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ParseError>(),
+            Some(&ParseError::InvalidEntry {
+                line: 8,
+                kind: ChangeKind::Security,
+                pattern: r"CVE-\d+-\d+: .+".to_string(),
+            })
+        );
+
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+        assert!(changelog.check_templates().is_empty());
+
+        changelog.set_templates(templates());
+        let version = ReleaseVersion::parse("0.1.0")?;
+        assert_eq!(
+            changelog.check_templates(),
+            [Violation::Template {
+                version: Some(version.clone()),
+                kind: ChangeKind::Security,
+                index: 0,
+                pattern: r"CVE-\d+-\d+: .+".to_string(),
+            }]
+        );
+
+        let error = changelog
+            .add_entry(Some(version.clone()), ChangeKind::Security, "Escape links")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r"Entry 2 of Security in 0.1.0 doesn't match the template `CVE-\d+-\d+: .+`"
+        );
+
+        changelog.add_entry(Some(version), ChangeKind::Fixed, "Any fix")?;
+        assert!(changelog
+            .find_release("0.1.0".to_string())?
+            .unwrap()
+            .changes()
+            .iter()
+            .any(|(kind, _)| kind == ChangeKind::Fixed));
+        assert!(TemplateRegistry::new()
+            .register(ChangeKind::Fixed, "(")
+            .is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+use std::fmt::{self, Display, Formatter};
+
+use derive_builder::UninitializedFieldError;
+
+use crate::{conflict::MergeConflict, ChangeKind, Date, ReleaseVersion};
+
+/// Represents an error which occurred while parsing a changelog.
+///
+/// Errors returned by [`Changelog::parse`](crate::Changelog::parse) could be downcast to this
+/// type to handle specific failures programmatically. New variants may be added in minor
+/// releases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The changelog contains unresolved git merge conflict markers.
+    MergeConflict(Vec<MergeConflict>),
+    /// The changelog contains multiple `# ` headings at the listed lines, e.g. concatenated
+    /// changelogs.
+    MultipleDocuments(Vec<usize>),
+    /// A release heading doesn't match `## [VERSION] - DATE` or `## [Unreleased]`.
+    InvalidRelease { line: usize, heading: String },
+    /// A release version isn't a valid semantic version.
+    InvalidVersion { line: usize, version: String },
+    /// A release date isn't a valid `YYYY-MM-DD` date.
+    InvalidDate { line: usize, date: String },
+    /// A `###` section of a release isn't a known change kind.
+    UnknownSection { line: usize, heading: String },
+    /// Release metadata comment isn't a list of `key=value` pairs.
+    InvalidMetadata { line: usize, content: String },
+    /// Content which doesn't belong to any part of the changelog.
+    UnexpectedContent { line: usize, content: String },
+    /// An entry doesn't match the template of its kind, see
+    /// [`ChangelogParseOptions::templates`](crate::ChangelogParseOptions::templates).
+    InvalidEntry {
+        line: usize,
+        kind: ChangeKind,
+        pattern: String,
+    },
+    /// Any other error, e.g. an invalid link definition.
+    Other(String),
+}
+
+impl ParseError {
+    /// Line of the problem, if it's related to a specific line.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            ParseError::MergeConflict(conflicts) => conflicts.first().map(|c| c.start),
+            ParseError::MultipleDocuments(lines) => lines.get(1).copied(),
+            ParseError::InvalidRelease { line, .. }
+            | ParseError::InvalidVersion { line, .. }
+            | ParseError::InvalidDate { line, .. }
+            | ParseError::UnknownSection { line, .. }
+            | ParseError::InvalidMetadata { line, .. }
+            | ParseError::UnexpectedContent { line, .. }
+            | ParseError::InvalidEntry { line, .. } => Some(*line),
+            ParseError::Other(_) => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseError::MergeConflict(conflicts) => {
+                let regions = conflicts
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "Found unresolved merge conflict markers at {regions}, resolve the conflicts before parsing"
+                )
+            }
+            ParseError::MultipleDocuments(lines) => {
+                let lines = lines
+                    .iter()
+                    .map(|line| line.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "Found multiple `# ` headings at lines {lines}, set `first_document_only` to parse only the first document or use `Changelog::parse_documents`"
+                )
+            }
+            ParseError::InvalidRelease { line, heading } => write!(
+                f,
+                "Failed to parse release at line: {line}, content: `## {heading}`. Expected format: `## [VERSION] - [DATE]` or `## [Unreleased]`"
+            ),
+            ParseError::InvalidVersion { line, version } => {
+                write!(f, "Failed to parse version at line: {line}, content: `{version}`")
+            }
+            ParseError::InvalidDate { line, date } => write!(
+                f,
+                "Failed to parse date at line: {line}, content: `{date}`. Expected format: `YYYY-MM-DD`"
+            ),
+            ParseError::UnknownSection { line, heading } => {
+                write!(f, "Unknown change type at line: {line}, content: `### {heading}`")
+            }
+            ParseError::InvalidMetadata { line, content } => write!(
+                f,
+                "Failed to parse release metadata at line: {line}, content: `{content}`. Expected format: `key=value`"
+            ),
+            ParseError::UnexpectedContent { line, content } => {
+                write!(f, "Unexpected content at line: {line}, content: `{content}`")
+            }
+            ParseError::InvalidEntry {
+                line,
+                kind,
+                pattern,
+            } => write!(
+                f,
+                "{kind} entry at line: {line} doesn't match the template `{pattern}`"
+            ),
+            ParseError::Other(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Represents an error which occurred while building a changelog with
+/// [`ChangelogBuilder`](crate::changelog::ChangelogBuilder).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// A required field wasn't set.
+    UninitializedField(&'static str),
+    /// The changelog is invalid, lists all problems found.
+    Invalid(Vec<BuildProblem>),
+}
+
+/// A single problem found while validating a changelog on build.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildProblem {
+    /// Multiple releases have the same version.
+    DuplicateVersion(ReleaseVersion),
+    /// A release has a date, but no version.
+    MissingVersion(Date),
+    /// The git HEAD reference is empty.
+    EmptyHead,
+    /// The tag prefix contains whitespace.
+    InvalidTagPrefix(String),
+    /// The repository URL is invalid.
+    InvalidUrl(String),
+}
+
+impl BuildError {
+    /// Problems found while validating the changelog, empty for uninitialized fields.
+    pub fn problems(&self) -> &[BuildProblem] {
+        match self {
+            BuildError::UninitializedField(_) => &[],
+            BuildError::Invalid(problems) => problems,
+        }
+    }
+}
+
+impl Display for BuildProblem {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            BuildProblem::DuplicateVersion(version) => write!(f, "Duplicate version: {version}"),
+            BuildProblem::MissingVersion(date) => {
+                write!(f, "Release dated {date} has no version")
+            }
+            BuildProblem::EmptyHead => write!(f, "HEAD reference is empty"),
+            BuildProblem::InvalidTagPrefix(prefix) => {
+                write!(f, "Tag prefix `{prefix}` contains whitespace")
+            }
+            BuildProblem::InvalidUrl(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            BuildError::UninitializedField(field) => write!(f, "`{field}` must be initialized"),
+            BuildError::Invalid(problems) => {
+                let problems = problems
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                write!(f, "Invalid changelog: {problems}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+impl From<UninitializedFieldError> for BuildError {
+    fn from(error: UninitializedFieldError) -> Self {
+        BuildError::UninitializedField(error.field_name())
+    }
+}
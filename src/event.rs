@@ -0,0 +1,139 @@
+//! Notifications of changelog mutations, see [`Changelog::on_event`].
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use crate::{ChangeKind, Changelog, Date, ReleaseVersion};
+
+/// Mutation of a changelog, emitted after it's done.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangelogEvent {
+    /// Release added with [`Changelog::add_release`], Unreleased if `version` is `None`
+    ReleaseAdded { version: Option<ReleaseVersion> },
+    /// Entry added to the release with `version`, Unreleased if `None`
+    EntryAdded {
+        version: Option<ReleaseVersion>,
+        kind: ChangeKind,
+        text: String,
+    },
+    /// Unreleased changes promoted with [`Changelog::promote_unreleased`]
+    Promoted { version: ReleaseVersion, date: Date },
+}
+
+type Observer = Arc<dyn Fn(&ChangelogEvent) + Send + Sync>;
+
+/// Registered observers, clones of the changelog keep notifying them.
+#[derive(Clone, Default)]
+pub(crate) struct Observers(Vec<Observer>);
+
+impl Debug for Observers {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
+
+impl Changelog {
+    /// Call `observer` on every mutation made through the changelog methods, e.g. for audit
+    /// logs, notifications or metrics
+    ///
+    /// Events are emitted by [`Changelog::add_release`], [`Changelog::add_unreleased_change`],
+    /// [`Changelog::promote_unreleased`] and the methods built on them. Releases changed through
+    /// [`Changelog::releases_mut`] or [`Changelog::get_unreleased_mut`] aren't observed.
+    ///
+    /// Promoting adds a new empty Unreleased release, which is reported as
+    /// [`ChangelogEvent::ReleaseAdded`] before [`ChangelogEvent::Promoted`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// use keep_a_changelog::{
+    ///     event::ChangelogEvent, CalendarDate, ChangeKind, Changelog, Date, Version,
+    /// };
+    ///
+    /// let events = Arc::new(Mutex::new(vec![]));
+    /// let log = events.clone();
+    ///
+    /// let mut changelog = Changelog::default_with_unreleased();
+    /// changelog.on_event(move |event| log.lock().unwrap().push(event.clone()));
+    ///
+    /// changelog.add_unreleased_change(ChangeKind::Added, "New feature");
+    /// changelog
+    ///     .promote_unreleased(Version::new(0, 1, 0), Date::new(2024, 6, 1).unwrap())
+    ///     .unwrap();
+    ///
+    /// let events = events.lock().unwrap();
+    /// assert_eq!(events.len(), 3);
+    /// assert_eq!(
+    ///     events[0],
+    ///     ChangelogEvent::EntryAdded {
+    ///         version: None,
+    ///         kind: ChangeKind::Added,
+    ///         text: "New feature".to_string(),
+    ///     }
+    /// );
+    /// assert!(matches!(events[2], ChangelogEvent::Promoted { .. }));
+    /// ```
+    pub fn on_event(
+        &mut self,
+        observer: impl Fn(&ChangelogEvent) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.observers.0.push(Arc::new(observer));
+        self
+    }
+
+    pub(crate) fn emit(&self, event: ChangelogEvent) {
+        self.observers
+            .0
+            .iter()
+            .for_each(|observer| observer(&event));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use eyre::Result;
+    use semver::Version;
+
+    use super::*;
+    use crate::CalendarDate;
+
+    #[test]
+    fn test_on_event() -> Result<()> {
+        let events = Arc::new(Mutex::new(vec![]));
+        let log = events.clone();
+
+        let mut changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+        changelog.on_event(move |event| log.lock().unwrap().push(event.clone()));
+
+        let date = Date::new(2024, 6, 1).unwrap();
+        changelog.promote_unreleased(Version::new(0, 2, 0), date)?;
+        assert!(changelog
+            .promote_unreleased(Version::new(0, 2, 0), date)
+            .is_err());
+
+        changelog
+            .clone()
+            .add_unreleased_change(ChangeKind::Fixed, "Bug fix");
+
+        let version = ReleaseVersion::from(Version::new(0, 2, 0));
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                ChangelogEvent::ReleaseAdded { version: None },
+                ChangelogEvent::Promoted { version, date },
+                ChangelogEvent::EntryAdded {
+                    version: None,
+                    kind: ChangeKind::Fixed,
+                    text: "Bug fix".to_string(),
+                },
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+//! Excerpts of the latest releases for embedding into other documents, e.g. README.md, see
+//! [`Changelog::excerpt`] and [`Changelog::update_readme_section`].
+use std::{fmt, fs, path::Path};
+
+use eyre::{bail, Context, Result};
+
+use crate::{utils::DisplayFn, Changelog, FormatOptions};
+
+/// Options of [`Changelog::excerpt`].
+#[derive(Debug, Clone, Default)]
+pub struct ExcerptOptions {
+    /// Include the Unreleased section if it has changes
+    pub include_unreleased: bool,
+    /// Link release headings to the compare URLs, as the excerpt has no link definitions
+    pub inline_links: bool,
+}
+
+/// Comments delimiting the excerpt in the document, see [`Changelog::update_readme_section`].
+#[derive(Debug, Clone)]
+pub struct ReadmeMarkers {
+    pub start: String,
+    pub end: String,
+}
+
+impl Default for ReadmeMarkers {
+    fn default() -> Self {
+        Self {
+            start: "<!-- changelog start -->".to_string(),
+            end: "<!-- changelog end -->".to_string(),
+        }
+    }
+}
+
+impl Changelog {
+    /// Render the latest `n_releases` releases for embedding into another document
+    ///
+    /// Headings are demoted by one level, so releases are `###` headings and sections are
+    /// `####` headings. Link definitions are omitted, draft releases are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{excerpt::ExcerptOptions, Changelog};
+    ///
+    /// let changelog = Changelog::parse_from_file("tests/data/early_changelog.md", None).unwrap();
+    /// let excerpt = changelog.excerpt(1, ExcerptOptions::default());
+    ///
+    /// assert!(excerpt.starts_with("### [0.1.2] - 2024-05-20\n\n#### Fixed\n"));
+    /// assert!(!excerpt.contains("[0.1.1]"));
+    /// ```
+    pub fn excerpt(&self, n_releases: usize, opts: ExcerptOptions) -> String {
+        let mut changelog = self.clone();
+        changelog.set_format_options(FormatOptions {
+            inline_links: opts.inline_links,
+            ..self.format().clone()
+        });
+
+        let mut count = 0;
+        let releases = self
+            .releases()
+            .iter()
+            .filter(|release| !release.draft())
+            .filter(|release| match release.version() {
+                Some(_) => true,
+                None => opts.include_unreleased && !release.changes().is_empty(),
+            })
+            .take_while(|release| {
+                if release.version().is_some() {
+                    count += 1;
+                }
+                count <= n_releases
+            })
+            .collect::<Vec<_>>();
+
+        let rendered = DisplayFn(|f: &mut fmt::Formatter| {
+            releases
+                .iter()
+                .try_for_each(|release| changelog.fmt_release(f, release))
+        })
+        .to_string()
+        .replace("\n\n\n", "\n\n");
+
+        let mut fenced = false;
+        let mut excerpt = rendered
+            .trim_end()
+            .lines()
+            .map(|line| {
+                if line.trim_start().starts_with("```") {
+                    fenced = !fenced;
+                }
+                match !fenced && line.starts_with('#') {
+                    true => format!("#{line}"),
+                    false => line.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        excerpt.push('\n');
+        excerpt
+    }
+
+    /// Replace the text between the `markers` of the document at `readme_path` with the
+    /// [excerpt](Changelog::excerpt) of the latest `n_releases` releases
+    ///
+    /// Returns whether the document was changed, fails if the markers aren't found.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use keep_a_changelog::{
+    ///     excerpt::{ExcerptOptions, ReadmeMarkers},
+    ///     Changelog,
+    /// };
+    ///
+    /// let changelog = Changelog::parse_from_file("CHANGELOG.md", None).unwrap();
+    /// let markers = ReadmeMarkers::default();
+    /// changelog
+    ///     .update_readme_section("README.md", &markers, 3, ExcerptOptions::default())
+    ///     .unwrap();
+    /// ```
+    pub fn update_readme_section<P: AsRef<Path>>(
+        &self,
+        readme_path: P,
+        markers: &ReadmeMarkers,
+        n_releases: usize,
+        opts: ExcerptOptions,
+    ) -> Result<bool> {
+        let path = readme_path.as_ref();
+        let readme = fs::read_to_string(path)
+            .wrap_err_with(|| format!("Failed to read {}", path.display()))?;
+
+        let Some(start) = readme
+            .find(&markers.start)
+            .map(|idx| idx + markers.start.len())
+        else {
+            bail!("Marker {} not found in {}", markers.start, path.display());
+        };
+        let Some(end) = readme[start..].find(&markers.end).map(|idx| start + idx) else {
+            bail!("Marker {} not found in {}", markers.end, path.display());
+        };
+
+        let excerpt = self.excerpt(n_releases, opts);
+        let updated = format!("{}\n\n{excerpt}\n{}", &readme[..start], &readme[end..]);
+        if updated == readme {
+            return Ok(false);
+        }
+
+        fs::write(path, updated).wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::ChangelogParseOptions;
+
+    #[test]
+    fn test_update_readme_section() -> Result<()> {
+        let changelog = Changelog::parse_from_file(
+            "tests/data/early_changelog_multiple_sections.md",
+            Some(ChangelogParseOptions {
+                url: Some("https://github.com/owner/repo".to_string()),
+                ..Default::default()
+            }),
+        )?;
+        let opts = ExcerptOptions {
+            include_unreleased: true,
+            inline_links: true,
+        };
+
+        let dir = format!("tests/tmp/excerpt_{}", Uuid::new_v4());
+        fs::create_dir_all(&dir)?;
+        let path = format!("{dir}/README.md");
+        fs::write(
+            &path,
+            "# Project\n\n## Changes\n\n<!-- changelog start -->\nstale\n<!-- changelog end -->\n\n## License\n",
+        )?;
+
+        let markers = ReadmeMarkers::default();
+        assert!(changelog.update_readme_section(&path, &markers, 2, opts.clone())?);
+        assert!(!changelog.update_readme_section(&path, &markers, 2, opts.clone())?);
+
+        let readme = fs::read_to_string(&path)?;
+        assert!(readme.starts_with(
+            "# Project\n\n## Changes\n\n<!-- changelog start -->\n\n### [Unreleased](https://github.com/owner/repo/compare/0.1.2...HEAD)\n\n#### Added\n"
+        ));
+        assert!(readme.contains(
+            "### [0.1.1](https://github.com/owner/repo/compare/0.1.0...0.1.1) - 2024-05-18\n"
+        ));
+        assert!(!readme.contains("[0.1.0]("));
+        assert!(!readme.contains("stale"));
+        assert!(readme.ends_with("\n<!-- changelog end -->\n\n## License\n"));
+
+        let missing = ReadmeMarkers {
+            start: "<!-- missing -->".to_string(),
+            ..Default::default()
+        };
+        assert!(changelog
+            .update_readme_section(&path, &missing, 2, opts)
+            .is_err());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::{json, Value};
+
+use super::truncate;
+use crate::{ChangeKind, Release};
+
+/// Slack limits the text of a section block to 3000 characters
+const SLACK_SECTION_LIMIT: usize = 3000;
+/// Slack limits the text of a header block to 150 characters
+const SLACK_HEADER_LIMIT: usize = 150;
+/// Slack limits a message to 50 blocks
+const SLACK_BLOCKS_LIMIT: usize = 50;
+/// Discord limits a message to 2000 characters
+const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+impl ChangeKind {
+    fn emoji(&self) -> &'static str {
+        match self {
+            ChangeKind::Breaking => ":boom:",
+            ChangeKind::Added => ":sparkles:",
+            ChangeKind::Changed => ":recycle:",
+            ChangeKind::Deprecated => ":warning:",
+            ChangeKind::Removed => ":wastebasket:",
+            ChangeKind::Fixed => ":bug:",
+            ChangeKind::Security => ":lock:",
+        }
+    }
+}
+
+impl Release {
+    /// Render release as Slack [Block Kit](https://api.slack.com/block-kit) blocks
+    ///
+    /// Returns the JSON array of blocks: a header with the version and date and a section per
+    /// change kind, truncated to the Slack limits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Release, Version};
+    ///
+    /// let mut release = Release::builder()
+    ///     .version(Version::parse("1.0.0").unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// release.added("New feature".to_string());
+    ///
+    /// let blocks = release.to_slack_blocks();
+    /// assert_eq!(blocks[0]["text"]["text"], "1.0.0");
+    /// assert_eq!(blocks[1]["text"]["text"], "*:sparkles: Added*\n• New feature");
+    /// ```
+    pub fn to_slack_blocks(&self) -> Value {
+        let mut blocks = vec![json!({
+            "type": "header",
+            "text": {
+                "type": "plain_text",
+                "text": truncate(&self.title(), SLACK_HEADER_LIMIT),
+            },
+        })];
+
+        if let Some(description) = self.description() {
+            blocks.push(slack_section(&slack_markdown(description)));
+        }
+
+        for (kind, entries) in self.changes().sections() {
+            let items = entries
+                .iter()
+                .map(|change| format!("• {}", slack_markdown(change)))
+                .collect::<Vec<_>>()
+                .join("\n");
+            let heading = kind.heading();
+            let emoji = kind.emoji();
+            blocks.push(slack_section(&format!("*{emoji} {heading}*\n{items}")));
+        }
+
+        blocks.truncate(SLACK_BLOCKS_LIMIT);
+        Value::Array(blocks)
+    }
+
+    /// Render release as Discord flavored Markdown message
+    ///
+    /// The message is truncated to the Discord limit of 2000 characters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{CalendarDate, Date, Release, Version};
+    ///
+    /// let mut release = Release::builder()
+    ///     .version(Version::parse("1.0.0").unwrap())
+    ///     .date(Date::new(2024, 6, 1).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// release.fixed("Bug fix".to_string());
+    ///
+    /// assert_eq!(
+    ///     release.to_discord_markdown(),
+    ///     "**1.0.0 - 2024-06-01**\n\n**:bug: Fixed**\n- Bug fix\n"
+    /// );
+    /// ```
+    pub fn to_discord_markdown(&self) -> String {
+        let mut message = format!("**{}**\n", self.title());
+
+        if let Some(description) = self.description() {
+            message.push_str(&format!("\n{description}\n"));
+        }
+
+        for (kind, entries) in self.changes().sections() {
+            message.push_str(&format!("\n**{} {}**\n", kind.emoji(), kind.heading()));
+            entries
+                .iter()
+                .for_each(|change| message.push_str(&format!("- {change}\n")));
+        }
+
+        truncate(&message, DISCORD_MESSAGE_LIMIT)
+    }
+}
+
+fn slack_section(text: &str) -> Value {
+    json!({
+        "type": "section",
+        "text": {
+            "type": "mrkdwn",
+            "text": truncate(text, SLACK_SECTION_LIMIT),
+        },
+    })
+}
+
+/// Convert Markdown links and bold text to Slack mrkdwn, control characters are escaped so the
+/// text can't form links or mentions.
+fn slack_markdown(markdown: &str) -> String {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]*)\]\(([^)]*)\)").unwrap());
+
+    let escaped = markdown
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    link.replace_all(&escaped, "<$2|$1>").replace("**", "*")
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use crate::{Changelog, Version};
+
+    #[test]
+    fn test_slack_links() -> Result<()> {
+        let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Fixed\n- **Crash** on start ([#1](https://example.com/1))\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+        let blocks = changelog.releases()[0].to_slack_blocks();
+
+        assert_eq!(blocks[0]["text"]["text"], "0.1.0 - 2024-04-28");
+        assert_eq!(
+            blocks[1]["text"]["text"],
+            "*:bug: Fixed*\n• *Crash* on start (<https://example.com/1|#1>)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_slack_escaped() {
+        assert_eq!(
+            super::slack_markdown(
+                "Fix <script> & <!channel> in [docs](https://example.com/?a=1&b=2)"
+            ),
+            "Fix &lt;script&gt; &amp; &lt;!channel&gt; in <https://example.com/?a=1&amp;b=2|docs>"
+        );
+    }
+
+    #[test]
+    fn test_discord_truncated() -> Result<()> {
+        let mut release = crate::Release::builder()
+            .version(Version::parse("1.0.0")?)
+            .build()?;
+        (0..100).for_each(|i| {
+            release.added(format!(
+                "Feature number {i} with a reasonably long description"
+            ));
+        });
+
+        let message = release.to_discord_markdown();
+        assert_eq!(message.chars().count(), 2000);
+        assert!(message.ends_with('…'));
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt::Write;
+
+use super::{escape, plain_text};
+use crate::{Changelog, Release};
+
+impl Changelog {
+    /// Render releases as AppStream metainfo `<releases>` block
+    ///
+    /// Unreleased and yanked releases are skipped. Release description and changes are rendered
+    /// as plain text paragraphs and lists, since AppStream descriptions only allow a small subset
+    /// of markup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// assert_eq!(
+    ///     changelog.to_appstream(),
+    ///     r#"<releases>
+    ///   <release version="0.1.0" date="2024-04-28">
+    ///     <description>
+    ///       <p>Added</p>
+    ///       <ul>
+    ///         <li>Initial release</li>
+    ///       </ul>
+    ///     </description>
+    ///   </release>
+    /// </releases>
+    /// "#
+    /// );
+    /// ```
+    pub fn to_appstream(&self) -> String {
+        let mut xml = String::from("<releases>\n");
+
+        self.releases()
+            .iter()
+            .filter(|release| !release.yanked() && !release.draft())
+            .for_each(|release| write_release(&mut xml, release));
+
+        xml.push_str("</releases>\n");
+        xml
+    }
+}
+
+fn write_release(xml: &mut String, release: &Release) {
+    let Some(version) = release.version() else {
+        return;
+    };
+
+    let version = escape(&version.to_string());
+    match release.date() {
+        Some(date) => writeln!(xml, r#"  <release version="{version}" date="{date}">"#).unwrap(),
+        None => writeln!(xml, r#"  <release version="{version}">"#).unwrap(),
+    }
+
+    let changes = release.changes();
+    if release.description().is_some() || !changes.is_empty() {
+        xml.push_str("    <description>\n");
+
+        if let Some(description) = release.description() {
+            description
+                .split("\n\n")
+                .map(plain_text)
+                .filter(|p| !p.is_empty())
+                .for_each(|p| writeln!(xml, "      <p>{}</p>", escape(&p)).unwrap());
+        }
+
+        for (kind, entries) in changes.sections() {
+            writeln!(xml, "      <p>{}</p>", kind.heading()).unwrap();
+            xml.push_str("      <ul>\n");
+            entries.iter().for_each(|change| {
+                writeln!(xml, "        <li>{}</li>", escape(&plain_text(change))).unwrap()
+            });
+            xml.push_str("      </ul>\n");
+        }
+
+        xml.push_str("    </description>\n");
+    }
+
+    xml.push_str("  </release>\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use crate::Changelog;
+
+    #[test]
+    fn test_to_appstream() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/early_changelog_multiple_sections.md")?;
+        let changelog = Changelog::parse(markdown, None)?;
+        let xml = changelog.to_appstream();
+
+        assert!(xml.starts_with("<releases>\n  <release version=\"0.1.2\" date=\"2024-05-20\">\n"));
+        assert_eq!(xml.matches("<release ").count(), 3);
+        assert!(!xml.contains("Unreleased"));
+        assert!(xml.ends_with("</release>\n</releases>\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_escape() -> Result<()> {
+        let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Fixed\n- Handle `<T>` & `&str`\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        assert!(changelog
+            .to_appstream()
+            .contains("<li>Handle &lt;T&gt; &amp; &amp;str</li>"));
+        Ok(())
+    }
+}
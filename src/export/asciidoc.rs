@@ -0,0 +1,120 @@
+use std::{fmt::Write, sync::OnceLock};
+
+use regex::Regex;
+
+use super::release_link;
+use crate::{
+    consts::{CHANGELOG_DESCRIPTION, CHANGELOG_TITLE},
+    Changelog,
+};
+
+impl Changelog {
+    /// Render changelog as AsciiDoc
+    ///
+    /// Useful to include the changelog into [Antora](https://antora.org) based documentation
+    /// sites without Markdown converters. Inline links are converted to AsciiDoc link macros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let adoc = changelog.to_asciidoc();
+    /// assert!(adoc.starts_with("= Changelog\n"));
+    /// assert!(adoc.contains("== 0.1.0 - 2024-04-28\n\n=== Added\n\n* Initial release\n"));
+    /// ```
+    pub fn to_asciidoc(&self) -> String {
+        let mut adoc = String::new();
+
+        let title = self
+            .title()
+            .clone()
+            .unwrap_or_else(|| CHANGELOG_TITLE.into());
+        writeln!(adoc, "= {}\n", inline(&title)).unwrap();
+
+        let description = match self.description() {
+            Some(description) => description.trim().to_owned(),
+            None => CHANGELOG_DESCRIPTION.into(),
+        };
+        writeln!(adoc, "{}\n", inline(&description)).unwrap();
+
+        for release in self.releases().iter().filter(|r| !r.draft()) {
+            let mut title = match release_link(self, release) {
+                Some(link) => match release.title().split_once(" - ") {
+                    Some((version, date)) => format!("link:{}[{version}] - {date}", link.url()),
+                    None => format!("link:{}[{}]", link.url(), release.title()),
+                },
+                None => release.title(),
+            };
+            if *release.yanked() {
+                title.push_str(" [YANKED]");
+            }
+            writeln!(adoc, "== {title}\n").unwrap();
+
+            if let Some(description) = release.description() {
+                writeln!(adoc, "{}\n", inline(description.trim())).unwrap();
+            }
+
+            for (kind, entries) in release.changes().sections() {
+                writeln!(adoc, "=== {}\n", kind.heading()).unwrap();
+                entries.iter().for_each(|change| {
+                    writeln!(adoc, "* {}", inline(change).replace('\n', " +\n")).unwrap()
+                });
+                adoc.push('\n');
+            }
+        }
+
+        adoc.truncate(adoc.trim_end().len());
+        adoc.push('\n');
+        adoc
+    }
+}
+
+/// Convert inline Markdown links to AsciiDoc link macros.
+fn inline(markdown: &str) -> String {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]*)\]\(([^)]*)\)").unwrap());
+
+    link.replace_all(markdown, "link:$2[$1]").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_to_asciidoc() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/early_changelog_multiple_sections.md")?;
+        let changelog = Changelog::parse(markdown, None)?;
+        let adoc = changelog.to_asciidoc();
+
+        assert!(adoc.contains(
+            "compare/0.1.2...HEAD[Unreleased]\n\n=== Added\n\n* New feature\n* Another new feature\n\n=== Fixed\n"
+        ));
+        assert!(adoc.contains("link:https://keepachangelog.com/en/1.0.0/[Keep a Changelog]"));
+        assert!(adoc.ends_with("* Initial release\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_release_links() -> Result<()> {
+        let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+        let changelog = Changelog::parse(
+            markdown.to_string(),
+            Some(crate::ChangelogParseOptions {
+                url: Some("https://github.com/owner/repo".to_string()),
+                ..Default::default()
+            }),
+        )?;
+
+        assert!(changelog.to_asciidoc().contains(
+            "== link:https://github.com/owner/repo/releases/tag/0.1.0[0.1.0] - 2024-04-28\n"
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+//! Renderers for formats other than Markdown.
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::{Changelog, Link, Release};
+
+#[cfg(feature = "announce")]
+mod announce;
+mod appstream;
+mod asciidoc;
+mod rst;
+mod security;
+mod store;
+
+pub use store::StoreNotesStrategy;
+
+impl Release {
+    /// Release heading text, e.g. "1.0.0 - 2024-06-01" or "Unreleased".
+    pub(crate) fn title(&self) -> String {
+        let version = self
+            .version()
+            .as_ref()
+            .map(|v| v.to_string())
+            .unwrap_or_else(|| "Unreleased".to_string());
+
+        match self.date() {
+            Some(date) => format!("{version} - {date}"),
+            None => version,
+        }
+    }
+}
+
+/// Compare or release link of the release, if the changelog has repository URL.
+pub(crate) fn release_link(changelog: &Changelog, release: &Release) -> Option<Link> {
+    changelog.url().as_ref()?;
+    release.compare_link(changelog).ok().flatten()
+}
+
+/// Strip inline Markdown formatting, keeping the link text.
+pub(crate) fn plain_text(markdown: &str) -> String {
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap());
+
+    link.replace_all(markdown, "$1")
+        .replace("**", "")
+        .replace('`', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Truncate text to `limit` characters, ending with an ellipsis if truncated.
+pub(crate) fn truncate(text: &str, limit: usize) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+
+    let mut truncated = text
+        .chars()
+        .take(limit.saturating_sub(1))
+        .collect::<String>();
+    if limit > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
+/// Escape XML special characters.
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(
+            plain_text("**BREAKING** Fix `parse` ([#12](https://example.com/pull/12))"),
+            "BREAKING Fix parse (#12)"
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("ßßßßßß", 4), "ßßß…");
+        assert_eq!(truncate("short", 0), "");
+    }
+}
@@ -0,0 +1,140 @@
+use std::{fmt::Write, sync::OnceLock};
+
+use regex::Regex;
+
+use super::release_link;
+use crate::{
+    consts::{CHANGELOG_DESCRIPTION, CHANGELOG_TITLE},
+    Changelog,
+};
+
+impl Changelog {
+    /// Render changelog as reStructuredText
+    ///
+    /// Useful to include the changelog into [Sphinx](https://www.sphinx-doc.org) documentation
+    /// without Markdown converters. Inline code spans and links are converted to their
+    /// reStructuredText counterparts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial `parse`\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let rst = changelog.to_rst();
+    /// assert!(rst.starts_with("Changelog\n=========\n"));
+    /// assert!(rst.contains("0.1.0 - 2024-04-28\n------------------\n\nAdded\n~~~~~\n\n- Initial ``parse``\n"));
+    /// ```
+    pub fn to_rst(&self) -> String {
+        let mut rst = String::new();
+
+        let title = self
+            .title()
+            .clone()
+            .unwrap_or_else(|| CHANGELOG_TITLE.into());
+        heading(&mut rst, &inline(&title), '=');
+
+        let description = match self.description() {
+            Some(description) => description.trim().to_owned(),
+            None => CHANGELOG_DESCRIPTION.into(),
+        };
+        writeln!(rst, "{}\n", inline(&description)).unwrap();
+
+        for release in self.releases().iter().filter(|r| !r.draft()) {
+            let mut title = match release_link(self, release) {
+                Some(link) => {
+                    let (version, date) = release.title().split_once(" - ").map_or_else(
+                        || (release.title(), None),
+                        |(version, date)| (version.to_string(), Some(date.to_string())),
+                    );
+                    let version = format!("`{version} <{}>`__", link.url());
+                    match date {
+                        Some(date) => format!("{version} - {date}"),
+                        None => version,
+                    }
+                }
+                None => release.title(),
+            };
+            if *release.yanked() {
+                title.push_str(" [YANKED]");
+            }
+            heading(&mut rst, &title, '-');
+
+            if let Some(description) = release.description() {
+                writeln!(rst, "{}\n", inline(description.trim())).unwrap();
+            }
+
+            for (kind, entries) in release.changes().sections() {
+                heading(&mut rst, kind.heading(), '~');
+                entries.iter().for_each(|change| {
+                    writeln!(rst, "- {}", inline(change).replace('\n', "\n  ")).unwrap()
+                });
+                rst.push('\n');
+            }
+        }
+
+        rst.truncate(rst.trim_end().len());
+        rst.push('\n');
+        rst
+    }
+}
+
+fn heading(rst: &mut String, title: &str, underline: char) {
+    let underline = underline.to_string().repeat(title.chars().count());
+    writeln!(rst, "{title}\n{underline}\n").unwrap();
+}
+
+/// Convert inline Markdown code spans and links to reStructuredText.
+fn inline(markdown: &str) -> String {
+    static CODE: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let code = CODE.get_or_init(|| Regex::new(r"`([^`]+)`").unwrap());
+    let link = LINK.get_or_init(|| Regex::new(r"\[([^\]]*)\]\(([^)]*)\)").unwrap());
+
+    let text = code.replace_all(markdown, "``$1``");
+    link.replace_all(&text, "`$1 <$2>`__").into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_to_rst() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/early_changelog_multiple_sections.md")?;
+        let changelog = Changelog::parse(markdown, None)?;
+        let rst = changelog.to_rst();
+
+        assert!(rst.contains(
+            "-\n\nAdded\n~~~~~\n\n- New feature\n- Another new feature\n\nFixed\n~~~~~\n"
+        ));
+        assert!(rst.contains("compare/0.1.2...HEAD>`__\n---"));
+        assert!(rst.contains("releases/tag/0.1.0>`__ - 2024-04-28\n"));
+        assert!(rst.contains("`Keep a Changelog <https://keepachangelog.com/en/1.0.0/>`__"));
+        assert!(rst.ends_with("- Initial release\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_release_links() -> Result<()> {
+        let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+        let changelog = Changelog::parse(
+            markdown.to_string(),
+            Some(crate::ChangelogParseOptions {
+                url: Some("https://github.com/owner/repo".to_string()),
+                ..Default::default()
+            }),
+        )?;
+
+        let title = "`0.1.0 <https://github.com/owner/repo/releases/tag/0.1.0>`__ - 2024-04-28";
+        let underline = "-".repeat(title.len());
+        assert!(changelog
+            .to_rst()
+            .contains(&format!("{title}\n{underline}\n")));
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+use std::fmt::Write;
+
+#[cfg(feature = "json")]
+use serde_json::{json, Value};
+
+use super::{escape, plain_text, release_link};
+use crate::{
+    consts::CHANGELOG_TITLE,
+    date::{self, CalendarDate},
+    ChangeKind, Changelog, Date, ReleaseVersion,
+};
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+impl Changelog {
+    /// Get all `Security` entries of the released versions, newest first
+    ///
+    /// Unreleased and draft releases are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Security\n- Pending fix\n\
+    ///     \n## [1.0.1] - 2024-05-20\n### Security\n- Escape HTML in titles\n\
+    ///     \n## [1.0.0] - 2024-05-18\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let report = changelog.security_report();
+    /// assert_eq!(report.len(), 1);
+    /// assert_eq!(report[0].0.to_string(), "1.0.1");
+    /// assert_eq!(report[0].2, "Escape HTML in titles");
+    /// ```
+    pub fn security_report(&self) -> Vec<(ReleaseVersion, Date, &str)> {
+        self.releases()
+            .iter()
+            .filter(|release| !release.draft())
+            .filter_map(|release| {
+                let version = release.version().clone()?;
+                let date = (*release.date())?;
+                Some((version, date, release))
+            })
+            .flat_map(|(version, date, release)| {
+                release
+                    .changes()
+                    .iter()
+                    .filter(|(kind, _)| *kind == ChangeKind::Security)
+                    .map(move |(_, change)| (version.clone(), date, change.as_str()))
+            })
+            .collect()
+    }
+
+    /// Render the [security report](Changelog::security_report) as JSON array, requires the `json`
+    /// feature
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [1.0.1] - 2024-05-20\n### Security\n- Escape HTML\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// assert_eq!(
+    ///     changelog.to_security_json().to_string(),
+    ///     r#"[{"date":"2024-05-20","entry":"Escape HTML","version":"1.0.1"}]"#
+    /// );
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_security_json(&self) -> Value {
+        self.security_report()
+            .into_iter()
+            .map(|(version, date, entry)| {
+                json!({
+                    "version": version.to_string(),
+                    "date": date.to_string(),
+                    "entry": entry,
+                })
+            })
+            .collect()
+    }
+
+    /// Render the [security report](Changelog::security_report) as RSS 2.0 feed
+    ///
+    /// Every entry is a feed item linking to the release, if the changelog has repository URL.
+    pub fn to_security_rss(&self) -> String {
+        let title = self.title().as_deref().unwrap_or(CHANGELOG_TITLE);
+
+        let mut rss = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        rss.push_str("<rss version=\"2.0\">\n  <channel>\n");
+        writeln!(rss, "    <title>{} - Security fixes</title>", escape(title)).unwrap();
+        if let Some(url) = self.url() {
+            writeln!(rss, "    <link>{}</link>", escape(url)).unwrap();
+        }
+        rss.push_str("    <description>Security fixes</description>\n");
+
+        for (version, date, entry) in self.security_report() {
+            let pub_date = rfc2822(date);
+
+            rss.push_str("    <item>\n");
+            writeln!(
+                rss,
+                "      <title>{version}: {}</title>",
+                escape(&plain_text(entry))
+            )
+            .unwrap();
+
+            let link = self
+                .find_release(version.to_string())
+                .ok()
+                .flatten()
+                .and_then(|release| release_link(self, release));
+            if let Some(link) = link {
+                writeln!(rss, "      <link>{}</link>", escape(link.url())).unwrap();
+            }
+
+            writeln!(rss, "      <pubDate>{pub_date}</pubDate>").unwrap();
+            rss.push_str("    </item>\n");
+        }
+
+        rss.push_str("  </channel>\n</rss>\n");
+        rss
+    }
+}
+
+/// RFC 2822 date-time of the start of the day in UTC, e.g. `Mon, 20 May 2024 00:00:00 +0000`
+fn rfc2822(date: Date) -> String {
+    let (year, month, day) = date.ymd();
+    let weekday = WEEKDAYS[date::weekday(date) as usize - 1];
+    let month = MONTHS[month as usize - 1];
+    format!("{weekday}, {day} {month} {year} 00:00:00 +0000")
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use crate::Changelog;
+
+    #[test]
+    fn test_to_security_rss() -> Result<()> {
+        let markdown =
+            "# Changelog\n## [1.0.1] - 2024-05-20\n### Security\n- Escape `<script>` tags\n\
+            \n## [1.0.0] - 2024-05-18\n### Added\n- Initial release\n\
+            \n[1.0.1]: https://github.com/owner/repo/compare/1.0.0...1.0.1\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+        let rss = changelog.to_security_rss();
+
+        assert!(rss.contains("<title>Changelog - Security fixes</title>"));
+        assert!(rss.contains("<link>https://github.com/owner/repo</link>"));
+        assert!(rss.contains(
+            "    <item>\n      <title>1.0.1: Escape &lt;script&gt; tags</title>\n      \
+            <link>https://github.com/owner/repo/compare/1.0.0...1.0.1</link>\n      \
+            <pubDate>Mon, 20 May 2024 00:00:00 +0000</pubDate>\n    </item>\n"
+        ));
+        assert_eq!(rss.matches("<item>").count(), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use super::{plain_text, truncate};
+use crate::{ChangeKind, Release};
+
+/// Sections of store notes, users care about new features and fixes the most
+const PRIORITY: [ChangeKind; 7] = [
+    ChangeKind::Added,
+    ChangeKind::Fixed,
+    ChangeKind::Changed,
+    ChangeKind::Security,
+    ChangeKind::Breaking,
+    ChangeKind::Deprecated,
+    ChangeKind::Removed,
+];
+
+/// How [`Release::to_store_notes`] fits the notes into the character limit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StoreNotesStrategy {
+    /// Leave out whole entries of the lowest priority and end with `+ N more`
+    #[default]
+    DropEntries,
+    /// Cut the notes at the limit, ending with an ellipsis
+    Truncate,
+}
+
+impl Release {
+    /// Character limit of the "What's New" text in the App Store
+    pub const APP_STORE_LIMIT: usize = 4000;
+    /// Character limit of the release notes in Google Play
+    pub const PLAY_STORE_LIMIT: usize = 500;
+
+    /// Render the release as plain text notes for app stores, at most `limit` characters long
+    ///
+    /// Sections are ordered by priority, Added and Fixed first, and Markdown formatting is
+    /// stripped. Notes over the limit are shortened with the `strategy`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, Release, StoreNotesStrategy};
+    ///
+    /// let changes = Changes::builder()
+    ///     .changed("Faster **startup**")
+    ///     .fixed("Crash on empty lists")
+    ///     .added("Dark mode")
+    ///     .build()
+    ///     .unwrap();
+    /// let release = Release::builder().changes(changes).build().unwrap();
+    ///
+    /// assert_eq!(
+    ///     release.to_store_notes(Release::PLAY_STORE_LIMIT, StoreNotesStrategy::DropEntries),
+    ///     "Added\n• Dark mode\n\nFixed\n• Crash on empty lists\n\nChanged\n• Faster startup"
+    /// );
+    /// assert_eq!(
+    ///     release.to_store_notes(60, StoreNotesStrategy::DropEntries),
+    ///     "Added\n• Dark mode\n\nFixed\n• Crash on empty lists\n\n+ 1 more"
+    /// );
+    /// assert_eq!(
+    ///     release.to_store_notes(25, StoreNotesStrategy::Truncate),
+    ///     "Added\n• Dark mode\n\nFixed…"
+    /// );
+    /// ```
+    pub fn to_store_notes(&self, limit: usize, strategy: StoreNotesStrategy) -> String {
+        let mut sections = PRIORITY
+            .iter()
+            .map(|kind| {
+                let entries = self
+                    .changes()
+                    .iter()
+                    .filter(|(k, _)| k == kind)
+                    .map(|(_, change)| plain_text(change))
+                    .collect::<Vec<_>>();
+                (kind, entries)
+            })
+            .filter(|(_, entries)| !entries.is_empty())
+            .collect::<Vec<_>>();
+
+        let notes = render(&sections, 0);
+        if strategy == StoreNotesStrategy::Truncate || notes.chars().count() <= limit {
+            return truncate(&notes, limit);
+        }
+
+        // Drop the entries of the lowest priority until the notes and the footer fit
+        let mut dropped = 0;
+        while let Some((_, entries)) = sections.last_mut() {
+            entries.pop();
+            dropped += 1;
+            if entries.is_empty() {
+                sections.pop();
+            }
+
+            let notes = render(&sections, dropped);
+            if notes.chars().count() <= limit {
+                return notes;
+            }
+        }
+
+        truncate(&render(&sections, dropped), limit)
+    }
+}
+
+fn render(sections: &[(&ChangeKind, Vec<String>)], dropped: usize) -> String {
+    let mut blocks = sections
+        .iter()
+        .map(|(kind, entries)| {
+            let items = entries
+                .iter()
+                .map(|entry| format!("• {entry}"))
+                .collect::<Vec<_>>()
+                .join("\n");
+            format!("{}\n{items}", kind.heading())
+        })
+        .collect::<Vec<_>>();
+
+    if dropped > 0 {
+        blocks.push(format!("+ {dropped} more"));
+    }
+
+    blocks.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::fits(
+        200,
+        StoreNotesStrategy::DropEntries,
+        "Added\n• Dark mode\n• Widgets\n\nFixed\n• Crash on start"
+    )]
+    #[case::dropped(
+        40,
+        StoreNotesStrategy::DropEntries,
+        "Added\n• Dark mode\n• Widgets\n\n+ 1 more"
+    )]
+    #[case::all_dropped(10, StoreNotesStrategy::DropEntries, "+ 3 more")]
+    #[case::too_short(5, StoreNotesStrategy::DropEntries, "+ 3 …")]
+    #[case::truncated(12, StoreNotesStrategy::Truncate, "Added\n• Dar…")]
+    fn test_store_notes(
+        #[case] limit: usize,
+        #[case] strategy: StoreNotesStrategy,
+        #[case] expected: &str,
+    ) {
+        let mut release = Release::builder().build().unwrap();
+        release
+            .fixed("Crash on start".to_string())
+            .added("Dark mode".to_string())
+            .added("[Widgets](https://example.com/widgets)".to_string());
+
+        let notes = release.to_store_notes(limit, strategy);
+        assert_eq!(notes, expected);
+        assert!(notes.chars().count() <= limit);
+    }
+}
@@ -0,0 +1,167 @@
+//! [`miette::Diagnostic`] implementations for pretty reporting of parse and validation errors.
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+use miette::{Diagnostic, LabeledSpan, NamedSource};
+
+use crate::{
+    diagnostics::{parse_error_spans, violation_spans, Lines},
+    ParseError, Violation,
+};
+
+impl Diagnostic for ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(format!("keep_a_changelog::{}", self.code())))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = match self {
+            ParseError::InvalidVersion { .. } => "use a semantic version, e.g. `1.2.3`",
+            ParseError::UnknownSection { .. } => {
+                "use one of Added, Changed, Deprecated, Removed, Fixed, Security or Breaking Changes"
+            }
+            ParseError::MergeConflict(_) => "resolve the conflicts before parsing",
+            _ => return None,
+        };
+        Some(Box::new(help))
+    }
+}
+
+impl Diagnostic for Violation {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(format!("keep_a_changelog::{}", self.code())))
+    }
+}
+
+/// Parse or validation error together with the CHANGELOG.md source, rendered by miette with
+/// the offending lines underlined.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::Changelog;
+///
+/// let markdown = "# Changelog\n## [1.0.0] - 2024-13-01\n### Fixed\n- Bug fix\n";
+/// let errors = Changelog::parse_recovering(markdown.to_string(), None).unwrap_err();
+///
+/// let report = miette::Report::new(errors[0].clone().with_source("CHANGELOG.md", markdown));
+/// assert_eq!(report.labels().unwrap().count(), 1);
+/// ```
+#[derive(Debug)]
+pub struct SourceError {
+    error: Box<dyn Diagnostic + Send + Sync>,
+    source: NamedSource<String>,
+    labels: Vec<LabeledSpan>,
+}
+
+impl SourceError {
+    fn new<E: Diagnostic + Send + Sync + 'static>(
+        error: E,
+        name: &str,
+        source: &str,
+        labels: Vec<(Range<usize>, &str)>,
+    ) -> Self {
+        let labels = labels
+            .into_iter()
+            .map(|(range, label)| {
+                LabeledSpan::new(Some(label.to_string()), range.start, range.len())
+            })
+            .collect();
+
+        Self {
+            error: Box::new(error),
+            source: NamedSource::new(name, source.to_string()),
+            labels,
+        }
+    }
+}
+
+impl Display for SourceError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl std::error::Error for SourceError {}
+
+impl Diagnostic for SourceError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.code()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        self.error.help()
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.source)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        Some(Box::new(self.labels.iter().cloned()))
+    }
+}
+
+impl ParseError {
+    /// Attach the CHANGELOG.md `source` named `name` to the error for reporting with miette.
+    pub fn with_source(self, name: &str, source: &str) -> SourceError {
+        let labels = parse_error_spans(&self, &Lines::new(source));
+        SourceError::new(self, name, source, labels)
+    }
+}
+
+impl Violation {
+    /// Attach the CHANGELOG.md `source` named `name` to the violation for reporting with miette.
+    pub fn with_source(self, name: &str, source: &str) -> SourceError {
+        let labels = violation_spans(&self, &Lines::new(source));
+        SourceError::new(self, name, source, labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+    use miette::{GraphicalReportHandler, GraphicalTheme};
+
+    use super::*;
+    use crate::{style::StylePolicy, Changelog};
+
+    fn render(error: SourceError) -> String {
+        let mut output = String::new();
+        GraphicalReportHandler::new_themed(GraphicalTheme::unicode_nocolor())
+            .render_report(&mut output, &error)
+            .unwrap();
+        output
+    }
+
+    #[test]
+    fn test_parse_error_report() {
+        let markdown = "\n# Changelog\n\n## [0.2.0] - 2024-13-01\n\n### Fixed\n\n- Bug fix\n";
+        let errors = Changelog::parse_recovering(markdown.to_string(), None).unwrap_err();
+        let report = render(errors[0].clone().with_source("CHANGELOG.md", markdown));
+
+        assert!(report.contains("keep_a_changelog::parse::invalid_date"));
+        assert!(report.contains("[CHANGELOG.md:4:1]"));
+        assert!(report.contains("## [0.2.0] - 2024-13-01"));
+        assert!(report.contains("invalid date"));
+    }
+
+    #[test]
+    fn test_violation_report() -> Result<()> {
+        let markdown =
+            "# Changelog\n\n## [0.1.0] - 2024-06-01\n\n### Fixed\n\n- Bug fix\n- bug fix.\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+        let violation = changelog
+            .check_style(&StylePolicy::default())
+            .into_iter()
+            .next()
+            .unwrap();
+        let report = render(violation.with_source("CHANGELOG.md", markdown));
+
+        assert!(report.contains("keep_a_changelog::style::capitalize"));
+        assert!(report.contains("[CHANGELOG.md:8:1]"));
+        Ok(())
+    }
+}
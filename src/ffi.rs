@@ -0,0 +1,285 @@
+//! C-compatible FFI layer.
+//!
+//! The C header `include/keep_a_changelog.h` is generated by
+//! [cbindgen](https://github.com/mozilla/cbindgen), regenerate it after changing the API:
+//!
+//! ```sh
+//! cbindgen --config cbindgen.toml --output include/keep_a_changelog.h
+//! ```
+//!
+//! The library is built as a `rlib` by default, build the C library explicitly:
+//!
+//! ```sh
+//! cargo rustc --lib --release --crate-type cdylib --features ffi
+//! ```
+//!
+//! ```c
+//! #include "keep_a_changelog.h"
+//!
+//! KacChangelog *changelog = kac_changelog_parse(buf, len);
+//! if (changelog == NULL) {
+//!     fprintf(stderr, "%s\n", kac_last_error());
+//!     return 1;
+//! }
+//!
+//! kac_changelog_add_entry(changelog, NULL, "fixed", "Crash on start");
+//!
+//! size_t size = kac_changelog_render(changelog, NULL, 0);
+//! char *out = malloc(size + 1);
+//! kac_changelog_render(changelog, out, size + 1);
+//!
+//! kac_changelog_free(changelog);
+//! ```
+use std::{
+    cell::RefCell,
+    ffi::{c_char, c_int, CStr, CString},
+    panic::AssertUnwindSafe,
+    ptr, slice,
+    str::FromStr,
+};
+
+use eyre::{Context, OptionExt, Result};
+
+use crate::{ChangeKind, Changelog, Release};
+
+/// Opaque changelog handle.
+pub struct KacChangelog(Changelog);
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(e: eyre::Error) {
+    let message = CString::new(format!("{e:#}").replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Message of the last error occurred on the current thread, or NULL if there was none.
+///
+/// The returned string is valid until the next failed call on the same thread.
+#[no_mangle]
+pub extern "C" fn kac_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Parse changelog from UTF-8 Markdown buffer of `len` bytes.
+///
+/// Returns NULL on error, see [`kac_last_error`]. The handle must be released with
+/// [`kac_changelog_free`].
+///
+/// # Safety
+///
+/// `buf` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kac_changelog_parse(buf: *const u8, len: usize) -> *mut KacChangelog {
+    if buf.is_null() {
+        set_last_error(eyre::eyre!("Buffer is NULL"));
+        return ptr::null_mut();
+    }
+
+    let bytes = slice::from_raw_parts(buf, len);
+    let result = catch_panic("Failed to parse changelog", || {
+        let markdown = std::str::from_utf8(bytes).wrap_err("Changelog is not valid UTF-8")?;
+        Changelog::parse(markdown.to_string(), None)
+    });
+
+    match result {
+        Ok(changelog) => Box::into_raw(Box::new(KacChangelog(changelog))),
+        Err(e) => {
+            set_last_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Render changelog as Markdown into `buf` of `len` bytes, NUL-terminated.
+///
+/// Returns the length of the rendered Markdown excluding the terminating NUL, as `snprintf`
+/// does, so the output was truncated if the returned value is not less than `len`. Pass NULL
+/// `buf` to query the required size. Returns -1 on error, see [`kac_last_error`].
+///
+/// # Safety
+///
+/// `changelog` must be a valid handle and `buf` must point to `len` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn kac_changelog_render(
+    changelog: *const KacChangelog,
+    buf: *mut c_char,
+    len: usize,
+) -> isize {
+    let Some(changelog) = changelog.as_ref() else {
+        set_last_error(eyre::eyre!("Changelog is NULL"));
+        return -1;
+    };
+
+    // Rendering doesn't mutate the changelog, so a panic can't leave it in a broken state
+    let rendered = match catch_panic("Failed to render changelog", || Ok(changelog.0.to_string())) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            set_last_error(e);
+            return -1;
+        }
+    };
+
+    if !buf.is_null() && len > 0 {
+        let count = rendered.len().min(len - 1);
+        ptr::copy_nonoverlapping(rendered.as_ptr(), buf.cast(), count);
+        *buf.add(count) = 0;
+    }
+
+    rendered.len() as isize
+}
+
+/// Add entry of `kind` (e.g. "added", "fixed") to the release with `version`, or to the
+/// Unreleased release if `version` is NULL. The Unreleased release is created if missing.
+///
+/// Returns 0 on success and -1 on error, see [`kac_last_error`].
+///
+/// # Safety
+///
+/// `changelog` must be a valid handle, `kind` and `text` must be valid NUL-terminated strings
+/// and `version` must be either NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn kac_changelog_add_entry(
+    changelog: *mut KacChangelog,
+    version: *const c_char,
+    kind: *const c_char,
+    text: *const c_char,
+) -> c_int {
+    let result = catch_panic("Failed to add entry", || {
+        let changelog = changelog.as_mut().ok_or_eyre("Changelog is NULL")?;
+        let version = match version.is_null() {
+            true => None,
+            false => Some(to_str(version)?),
+        };
+        let kind = ChangeKind::from_str(to_str(kind)?)?;
+        let text = to_str(text)?;
+
+        add_entry(&mut changelog.0, version, kind, text)
+    });
+
+    match result {
+        Ok(()) => 0,
+        Err(e) => {
+            set_last_error(e);
+            -1
+        }
+    }
+}
+
+/// Release changelog handle, NULL is ignored.
+///
+/// # Safety
+///
+/// `changelog` must be NULL or a handle returned by [`kac_changelog_parse`] that is not yet
+/// released.
+#[no_mangle]
+pub unsafe extern "C" fn kac_changelog_free(changelog: *mut KacChangelog) {
+    if !changelog.is_null() {
+        drop(Box::from_raw(changelog));
+    }
+}
+
+/// Run `f` turning a panic into an error with the `message`, unwinding across the FFI boundary
+/// is undefined behavior.
+fn catch_panic<T>(message: &str, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    std::panic::catch_unwind(AssertUnwindSafe(f))
+        .unwrap_or_else(|_| Err(eyre::eyre!("{message}: panicked")))
+}
+
+unsafe fn to_str<'a>(value: *const c_char) -> Result<&'a str> {
+    if value.is_null() {
+        eyre::bail!("String is NULL");
+    }
+
+    CStr::from_ptr(value)
+        .to_str()
+        .wrap_err("String is not valid UTF-8")
+}
+
+fn add_entry(
+    changelog: &mut Changelog,
+    version: Option<&str>,
+    kind: ChangeKind,
+    text: &str,
+) -> Result<()> {
+    if version.is_none() && changelog.get_unreleased().is_none() {
+        changelog.add_release(Release::builder().build()?);
+    }
+
+    let release = match version {
+        Some(version) => changelog
+            .find_release_mut(version.to_string())?
+            .ok_or_eyre(format!("Release {version} not found"))?,
+        None => changelog
+            .get_unreleased_mut()
+            .ok_or_eyre("Missing Unreleased release")?,
+    };
+
+    let mut changes = release.changes().clone();
+    changes.add(kind, text.to_string());
+    release.set_changes(changes);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+
+        unsafe {
+            let changelog = kac_changelog_parse(markdown.as_ptr(), markdown.len());
+            assert!(!changelog.is_null());
+
+            let kind = CString::new("fixed").unwrap();
+            let text = CString::new("Crash on start").unwrap();
+            assert_eq!(
+                kac_changelog_add_entry(changelog, ptr::null(), kind.as_ptr(), text.as_ptr()),
+                0
+            );
+
+            let size = kac_changelog_render(changelog, ptr::null_mut(), 0);
+            let mut buf = vec![0 as c_char; size as usize + 1];
+            assert_eq!(
+                kac_changelog_render(changelog, buf.as_mut_ptr(), buf.len()),
+                size
+            );
+
+            let rendered = CStr::from_ptr(buf.as_ptr()).to_str().unwrap();
+            assert!(rendered.contains("## [Unreleased]\n### Fixed\n- Crash on start\n"));
+
+            let mut small = [0 as c_char; 4];
+            kac_changelog_render(changelog, small.as_mut_ptr(), small.len());
+            assert_eq!(CStr::from_ptr(small.as_ptr()).to_str().unwrap(), "<!-");
+
+            kac_changelog_free(changelog);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let markdown = "# Changelog\n## Not a release\n";
+
+        unsafe {
+            let changelog = kac_changelog_parse(markdown.as_ptr(), markdown.len());
+            assert!(changelog.is_null());
+            assert!(!kac_last_error().is_null());
+
+            let kind = CString::new("unknown").unwrap();
+            let text = CString::new("Change").unwrap();
+            assert_eq!(
+                kac_changelog_add_entry(ptr::null_mut(), ptr::null(), kind.as_ptr(), text.as_ptr()),
+                -1
+            );
+            let error = CStr::from_ptr(kac_last_error()).to_str().unwrap();
+            assert_eq!(error, "Changelog is NULL");
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::Write;
+
+use crate::{
+    consts::{CHANGELOG_DESCRIPTION, CHANGELOG_TITLE},
+    utils::fnv1a,
+    Changelog,
+};
+
+impl Changelog {
+    /// Fingerprint of the changelog content, stable across platforms and crate versions
+    ///
+    /// Formatting differences are ignored: whitespace, bullet style, compact output, link order
+    /// and release links, which are generated from the repository URL. Release metadata is
+    /// ignored as well. See [`Changelog::semantic_eq`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let a = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial   release\n";
+    /// let b = "# Changelog\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n* Initial release\n";
+    ///
+    /// let a = Changelog::parse(a.to_string(), None).unwrap();
+    /// let b = Changelog::parse(b.to_string(), None).unwrap();
+    /// assert_eq!(a.fingerprint(), b.fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> u64 {
+        fnv1a(&self.canonical())
+    }
+
+    /// Compare content of two changelogs ignoring formatting differences, see
+    /// [`Changelog::fingerprint`].
+    pub fn semantic_eq(&self, other: &Changelog) -> bool {
+        self.canonical() == other.canonical()
+    }
+
+    /// Canonical representation of the changelog content, one item per line.
+    fn canonical(&self) -> String {
+        let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut canonical = String::new();
+
+        let title = self.title().as_deref().unwrap_or(CHANGELOG_TITLE);
+        let description = self
+            .description()
+            .as_deref()
+            .unwrap_or(CHANGELOG_DESCRIPTION);
+        writeln!(canonical, "title {}", normalize(title)).unwrap();
+        writeln!(canonical, "description {}", normalize(description)).unwrap();
+        writeln!(
+            canonical,
+            "url {}",
+            self.url().as_deref().unwrap_or_default()
+        )
+        .unwrap();
+        writeln!(canonical, "head {}", self.head()).unwrap();
+        writeln!(
+            canonical,
+            "tag_prefix {}",
+            self.tag_prefix().as_deref().unwrap_or_default()
+        )
+        .unwrap();
+
+        for release in self.releases() {
+            let version = release
+                .version()
+                .as_ref()
+                .map_or_else(|| "Unreleased".to_string(), |v| v.to_string());
+            let date = release.date().map(|d| d.to_string()).unwrap_or_default();
+            writeln!(
+                canonical,
+                "release {version} {date} yanked={} draft={}",
+                release.yanked(),
+                release.draft()
+            )
+            .unwrap();
+
+            if let Some(description) = release.description() {
+                writeln!(canonical, "description {}", normalize(description)).unwrap();
+            }
+
+            for (kind, change) in release.changes().iter() {
+                writeln!(canonical, "{} {}", kind.heading(), normalize(change)).unwrap();
+            }
+
+            for contributor in release.contributors() {
+                writeln!(canonical, "contributor {}", normalize(contributor)).unwrap();
+            }
+        }
+
+        let mut links = self
+            .links()
+            .iter()
+            .filter(|link| !link.is_release(self))
+            .map(|link| {
+                let title = link.title().as_deref().map(normalize).unwrap_or_default();
+                format!(
+                    "link {} {} {title}",
+                    link.anchor().to_lowercase(),
+                    link.url()
+                )
+            })
+            .collect::<Vec<_>>();
+        links.sort();
+        links
+            .iter()
+            .for_each(|link| canonical.push_str(&format!("{link}\n")));
+
+        if let Some(footer) = self.footer() {
+            writeln!(canonical, "footer {}", normalize(footer)).unwrap();
+        }
+
+        canonical
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_semantic_eq() -> Result<()> {
+        let changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+        let compact = Changelog::parse_from_file(
+            "tests/data/early_changelog_multiple_sections_compact.md",
+            None,
+        )?;
+        assert!(changelog.semantic_eq(&compact));
+        assert_eq!(changelog.fingerprint(), compact.fingerprint());
+
+        let mut changed = changelog.clone();
+        changed.releases_mut()[1].fixed("Another bug fix".to_string());
+        assert!(!changelog.semantic_eq(&changed));
+        assert_ne!(changelog.fingerprint(), changed.fingerprint());
+        Ok(())
+    }
+}
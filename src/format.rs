@@ -0,0 +1,144 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use crate::ChangeKind;
+
+/// Options controlling how a changelog is rendered.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Sort and group entries by scope within each section, see
+    /// [`ChangeEntry::scope`](crate::ChangeEntry)
+    pub group_by_scope: bool,
+    /// Insert `## YYYY` year headings before the first release of each year
+    pub group_by_year: bool,
+    /// Render only this many most recent releases, besides Unreleased, followed by a link to
+    /// the full history
+    pub max_releases: Option<usize>,
+    /// URL of the full history used with `max_releases`, defaults to CHANGELOG.md in the
+    /// repository
+    pub history_url: Option<String>,
+    /// Embed the compare URL into release headings, e.g. `## [1.2.3](https://…) - 2024-01-01`,
+    /// instead of emitting link definitions for releases
+    pub inline_links: bool,
+    /// Compare releases against the previous release which isn't yanked, as yanked tags may be
+    /// deleted from the repository
+    pub skip_yanked: bool,
+    /// Omit the Unreleased section if it has no changes
+    pub hide_empty_unreleased: bool,
+    /// Omit releases without changes and description
+    pub hide_empty_sections: bool,
+    /// Embed a machine-readable summary of the releases into a comment at the bottom of
+    /// CHANGELOG.md, its checksum is used to warn about manual edits of the file
+    #[cfg(feature = "json")]
+    pub sidecar: bool,
+    /// Insert a linked table of contents of this depth after the description, see
+    /// [`Changelog::toc`](crate::Changelog::toc)
+    pub toc: Option<usize>,
+    /// Rewrite every entry of the rendered document, see [`EntryTransform`]
+    pub transform: Option<EntryTransform>,
+    /// Whether release versions are wrapped in brackets, e.g. `## [1.0.0] - 2024-06-01`
+    pub heading_style: HeadingStyle,
+}
+
+/// Style of the release headings, see [`FormatOptions::heading_style`]
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{format::HeadingStyle, Changelog, FormatOptions};
+///
+/// let markdown = "# Changelog\n## Unreleased\n\n## 0.1.0 - 2024-04-28\n### Added\n- Initial release\n";
+/// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+/// assert!(changelog.to_string().contains("## Unreleased\n\n## 0.1.0 - 2024-04-28\n"));
+///
+/// changelog.set_format_options(FormatOptions {
+///     heading_style: HeadingStyle::Bracketed,
+///     ..Default::default()
+/// });
+/// assert!(changelog.to_string().contains("## [Unreleased]\n\n## [0.1.0] - 2024-04-28\n"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HeadingStyle {
+    /// `## [1.0.0] - 2024-06-01`
+    Bracketed,
+    /// `## 1.0.0 - 2024-06-01`
+    Plain,
+    /// Keep the style of each parsed heading, new releases follow the newest parsed heading and
+    /// are bracketed if there is none
+    #[default]
+    Preserve,
+}
+
+/// Function rewriting the entries of the rendered changelog, e.g. to link issue references,
+/// strip internal ticket IDs or redact customer names in a public changelog
+///
+/// The transform is applied when the changelog is rendered with `to_string`, CHANGELOG.md is
+/// always saved with the entries as written.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{format::EntryTransform, Changelog, FormatOptions};
+///
+/// let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Fixed\n- Crash on empty lists JIRA-42\n";
+/// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+/// changelog.set_format_options(FormatOptions {
+///     transform: Some(EntryTransform::new(|_, text| text.replace(" JIRA-42", ""))),
+///     ..Default::default()
+/// });
+///
+/// assert!(changelog.to_string().contains("- Crash on empty lists\n"));
+/// ```
+#[derive(Clone)]
+pub struct EntryTransform(Arc<TransformFn>);
+
+type TransformFn = dyn Fn(&ChangeKind, &str) -> String + Send + Sync;
+
+impl EntryTransform {
+    pub fn new(transform: impl Fn(&ChangeKind, &str) -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(transform))
+    }
+
+    pub(crate) fn apply(&self, kind: &ChangeKind, text: &str) -> String {
+        (self.0)(kind, text)
+    }
+}
+
+impl Debug for EntryTransform {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("EntryTransform(..)")
+    }
+}
+
+/// Transforms are equal only if they are the same function
+impl PartialEq for EntryTransform {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for EntryTransform {}
+
+impl FormatOptions {
+    /// Whether the rendered blocks don't map onto releases one to one, so the changelog can't be
+    /// patched release by release.
+    pub(crate) fn requires_full_render(&self) -> bool {
+        self.group_by_year
+            || self.max_releases.is_some()
+            || self.inline_links
+            || self.hide_empty_unreleased
+            || self.hide_empty_sections
+            || self.toc.is_some()
+            || self.has_sidecar()
+    }
+
+    /// Whether the sidecar is written, never without the `json` feature
+    fn has_sidecar(&self) -> bool {
+        #[cfg(feature = "json")]
+        return self.sidecar;
+        #[cfg(not(feature = "json"))]
+        false
+    }
+}
@@ -0,0 +1,307 @@
+use std::fmt;
+
+use eyre::{eyre, OptionExt, Result};
+use serde_json::{json, Value};
+
+use crate::{utils::encode, Changelog, Release, ReleaseVersion};
+
+/// Default GitHub REST API URL
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+/// GitHub Release, as created from a changelog release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GithubRelease {
+    pub tag_name: String,
+    pub name: String,
+    pub body: String,
+    /// Set for pre-release versions, e.g. `1.0.0-rc.1`
+    pub prerelease: bool,
+}
+
+/// Result of publishing a release to GitHub.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GithubSync {
+    Created,
+    Updated,
+    /// The GitHub Release is already in sync with the changelog
+    Unchanged,
+}
+
+/// Client of the GitHub Releases API, see [`GithubApi`].
+///
+/// Could be implemented on top of an existing HTTP client or mocked in tests.
+pub trait GithubClient {
+    /// GitHub Release with the `tag` and its id, `None` if there is no such release
+    fn get_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+    ) -> Result<Option<(u64, GithubRelease)>>;
+
+    fn create_release(&self, owner: &str, repo: &str, release: &GithubRelease) -> Result<()>;
+
+    fn update_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        id: u64,
+        release: &GithubRelease,
+    ) -> Result<()>;
+}
+
+/// [`GithubClient`] of the GitHub REST API, authenticated with a token.
+#[derive(Clone)]
+pub struct GithubApi {
+    token: String,
+    api_url: String,
+}
+
+impl fmt::Debug for GithubApi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GithubApi")
+            .field("token", &"<redacted>")
+            .field("api_url", &self.api_url)
+            .finish()
+    }
+}
+
+impl GithubApi {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            api_url: GITHUB_API_URL.to_string(),
+        }
+    }
+
+    /// Use API URL of a GitHub Enterprise Server, e.g. `https://github.example.com/api/v3`
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        ureq::request(method, &format!("{}{path}", self.api_url))
+            .set("Accept", "application/vnd.github+json")
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .set("User-Agent", "keep-a-changelog")
+    }
+}
+
+impl GithubClient for GithubApi {
+    fn get_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+    ) -> Result<Option<(u64, GithubRelease)>> {
+        let path = format!("/repos/{owner}/{repo}/releases/tags/{}", encode(tag));
+        let value: Value = match self.request("GET", &path).call() {
+            Ok(response) => response.into_json()?,
+            Err(ureq::Error::Status(404, _)) => return Ok(None),
+            Err(e) => return Err(eyre!("Failed to get GitHub Release {tag}: {e}")),
+        };
+
+        let id = value["id"]
+            .as_u64()
+            .ok_or_eyre("Missing GitHub Release id")?;
+        let string = |key: &str| value[key].as_str().unwrap_or_default().to_string();
+        let release = GithubRelease {
+            tag_name: string("tag_name"),
+            name: string("name"),
+            body: string("body"),
+            prerelease: value["prerelease"].as_bool().unwrap_or_default(),
+        };
+        Ok(Some((id, release)))
+    }
+
+    fn create_release(&self, owner: &str, repo: &str, release: &GithubRelease) -> Result<()> {
+        self.request("POST", &format!("/repos/{owner}/{repo}/releases"))
+            .send_json(release_json(release))
+            .map_err(|e| eyre!("Failed to create GitHub Release {}: {e}", release.tag_name))?;
+        Ok(())
+    }
+
+    fn update_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        id: u64,
+        release: &GithubRelease,
+    ) -> Result<()> {
+        self.request("PATCH", &format!("/repos/{owner}/{repo}/releases/{id}"))
+            .send_json(release_json(release))
+            .map_err(|e| eyre!("Failed to update GitHub Release {}: {e}", release.tag_name))?;
+        Ok(())
+    }
+}
+
+fn release_json(release: &GithubRelease) -> Value {
+    json!({
+        "tag_name": release.tag_name,
+        "name": release.name,
+        "body": release.body,
+        "prerelease": release.prerelease,
+    })
+}
+
+impl Release {
+    /// GitHub Release of this release with the `tag`, the body is the rendered release without
+    /// the heading
+    pub fn to_github_release(&self, tag: &str) -> Result<GithubRelease> {
+        let version = self
+            .version()
+            .as_ref()
+            .ok_or_eyre("Unreleased changes can't be published")?;
+
+        Ok(GithubRelease {
+            tag_name: tag.to_string(),
+            name: version.to_string(),
+            body: self.notes(),
+            prerelease: version.semver().is_some_and(|v| !v.pre.is_empty()),
+        })
+    }
+
+    /// Create or update the GitHub Release of the `tag` with this release
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use keep_a_changelog::{Changelog, GithubApi};
+    ///
+    /// let changelog = Changelog::parse_from_file("CHANGELOG.md", None).unwrap();
+    /// let client = GithubApi::new(std::env::var("GITHUB_TOKEN").unwrap());
+    ///
+    /// let release = changelog.find_release("1.0.0".to_string()).unwrap().unwrap();
+    /// release.publish_to_github(&client, "owner", "repo", "v1.0.0").unwrap();
+    /// ```
+    pub fn publish_to_github(
+        &self,
+        client: &impl GithubClient,
+        owner: &str,
+        repo: &str,
+        tag: &str,
+    ) -> Result<GithubSync> {
+        let release = self.to_github_release(tag)?;
+
+        match client.get_release(owner, repo, tag)? {
+            Some((_, existing)) if existing == release => Ok(GithubSync::Unchanged),
+            Some((id, _)) => {
+                client.update_release(owner, repo, id, &release)?;
+                Ok(GithubSync::Updated)
+            }
+            None => {
+                client.create_release(owner, repo, &release)?;
+                Ok(GithubSync::Created)
+            }
+        }
+    }
+}
+
+impl Changelog {
+    /// Create or update GitHub Releases of all released versions
+    ///
+    /// Tags are named with the tag prefix or the
+    /// [tag name resolver](Changelog::set_tag_name_resolver). Unreleased changes and drafts are
+    /// skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use keep_a_changelog::{Changelog, GithubApi};
+    ///
+    /// let changelog = Changelog::parse_from_file("CHANGELOG.md", None).unwrap();
+    /// let client = GithubApi::new(std::env::var("GITHUB_TOKEN").unwrap());
+    ///
+    /// for (version, sync) in changelog.sync_github_releases(&client, "owner", "repo").unwrap() {
+    ///     println!("{version}: {sync:?}");
+    /// }
+    /// ```
+    pub fn sync_github_releases(
+        &self,
+        client: &impl GithubClient,
+        owner: &str,
+        repo: &str,
+    ) -> Result<Vec<(ReleaseVersion, GithubSync)>> {
+        self.releases()
+            .iter()
+            .filter(|release| release.date().is_some() && !release.draft())
+            .filter_map(|release| Some((release, release.version().as_ref()?)))
+            .map(|(release, version)| {
+                let tag = self.tag_name(version);
+                let sync = release.publish_to_github(client, owner, repo, &tag)?;
+                Ok((version.clone(), sync))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use super::*;
+    use crate::ChangelogParseOptions;
+
+    #[derive(Default)]
+    struct MockClient {
+        releases: RefCell<HashMap<String, (u64, GithubRelease)>>,
+    }
+
+    impl GithubClient for MockClient {
+        fn get_release(&self, _: &str, _: &str, tag: &str) -> Result<Option<(u64, GithubRelease)>> {
+            Ok(self.releases.borrow().get(tag).cloned())
+        }
+
+        fn create_release(&self, _: &str, _: &str, release: &GithubRelease) -> Result<()> {
+            let mut releases = self.releases.borrow_mut();
+            let id = releases.len() as u64;
+            releases.insert(release.tag_name.clone(), (id, release.clone()));
+            Ok(())
+        }
+
+        fn update_release(&self, _: &str, _: &str, id: u64, release: &GithubRelease) -> Result<()> {
+            let mut releases = self.releases.borrow_mut();
+            releases.insert(release.tag_name.clone(), (id, release.clone()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sync_github_releases() -> Result<()> {
+        let mut changelog = Changelog::parse_from_file(
+            "tests/data/early_changelog_multiple_sections.md",
+            Some(ChangelogParseOptions {
+                tag_prefix: Some("v".to_string()),
+                ..Default::default()
+            }),
+        )?;
+        let client = MockClient::default();
+
+        let synced = changelog.sync_github_releases(&client, "owner", "repo")?;
+        assert_eq!(synced.len(), 3);
+        assert!(synced.iter().all(|(_, sync)| *sync == GithubSync::Created));
+
+        let (_, release) = client.releases.borrow()["v0.1.2"].clone();
+        assert_eq!(release.name, "0.1.2");
+        assert!(release.body.starts_with("### "));
+
+        changelog.releases_mut()[1].fixed("Another bug fix".to_string());
+        let synced = changelog.sync_github_releases(&client, "owner", "repo")?;
+        assert_eq!(
+            synced.iter().map(|(_, sync)| *sync).collect::<Vec<_>>(),
+            [
+                GithubSync::Updated,
+                GithubSync::Unchanged,
+                GithubSync::Unchanged
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_redacts_token() {
+        let api = GithubApi::new("ghp_secret");
+        assert!(!format!("{api:?}").contains("ghp_secret"));
+    }
+}
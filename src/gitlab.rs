@@ -0,0 +1,346 @@
+use std::fmt;
+
+use eyre::{eyre, OptionExt, Result};
+use serde_json::{json, Value};
+
+use crate::{patch, utils::encode, CalendarDate, Changelog, Date, Release, ReleaseVersion};
+
+/// Default GitLab REST API URL
+const GITLAB_API_URL: &str = "https://gitlab.com/api/v4";
+
+/// GitLab Release, as created from a changelog release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitlabRelease {
+    pub tag_name: String,
+    pub name: String,
+    pub description: String,
+    pub released_at: Option<Date>,
+}
+
+impl GitlabRelease {
+    /// Parse GitLab Release from the Releases API JSON
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let tag_name = value["tag_name"]
+            .as_str()
+            .ok_or_eyre("Missing GitLab Release tag name")?;
+        let string = |key: &str| value[key].as_str().unwrap_or_default().to_string();
+        let released_at = value["released_at"]
+            .as_str()
+            .and_then(|date| date.get(..10))
+            .map(|date| Date::parse_ymd(date).ok_or_eyre(format!("Invalid release date: {date}")))
+            .transpose()?;
+
+        Ok(Self {
+            tag_name: tag_name.to_string(),
+            name: string("name"),
+            description: string("description"),
+            released_at,
+        })
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "tag_name": self.tag_name,
+            "name": self.name,
+            "description": self.description,
+            "released_at": self.released_at.map(|date| format!("{date}T00:00:00Z")),
+        })
+    }
+}
+
+/// Result of publishing a release to GitLab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitlabSync {
+    Created,
+    Updated,
+    /// The GitLab Release is already in sync with the changelog
+    Unchanged,
+}
+
+/// Client of the GitLab Releases API, see [`GitlabApi`].
+///
+/// Projects are referred by the path, e.g. `group/project`, or the numeric id.
+pub trait GitlabClient {
+    /// GitLab Release with the `tag`, `None` if there is no such release
+    fn get_release(&self, project: &str, tag: &str) -> Result<Option<GitlabRelease>>;
+
+    fn create_release(&self, project: &str, release: &GitlabRelease) -> Result<()>;
+
+    fn update_release(&self, project: &str, release: &GitlabRelease) -> Result<()>;
+}
+
+/// [`GitlabClient`] of the GitLab REST API, authenticated with a personal, project or group
+/// access token.
+#[derive(Clone)]
+pub struct GitlabApi {
+    token: String,
+    api_url: String,
+}
+
+impl fmt::Debug for GitlabApi {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GitlabApi")
+            .field("token", &"<redacted>")
+            .field("api_url", &self.api_url)
+            .finish()
+    }
+}
+
+impl GitlabApi {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self {
+            token: token.into(),
+            api_url: GITLAB_API_URL.to_string(),
+        }
+    }
+
+    /// Use API URL of a self-managed GitLab instance, e.g. `https://gitlab.example.com/api/v4`
+    pub fn with_api_url(mut self, api_url: impl Into<String>) -> Self {
+        self.api_url = api_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    fn request(&self, method: &str, project: &str, path: &str) -> ureq::Request {
+        let project = encode(project);
+        ureq::request(
+            method,
+            &format!("{}/projects/{project}/releases{path}", self.api_url),
+        )
+        .set("PRIVATE-TOKEN", &self.token)
+        .set("User-Agent", "keep-a-changelog")
+    }
+}
+
+impl GitlabClient for GitlabApi {
+    fn get_release(&self, project: &str, tag: &str) -> Result<Option<GitlabRelease>> {
+        let path = format!("/{}", encode(tag));
+        let value: Value = match self.request("GET", project, &path).call() {
+            Ok(response) => response.into_json()?,
+            Err(ureq::Error::Status(404, _)) => return Ok(None),
+            Err(e) => return Err(eyre!("Failed to get GitLab Release {tag}: {e}")),
+        };
+
+        GitlabRelease::from_json(&value).map(Some)
+    }
+
+    fn create_release(&self, project: &str, release: &GitlabRelease) -> Result<()> {
+        self.request("POST", project, "")
+            .send_json(release.to_json())
+            .map_err(|e| eyre!("Failed to create GitLab Release {}: {e}", release.tag_name))?;
+        Ok(())
+    }
+
+    fn update_release(&self, project: &str, release: &GitlabRelease) -> Result<()> {
+        let path = format!("/{}", encode(&release.tag_name));
+        self.request("PUT", project, &path)
+            .send_json(release.to_json())
+            .map_err(|e| eyre!("Failed to update GitLab Release {}: {e}", release.tag_name))?;
+        Ok(())
+    }
+}
+
+impl Release {
+    /// GitLab Release of this release with the `tag`, the description is the rendered release
+    /// without the heading
+    pub fn to_gitlab_release(&self, tag: &str) -> Result<GitlabRelease> {
+        let version = self
+            .version()
+            .as_ref()
+            .ok_or_eyre("Unreleased changes can't be published")?;
+
+        Ok(GitlabRelease {
+            tag_name: tag.to_string(),
+            name: version.to_string(),
+            description: self.notes(),
+            released_at: *self.date(),
+        })
+    }
+
+    /// Create or update the GitLab Release of the `tag` with this release
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use keep_a_changelog::{Changelog, GitlabApi};
+    ///
+    /// let changelog = Changelog::parse_from_file("CHANGELOG.md", None).unwrap();
+    /// let client = GitlabApi::new(std::env::var("GITLAB_TOKEN").unwrap());
+    ///
+    /// let release = changelog.find_release("1.0.0".to_string()).unwrap().unwrap();
+    /// release.publish_to_gitlab(&client, "group/project", "v1.0.0").unwrap();
+    /// ```
+    pub fn publish_to_gitlab(
+        &self,
+        client: &impl GitlabClient,
+        project: &str,
+        tag: &str,
+    ) -> Result<GitlabSync> {
+        let release = self.to_gitlab_release(tag)?;
+
+        match client.get_release(project, tag)? {
+            Some(existing) if existing == release => Ok(GitlabSync::Unchanged),
+            Some(_) => {
+                client.update_release(project, &release)?;
+                Ok(GitlabSync::Updated)
+            }
+            None => {
+                client.create_release(project, &release)?;
+                Ok(GitlabSync::Created)
+            }
+        }
+    }
+}
+
+impl Changelog {
+    /// Create or update GitLab Releases of all released versions
+    ///
+    /// Tags are named with the tag prefix or the
+    /// [tag name resolver](Changelog::set_tag_name_resolver). Unreleased changes and drafts are
+    /// skipped.
+    pub fn sync_gitlab_releases(
+        &self,
+        client: &impl GitlabClient,
+        project: &str,
+    ) -> Result<Vec<(ReleaseVersion, GitlabSync)>> {
+        self.releases()
+            .iter()
+            .filter(|release| release.date().is_some() && !release.draft())
+            .filter_map(|release| Some((release, release.version().as_ref()?)))
+            .map(|(release, version)| {
+                let tag = self.tag_name(version);
+                let sync = release.publish_to_gitlab(client, project, &tag)?;
+                Ok((version.clone(), sync))
+            })
+            .collect()
+    }
+
+    /// Parse releases from the GitLab Releases API JSON, e.g. the response of
+    /// `GET /projects/:id/releases`
+    ///
+    /// Versions are the tag names without the tag prefix, release descriptions are parsed as
+    /// changelog sections.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, ChangelogParseOptions};
+    ///
+    /// let json = r####"[{
+    ///     "tag_name": "v1.0.0",
+    ///     "name": "1.0.0",
+    ///     "description": "### Added\n\n- Initial release",
+    ///     "released_at": "2024-04-28T10:00:00.000Z"
+    /// }]"####;
+    /// let opts = ChangelogParseOptions {
+    ///     tag_prefix: Some("v".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let changelog = Changelog::parse("# Changelog\n".to_string(), Some(opts)).unwrap();
+    ///
+    /// let releases = changelog.releases_from_gitlab(json).unwrap();
+    /// assert_eq!(releases[0].version().as_ref().unwrap().to_string(), "1.0.0");
+    /// assert_eq!(releases[0].changes().iter().count(), 1);
+    /// ```
+    pub fn releases_from_gitlab(&self, json: &str) -> Result<Vec<Release>> {
+        let value: Value = serde_json::from_str(json)?;
+        let opts = patch::parse_options(self);
+
+        value
+            .as_array()
+            .ok_or_eyre("Expected an array of GitLab Releases")?
+            .iter()
+            .map(|value| {
+                let release = GitlabRelease::from_json(value)?;
+                let tag = release.tag_name.as_str();
+                let version = self
+                    .tag_prefix()
+                    .as_deref()
+                    .and_then(|prefix| tag.strip_prefix(prefix))
+                    .unwrap_or(tag);
+                let date = release
+                    .released_at
+                    .ok_or_else(|| eyre!("Missing release date of GitLab Release {tag}"))?;
+
+                let block = format!("## [{version}] - {date}\n\n{}\n", release.description);
+                patch::parse_block(&block, &opts)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::HashMap};
+
+    use super::*;
+    use crate::ChangelogParseOptions;
+
+    #[derive(Default)]
+    struct MockClient {
+        releases: RefCell<HashMap<String, GitlabRelease>>,
+    }
+
+    impl GitlabClient for MockClient {
+        fn get_release(&self, _: &str, tag: &str) -> Result<Option<GitlabRelease>> {
+            Ok(self.releases.borrow().get(tag).cloned())
+        }
+
+        fn create_release(&self, _: &str, release: &GitlabRelease) -> Result<()> {
+            self.update_release("", release)
+        }
+
+        fn update_release(&self, _: &str, release: &GitlabRelease) -> Result<()> {
+            let mut releases = self.releases.borrow_mut();
+            releases.insert(release.tag_name.clone(), release.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_gitlab_round_trip() -> Result<()> {
+        let changelog = Changelog::parse_from_file(
+            "tests/data/early_changelog_multiple_sections.md",
+            Some(ChangelogParseOptions {
+                url: Some("https://gitlab.com/group/project".to_string()),
+                tag_prefix: Some("v".to_string()),
+                ..Default::default()
+            }),
+        )?;
+        let client = MockClient::default();
+
+        let synced = changelog.sync_gitlab_releases(&client, "group/project")?;
+        assert_eq!(synced.len(), 3);
+        assert!(synced.iter().all(|(_, sync)| *sync == GitlabSync::Created));
+
+        let json = client
+            .releases
+            .borrow()
+            .values()
+            .map(GitlabRelease::to_json)
+            .collect::<Vec<_>>();
+        let mut releases = changelog.releases_from_gitlab(&Value::Array(json).to_string())?;
+        releases.sort_by(|a, b| b.cmp(a));
+        assert_eq!(releases, changelog.releases()[1..]);
+
+        let link = changelog.releases()[1].compare_link(&changelog)?.unwrap();
+        assert_eq!(
+            link.url(),
+            "https://gitlab.com/group/project/-/compare/v0.1.1...v0.1.2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(
+            encode("group/sub group/project"),
+            "group%2Fsub%20group%2Fproject"
+        );
+    }
+
+    #[test]
+    fn test_debug_redacts_token() {
+        let api = GitlabApi::new("glpat-secret");
+        assert!(!format!("{api:?}").contains("glpat-secret"));
+    }
+}
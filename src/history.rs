@@ -0,0 +1,174 @@
+use eyre::{OptionExt, Result};
+
+use crate::{ChangeKind, Changelog, Date, Release, ReleaseVersion};
+
+/// Reversible operation on a changelog, see [`Changelog::apply`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    AddRelease(Box<Release>),
+    /// Add an entry to the release with `version`, Unreleased if `None`
+    AddEntry {
+        version: Option<ReleaseVersion>,
+        kind: ChangeKind,
+        text: String,
+    },
+    /// Promote the Unreleased release, see [`Changelog::promote_unreleased`]
+    Promote {
+        version: ReleaseVersion,
+        date: Date,
+    },
+    Yank(ReleaseVersion),
+}
+
+/// Applied operations with the releases before them.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct History {
+    undo: Vec<(Operation, Vec<Release>)>,
+    redo: Vec<Operation>,
+}
+
+impl Changelog {
+    /// Apply the operation and record it for [`Changelog::undo`], the redo history is cleared
+    ///
+    /// Nothing is recorded if the operation fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{history::Operation, ChangeKind, Changelog};
+    ///
+    /// let mut changelog = Changelog::default_with_unreleased();
+    /// changelog
+    ///     .apply(Operation::AddEntry {
+    ///         version: None,
+    ///         kind: ChangeKind::Added,
+    ///         text: "New feature".to_string(),
+    ///     })
+    ///     .unwrap();
+    /// assert!(!changelog.get_unreleased().unwrap().changes().is_empty());
+    ///
+    /// assert!(changelog.undo());
+    /// assert!(changelog.get_unreleased().unwrap().changes().is_empty());
+    ///
+    /// assert!(changelog.redo().unwrap());
+    /// assert!(!changelog.get_unreleased().unwrap().changes().is_empty());
+    /// ```
+    pub fn apply(&mut self, operation: Operation) -> Result<&mut Self> {
+        self.apply_recorded(operation)?;
+        self.history.redo.clear();
+        Ok(self)
+    }
+
+    /// Revert the last applied operation, returns `false` if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some((operation, releases)) = self.history.undo.pop() else {
+            return false;
+        };
+
+        *self.releases_mut() = releases;
+        self.history.redo.push(operation);
+        true
+    }
+
+    /// Apply the last undone operation again, returns `false` if there is nothing to redo
+    pub fn redo(&mut self) -> Result<bool> {
+        let Some(operation) = self.history.redo.pop() else {
+            return Ok(false);
+        };
+
+        self.apply_recorded(operation)?;
+        Ok(true)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.history.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.history.redo.is_empty()
+    }
+
+    fn apply_recorded(&mut self, operation: Operation) -> Result<()> {
+        let releases = self.releases().clone();
+
+        let result = self.run(&operation);
+        if result.is_err() {
+            *self.releases_mut() = releases;
+            return result;
+        }
+
+        self.history.undo.push((operation, releases));
+        Ok(())
+    }
+
+    fn run(&mut self, operation: &Operation) -> Result<()> {
+        match operation.clone() {
+            Operation::AddRelease(release) => {
+                self.add_release(*release);
+            }
+            Operation::AddEntry {
+                version,
+                kind,
+                text,
+            } => {
+                self.add_entry(version, kind, text)?;
+            }
+            Operation::Promote { version, date } => {
+                self.promote_unreleased(version, date)?;
+            }
+            Operation::Yank(version) => {
+                let release = self
+                    .find_release_mut(version.to_string())?
+                    .ok_or_eyre(format!("Release {version} not found"))?;
+                *release = release.clone().set_yanked(true);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CalendarDate;
+
+    #[test]
+    fn test_undo_redo() -> Result<()> {
+        let mut changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+        let original = changelog.releases().clone();
+        let version = ReleaseVersion::parse("0.2.0")?;
+
+        changelog
+            .apply(Operation::Promote {
+                version: version.clone(),
+                date: Date::new(2024, 6, 1).unwrap(),
+            })?
+            .apply(Operation::Yank(version.clone()))?;
+        assert!(changelog
+            .apply(Operation::Yank(ReleaseVersion::parse("9.9.9")?))
+            .is_err());
+        let yanked = changelog.releases().clone();
+        assert!(yanked[1].yanked());
+
+        assert!(changelog.undo());
+        assert!(changelog.undo());
+        assert!(!changelog.undo());
+        assert_eq!(changelog.releases(), &original);
+
+        assert!(changelog.redo()?);
+        assert!(changelog.redo()?);
+        assert!(!changelog.redo()?);
+        assert_eq!(changelog.releases(), &yanked);
+
+        changelog.undo();
+        changelog.apply(Operation::AddEntry {
+            version: Some(version),
+            kind: ChangeKind::Fixed,
+            text: "Late fix".to_string(),
+        })?;
+        assert!(!changelog.can_redo());
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+//! Entry points for release automation hooks, e.g. cargo-release
+//! [`pre-release-hook`](https://github.com/crate-ci/cargo-release/blob/master/docs/reference.md)
+//! or release-plz custom steps.
+//!
+//! Every hook reads the CHANGELOG.md file, updates it and saves it atomically, so a failing
+//! release never leaves a half-written changelog behind.
+//!
+//! ```no_run
+//! use keep_a_changelog::{hooks, CalendarDate, Date};
+//!
+//! // cargo-release passes the new version to the hook in the `NEW_VERSION` variable
+//! let version = std::env::var("NEW_VERSION").unwrap();
+//! let today = Date::today();
+//! hooks::prepare_release("CHANGELOG.md", &version, today).unwrap();
+//! ```
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use eyre::{bail, Context, OptionExt, Result};
+
+use crate::{Changelog, Date, ReleaseVersion};
+
+/// Promote the Unreleased changes of the changelog at `changelog_path` to `version`
+///
+/// Placeholders are expanded (see [`Changelog::expand_placeholders`]), release links are
+/// regenerated from the repository URL on save and a new empty Unreleased release is added.
+/// Fails if the release already exists.
+pub fn prepare_release<P: AsRef<Path>>(
+    changelog_path: P,
+    version: &str,
+    date: Date,
+) -> Result<Changelog> {
+    let path = changelog_path.as_ref();
+    let mut changelog = parse(path)?;
+
+    changelog.expand_placeholders(ReleaseVersion::parse(version)?, date)?;
+
+    save(&changelog, path)?;
+    Ok(changelog)
+}
+
+/// Finalize the `version` release of the changelog at `changelog_path` once it's published
+///
+/// The draft marker is removed and the `metadata`, e.g. commit SHA or tag name, is recorded in
+/// the release metadata. Fails if the release doesn't exist or has no date.
+pub fn finalize_release<P: AsRef<Path>>(
+    changelog_path: P,
+    version: &str,
+    metadata: BTreeMap<String, String>,
+) -> Result<Changelog> {
+    let path = changelog_path.as_ref();
+    let mut changelog = parse(path)?;
+
+    let release = changelog
+        .find_release_mut(version.to_string())?
+        .ok_or_eyre(format!("Release {version} not found"))?;
+    if release.date().is_none() {
+        bail!("Release {version} has no date, prepare it first");
+    }
+
+    release.set_draft(false);
+    release.metadata_mut().extend(metadata);
+
+    save(&changelog, path)?;
+    Ok(changelog)
+}
+
+fn parse(path: &Path) -> Result<Changelog> {
+    let path = path
+        .to_str()
+        .ok_or_eyre(format!("Invalid changelog path: {}", path.display()))?;
+    Changelog::parse_from_file(path, None)
+}
+
+/// Write the changelog to a temporary file next to `path` and move it over the original
+fn save(changelog: &Changelog, path: &Path) -> Result<()> {
+    let mut tmp = PathBuf::from(path.as_os_str());
+    tmp.as_mut_os_string().push(".tmp");
+
+    fs::write(&tmp, changelog.file_contents())
+        .wrap_err_with(|| format!("Failed to write {}", tmp.display()))?;
+    fs::rename(&tmp, path).wrap_err_with(|| format!("Failed to replace {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::CalendarDate;
+
+    #[test]
+    fn test_release_hooks() -> Result<()> {
+        let dir = format!("tests/tmp/hooks_{}", Uuid::new_v4());
+        fs::create_dir_all(&dir)?;
+        let path = format!("{dir}/CHANGELOG.md");
+        fs::copy("tests/data/cargo_release_changelog.md", &path)?;
+
+        let date = Date::new(2024, 6, 1).unwrap();
+        prepare_release(&path, "0.2.0", date)?;
+        assert!(prepare_release(&path, "0.2.0", date).is_err());
+        assert!(finalize_release(&path, "0.3.0", BTreeMap::new()).is_err());
+
+        let metadata = BTreeMap::from([("sha".to_string(), "abc123".to_string())]);
+        finalize_release(&path, "0.2.0", metadata)?;
+
+        let contents = fs::read_to_string(&path)?;
+        assert!(contents.contains(
+            "## [0.2.0] - 2024-06-01\n<!-- kac: sha=abc123 -->\n\n### Added\n\n- Feature available since 0.2.0\n"
+        ));
+        assert!(contents.contains(
+            "[0.2.0]: https://github.com/napalmpapalam/keep-a-changelog-rs/compare/0.1.0...0.2.0\n"
+        ));
+        assert_eq!(fs::read_dir(&dir)?.count(), 1);
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,259 @@
+use std::{
+    fmt::{self, Display},
+    fs,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use eyre::{bail, Context, Result};
+use regex::Regex;
+
+use crate::{parser::Parser, Changelog, ChangelogParseOptions, Release, ReleaseVersion};
+
+/// Maximum depth of nested includes, guards against include cycles.
+const MAX_INCLUDE_DEPTH: usize = 8;
+
+/// `<!-- include: path -->` directive, see [`ChangelogParseOptions::includes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Include {
+    /// Path as written in the directive, relative to the including file
+    pub path: PathBuf,
+    /// Versions of the releases read from the included file, these aren't rendered as they're
+    /// kept in that file. Empty unless the directive was expanded.
+    pub versions: Vec<ReleaseVersion>,
+}
+
+impl Display for Include {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "<!-- include: {} -->", self.path.display())
+    }
+}
+
+impl Changelog {
+    /// Concatenate several changelogs into one
+    ///
+    /// The first part provides the title, description, options and footer, releases and links
+    /// of all parts are combined. Unreleased changes are merged, duplicate versions are an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let current = "# Changelog\n## [2.0.0] - 2024-01-10\n### Changed\n- Major release\n";
+    /// let old = "# Changelog 2023\n## [1.0.0] - 2023-01-10\n### Added\n- Initial release\n";
+    ///
+    /// let changelog = Changelog::concat(vec![
+    ///     Changelog::parse(current.to_string(), None).unwrap(),
+    ///     Changelog::parse(old.to_string(), None).unwrap(),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(changelog.releases().len(), 2);
+    /// assert_eq!(changelog.title().as_deref(), Some("Changelog"));
+    /// ```
+    pub fn concat(parts: Vec<Changelog>) -> Result<Changelog> {
+        let mut parts = parts.into_iter();
+        let Some(mut changelog) = parts.next() else {
+            bail!("No changelogs to concatenate");
+        };
+
+        for part in parts {
+            for release in part.releases() {
+                match release.version() {
+                    None => match changelog.get_unreleased_mut() {
+                        Some(unreleased) => {
+                            unreleased.merge(release.clone());
+                        }
+                        None => {
+                            changelog.add_release(release.clone());
+                        }
+                    },
+                    Some(version) => {
+                        if changelog.find_release(version.to_string())?.is_some() {
+                            bail!("Duplicate release {version} in concatenated changelogs");
+                        }
+                        changelog.add_release(release.clone());
+                    }
+                }
+            }
+
+            for link in part.links() {
+                if !changelog
+                    .links()
+                    .iter()
+                    .any(|l| l.anchor() == link.anchor())
+                {
+                    changelog.add_link(link.anchor().clone(), link.url().clone());
+                }
+            }
+        }
+
+        Ok(changelog)
+    }
+
+    /// Whether the release was read from an included file.
+    pub(crate) fn is_included(&self, release: &Release) -> bool {
+        release.version().as_ref().is_some_and(|version| {
+            self.includes
+                .iter()
+                .any(|include| include.versions.contains(version))
+        })
+    }
+}
+
+/// Parse changelog keeping `<!-- include: path -->` directives, with `base_dir` the referenced
+/// files are read relative to it and concatenated with the changelog.
+pub(crate) fn parse(
+    markdown: String,
+    opts: Option<ChangelogParseOptions>,
+    base_dir: Option<&Path>,
+) -> Result<Changelog> {
+    parse_nested(markdown, opts, base_dir, 0)
+}
+
+fn parse_nested(
+    markdown: String,
+    opts: Option<ChangelogParseOptions>,
+    base_dir: Option<&Path>,
+    depth: usize,
+) -> Result<Changelog> {
+    let (markdown, paths) = extract_includes(&markdown);
+    let mut changelog = Parser::parse(markdown, opts.clone())?;
+
+    let Some(base_dir) = base_dir.filter(|_| !paths.is_empty()) else {
+        changelog.includes = paths
+            .into_iter()
+            .map(|path| Include {
+                path,
+                versions: vec![],
+            })
+            .collect();
+        return Ok(changelog);
+    };
+
+    if depth >= MAX_INCLUDE_DEPTH {
+        bail!("Too many nested includes, check for include cycles");
+    }
+
+    let mut includes = vec![];
+    let mut parts = vec![];
+    for include in paths {
+        let path = base_dir.join(&include);
+        let markdown = fs::read_to_string(&path)
+            .wrap_err_with(|| format!("Failed to read included file: {}", path.display()))?;
+        let base_dir = path.parent().unwrap_or(base_dir);
+
+        let part = parse_nested(markdown, opts.clone(), Some(base_dir), depth + 1)
+            .wrap_err_with(|| format!("Failed to parse included file: {}", path.display()))?;
+        includes.push(Include {
+            path: include,
+            versions: part
+                .releases()
+                .iter()
+                .filter_map(|release| release.version().clone())
+                .collect(),
+        });
+        parts.push(part);
+    }
+
+    changelog.includes = includes;
+    parts.insert(0, changelog);
+    Changelog::concat(parts)
+}
+
+/// Remove include directives from the Markdown, returning the included paths.
+fn extract_includes(markdown: &str) -> (String, Vec<PathBuf>) {
+    static INCLUDE: OnceLock<Regex> = OnceLock::new();
+    let include = INCLUDE.get_or_init(|| Regex::new(r"^<!--\s*include:\s*(.+?)\s*-->$").unwrap());
+
+    let mut includes = vec![];
+    let mut lines = vec![];
+
+    for line in markdown.lines() {
+        match include.captures(line.trim()) {
+            Some(captures) => includes.push(PathBuf::from(&captures[1])),
+            None => lines.push(line),
+        }
+    }
+
+    if includes.is_empty() {
+        return (markdown.to_string(), includes);
+    }
+
+    (lines.join("\n") + "\n", includes)
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_include() -> Result<()> {
+        let dir = Path::new("tests/tmp").join(format!("include_{}", Uuid::new_v4()));
+        fs::create_dir_all(dir.join("old"))?;
+        fs::write(
+            dir.join("old/CHANGELOG-2023.md"),
+            "# Changelog\n## [0.1.0] - 2023-04-28\n### Added\n- Initial release\n",
+        )?;
+        fs::write(
+            dir.join("CHANGELOG.md"),
+            "# Changelog\n## [Unreleased]\n### Added\n- Feature\n\n## [0.2.0] - 2024-04-28\n### Fixed\n- Bug fix\n\n<!-- include: old/CHANGELOG-2023.md -->\n",
+        )?;
+
+        let path = dir.join("CHANGELOG.md");
+        let opts = ChangelogParseOptions {
+            includes: true,
+            ..Default::default()
+        };
+
+        let changelog = Changelog::parse_from_file(path.to_str().unwrap(), Some(opts.clone()))?;
+        let versions = changelog
+            .releases()
+            .iter()
+            .map(|r| r.version().as_ref().map(|v| v.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            vec![None, Some("0.2.0".to_string()), Some("0.1.0".to_string())]
+        );
+
+        let output = changelog.file_contents();
+        assert!(output.contains("<!-- include: old/CHANGELOG-2023.md -->\n"));
+        assert!(!output.contains("## [0.1.0]"));
+
+        let changelog = Changelog::parse_from_file(path.to_str().unwrap(), None)?;
+        assert_eq!(changelog.releases().len(), 2);
+        assert_eq!(
+            changelog.includes(),
+            &vec![Include {
+                path: PathBuf::from("old/CHANGELOG-2023.md"),
+                versions: vec![],
+            }]
+        );
+        assert!(changelog
+            .file_contents()
+            .contains("<!-- include: old/CHANGELOG-2023.md -->\n"));
+
+        fs::write(
+            dir.join("CHANGELOG.md"),
+            "# Changelog\n## [0.2.0] - 2024-04-28\n### Fixed\n- Bug fix\n<!-- include: CHANGELOG.md -->\n",
+        )?;
+        assert!(Changelog::parse_from_file(path.to_str().unwrap(), Some(opts)).is_err());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_concat_duplicate_version() -> Result<()> {
+        let markdown = "# Changelog\n## [0.1.0] - 2023-04-28\n### Added\n- Initial release\n";
+        let part = Changelog::parse(markdown.to_string(), None)?;
+
+        assert!(Changelog::concat(vec![part.clone(), part]).is_err());
+        assert!(Changelog::concat(vec![]).is_err());
+        Ok(())
+    }
+}
@@ -1,14 +1,101 @@
-pub use changelog::{Changelog, ChangelogParseOptions};
-pub use changes::{ChangeKind, Changes};
+pub use audience::AudienceFilter;
+pub use borrowed::{ChangelogRef, LinkRef, ReleaseRef};
+pub use changelog::{Changelog, ChangelogParseOptions, InitOptions, NormalizedDate};
+pub use changes::{ChangeEntry, ChangeKind, Changes, ChangesBuilder, Visibility};
+#[cfg(feature = "chrono")]
 pub use chrono::NaiveDate;
+#[cfg(feature = "tz")]
+pub use chrono_tz::Tz;
+pub use conflict::{ConflictSide, MergeConflict};
+pub use date::{CalendarDate, Date};
+pub use entry::Entry;
+pub use entry_template::TemplateRegistry;
+pub use error::{BuildError, BuildProblem, ParseError};
+pub use export::StoreNotesStrategy;
+#[cfg(feature = "fancy-errors")]
+pub use fancy::SourceError;
+pub use format::{EntryTransform, FormatOptions, HeadingStyle};
+#[cfg(feature = "github")]
+pub use github::{GithubApi, GithubClient, GithubRelease, GithubSync};
+#[cfg(feature = "gitlab")]
+pub use gitlab::{GitlabApi, GitlabClient, GitlabRelease, GitlabSync};
+pub use include::Include;
 pub use link::Link;
+pub use placeholder::{Marker, MarkerAnchor, UnreleasedTemplate};
 pub use release::{Release, ReleaseBuilder};
-pub use semver::Version;
+pub use release_date::ReleaseDate;
+pub use search::EntryRef;
+pub use semver::{Version, VersionReq};
+pub use span::Span;
+pub use validation::{ValidationOptions, Violation};
+pub use version::{CalVer, CalVerScheme, ReleaseVersion};
+#[cfg(feature = "watch")]
+pub use watch::ChangelogWatcher;
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod audience;
+pub mod borrowed;
+#[cfg(feature = "cargo")]
+mod cargo;
 pub mod changelog;
 pub mod changes;
+mod changeset;
+pub mod check;
+pub mod conflict;
 mod consts;
+pub mod convert;
+pub mod date;
+pub mod diagnostics;
+#[cfg(feature = "dry-run")]
+mod diff;
+pub mod entry;
+pub mod entry_template;
+pub mod error;
+pub mod event;
+pub mod excerpt;
+mod export;
+#[cfg(feature = "fancy-errors")]
+mod fancy;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod fingerprint;
+pub mod format;
+#[cfg(feature = "git")]
+mod git;
+#[cfg(feature = "github")]
+mod github;
+#[cfg(feature = "gitlab")]
+mod gitlab;
+#[cfg(feature = "history")]
+pub mod history;
+pub mod hooks;
+mod include;
 pub mod link;
+pub mod model;
 mod parser;
+mod patch;
+pub mod placeholder;
 pub mod release;
+pub mod release_date;
+pub mod search;
+#[cfg(feature = "json")]
+mod sidecar;
+pub mod span;
+pub mod style;
+pub mod summary;
+pub mod tag;
+#[cfg(feature = "templates")]
+pub mod templates;
+mod toc;
 mod token;
+#[cfg(feature = "tz")]
+mod tz;
+mod upgrade;
 mod utils;
+pub mod validation;
+pub mod version;
+mod visibility;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "watch")]
+mod watch;
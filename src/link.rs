@@ -1,18 +1,32 @@
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display, sync::OnceLock};
 
 use derive_getters::Getters;
-use eyre::{eyre, OptionExt, Result};
+use eyre::{eyre, Result};
+use regex::Regex;
+
+use crate::{
+    span::{Span, Unspanned},
+    utils::normalize_repo_url,
+    Changelog,
+};
 
 /// Represents a link in a changelog.
 #[derive(Debug, Clone, Getters, PartialEq, Eq)]
 pub struct Link {
     pub anchor: String,
     pub url: String,
+    /// Optional link title, e.g. `Release 1.2.3` in `[1.2.3]: https://example.com "Release 1.2.3"`
+    pub title: Option<String>,
+    #[getter(skip)]
+    pub(crate) span: Unspanned<Option<Span>>,
 }
 
 impl Link {
     /// Parse a link from a string.
     ///
+    /// Accepts link reference definitions as defined by CommonMark: the URL could be enclosed
+    /// in angle brackets, followed by an optional title, and the parts could be split across
+    /// lines.
     ///
     /// # Examples
     ///
@@ -22,16 +36,20 @@ impl Link {
     /// let link = Link::parse("[anchor]: https://example.com".to_string()).unwrap();
     /// assert_eq!(link.anchor(), "anchor");
     /// assert_eq!(link.url(), "https://example.com");
+    ///
+    /// let link = Link::parse("[1.2.3]: <https://example.com> \"Release 1.2.3\"".to_string()).unwrap();
+    /// assert_eq!(link.url(), "https://example.com");
+    /// assert_eq!(link.title().as_deref(), Some("Release 1.2.3"));
     /// ```
     pub fn parse(line: String) -> Result<Self> {
-        let mut parts = line.splitn(2, ": ").map(|s| s.to_string());
-        let anchor = parts
-            .next()
-            .ok_or_eyre(format!("Missing anchor: {line}"))?
-            .replace(['[', ']'], "");
-        let url = parts.next().ok_or_eyre("Missing url")?;
+        let (anchor, url, title) = parse_definition(&line)?;
 
-        Ok(Self { anchor, url })
+        Ok(Self {
+            anchor: anchor.to_string(),
+            url: url.to_string(),
+            title: title.map(|title| title.into_owned()),
+            span: Unspanned::default(),
+        })
     }
 
     pub fn new<S: Into<String>>(anchor: S, url: S) -> Result<Self> {
@@ -48,16 +66,110 @@ impl Link {
             return Err(eyre!("Missing url"));
         }
 
-        Ok(Self { anchor, url })
+        if url.chars().any(char::is_whitespace) {
+            return Err(eyre!("Invalid url: {url}"));
+        }
+
+        if url.starts_with("http://") || url.starts_with("https://") {
+            normalize_repo_url(&url)?;
+        }
+
+        Ok(Self {
+            anchor,
+            url,
+            title: None,
+            span: Unspanned::default(),
+        })
+    }
+
+    /// Location of the link definition in the parsed source, `None` if the link wasn't parsed.
+    pub fn span(&self) -> Option<&Span> {
+        self.span.0.as_ref()
+    }
+
+    /// Move the span by `lines` and `bytes`, see [`Span::shifted`].
+    pub(crate) fn shift_span(&mut self, lines: isize, bytes: isize) {
+        if let Some(span) = self.span.0.as_mut() {
+            *span = span.shifted(lines, bytes);
+        }
+    }
+}
+
+impl Link {
+    /// Whether the link is a release or compare link, which are generated from the releases.
+    pub(crate) fn is_release(&self, changelog: &Changelog) -> bool {
+        static TAG: OnceLock<Regex> = OnceLock::new();
+        let tag = TAG.get_or_init(|| Regex::new(r"\d+\.\d+\.\d+((-rc|-x)\.\d+)?").unwrap());
+
+        tag.is_match(&self.anchor)
+            || self.anchor.contains("Unreleased")
+            || changelog
+                .releases()
+                .iter()
+                .any(|release| release.anchor().eq_ignore_ascii_case(&self.anchor))
+    }
+}
+
+/// Insert link, replacing the link with the same anchor, anchors are matched case-insensitively
+/// as in CommonMark.
+pub(crate) fn upsert_link(links: &mut Vec<Link>, link: Link) {
+    match links
+        .iter_mut()
+        .find(|l| l.anchor.to_lowercase() == link.anchor.to_lowercase())
+    {
+        Some(existing) => *existing = link,
+        None => links.push(link),
     }
 }
 
 impl Display for Link {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "[{}]: {}", self.anchor, self.url)
+        if self.url.is_empty() || self.url.contains(char::is_whitespace) {
+            write!(f, "[{}]: <{}>", self.anchor, self.url)?;
+        } else {
+            write!(f, "[{}]: {}", self.anchor, self.url)?;
+        }
+
+        match &self.title {
+            Some(title) => write!(f, " \"{}\"", title.replace('"', "\\\"")),
+            None => Ok(()),
+        }
     }
 }
 
+/// Anchor, URL and title of a link reference definition, borrowed from the `line` unless the
+/// title has escapes.
+pub(crate) fn parse_definition(line: &str) -> Result<(&str, &str, Option<Cow<'_, str>>)> {
+    static DEFINITION: OnceLock<Regex> = OnceLock::new();
+    let definition = DEFINITION.get_or_init(|| {
+        Regex::new(
+            r#"(?s)^\s{0,3}\[([^\]]*)\]:\s*(?:<([^<>\n]*)>|(\S+))(?:\s+(?:"((?:[^"\\]|\\.)*)"|'((?:[^'\\]|\\.)*)'|\(((?:[^()\\]|\\.)*)\)))?\s*$"#,
+        )
+        .unwrap()
+    });
+    static ESCAPE: OnceLock<Regex> = OnceLock::new();
+    let escape = ESCAPE.get_or_init(|| Regex::new(r"\\(.)").unwrap());
+
+    let captures = definition
+        .captures(line)
+        .ok_or_else(|| eyre!("Invalid link definition: {line}"))?;
+
+    let anchor = captures.get(1).map_or("", |anchor| anchor.as_str());
+    if anchor.trim().is_empty() {
+        return Err(eyre!("Missing anchor: {line}"));
+    }
+
+    let url = captures
+        .get(2)
+        .or_else(|| captures.get(3))
+        .map_or("", |url| url.as_str());
+    let title = (4..=6)
+        .find_map(|idx| captures.get(idx))
+        .map(|title| escape.replace_all(title.as_str(), "$1"));
+
+    Ok((anchor, url, title))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -74,6 +186,55 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_new_with_malformed_url() {
+        assert!(Link::new("anchor", "https://").is_err());
+        assert!(Link::new("anchor", "https://example.com/a b").is_err());
+    }
+
+    #[test]
+    fn test_parse_with_title() -> Result<()> {
+        for line in [
+            "[1.2.3]: https://example.com \"Release 1.2.3\"",
+            "[1.2.3]: <https://example.com> 'Release 1.2.3'",
+            "[1.2.3]:\n    https://example.com\n    (Release 1.2.3)",
+        ] {
+            let link = Link::parse(line.to_string())?;
+            assert_eq!(link.anchor(), "1.2.3");
+            assert_eq!(link.url(), "https://example.com");
+            assert_eq!(link.title().as_deref(), Some("Release 1.2.3"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_round_trip() -> Result<()> {
+        let line = r#"[anchor]: <https://example.com/a b> "Say \"hi\"""#;
+        let link = Link::parse(line.to_string())?;
+        assert_eq!(link.url(), "https://example.com/a b");
+        assert_eq!(link.title().as_deref(), Some("Say \"hi\""));
+        assert_eq!(link.to_string(), line);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Link::parse("[]: https://example.com".to_string()).is_err());
+        assert!(Link::parse("[anchor]: https://example.com trailing".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_upsert_link() -> Result<()> {
+        let mut links = vec![];
+        upsert_link(&mut links, Link::new("Docs", "https://example.com/v1")?);
+        upsert_link(&mut links, Link::new("home", "https://example.com")?);
+        upsert_link(&mut links, Link::new("docs", "https://example.com/v2")?);
+
+        assert_eq!(links.len(), 2);
+        assert_eq!(links[0].to_string(), "[docs]: https://example.com/v2");
+        Ok(())
+    }
+
     #[test]
     fn test_new_with_valid_anchor_and_url() {
         let result = Link::new("anchor", "https://example.com");
@@ -0,0 +1,14 @@
+//! Stable, versioned representations of the changelog model.
+//!
+//! The types exported from the crate root (e.g. [`crate::Changelog`]) evolve together with the
+//! features of this library. Tools which need to persist or exchange changelogs, or which want to
+//! upgrade this crate without sweeping breakage, should program against a versioned model instead
+//! and convert at the boundaries.
+//!
+//! Every model version is plain data with public fields and follows these guarantees:
+//!
+//! - fields are never removed, renamed or change their type within a model version;
+//! - conversions from the crate types never fail and conversions back only fail on invalid
+//!   data (e.g. a malformed version or date);
+//! - incompatible changes are introduced as a new model version next to the existing ones.
+pub mod v1;
@@ -0,0 +1,227 @@
+//! Version 1 of the changelog model.
+//!
+//! # Examples
+//!
+//! ```
+//! use keep_a_changelog::{model::v1, Changelog};
+//!
+//! let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+//! let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+//!
+//! let model = v1::Changelog::from(&changelog);
+//! assert_eq!(model.releases[0].version.as_deref(), Some("0.1.0"));
+//! assert_eq!(model.releases[0].sections[0].kind, "Added");
+//!
+//! let changelog = Changelog::try_from(model).unwrap();
+//! assert_eq!(changelog.releases().len(), 1);
+//! ```
+use std::str::FromStr;
+
+use eyre::{eyre, Context, Error, Result};
+
+use crate::{changelog::ChangelogBuilder, changes::ChangeKind, CalendarDate, Date, ReleaseVersion};
+
+/// Changelog document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Changelog {
+    /// Disabled markdownlint rules, e.g. "MD022"
+    pub lint: Vec<String>,
+    /// Comment flag rendered above the title
+    pub flag: Option<String>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub url: Option<String>,
+    pub head: String,
+    pub tag_prefix: Option<String>,
+    pub releases: Vec<Release>,
+    pub links: Vec<Link>,
+    pub footer: Option<String>,
+    pub compact: bool,
+}
+
+/// Single release, `version` and `date` are `None` for the Unreleased section.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Release {
+    /// Version string, e.g. "1.2.3"
+    pub version: Option<String>,
+    /// ISO 8601 date, e.g. "2024-04-28"
+    pub date: Option<String>,
+    pub yanked: bool,
+    pub description: Option<String>,
+    pub sections: Vec<Section>,
+}
+
+/// Section of changes of a single kind.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Section {
+    /// Section heading, e.g. "Added" or "Fixed"
+    pub kind: String,
+    pub entries: Vec<String>,
+}
+
+/// Link reference definition.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Link {
+    pub anchor: String,
+    pub url: String,
+}
+
+impl From<&crate::Changelog> for Changelog {
+    fn from(changelog: &crate::Changelog) -> Self {
+        let mut lint = changelog
+            .lint()
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        lint.sort();
+
+        Self {
+            lint,
+            flag: changelog.flag().clone(),
+            title: changelog.title().clone(),
+            description: changelog.description().clone(),
+            url: changelog.url().clone(),
+            head: changelog.head().clone(),
+            tag_prefix: changelog.tag_prefix().clone(),
+            releases: changelog.releases().iter().map(Release::from).collect(),
+            links: changelog.links().iter().map(Link::from).collect(),
+            footer: changelog.footer().clone(),
+            compact: *changelog.compact(),
+        }
+    }
+}
+
+impl TryFrom<Changelog> for crate::Changelog {
+    type Error = Error;
+
+    fn try_from(changelog: Changelog) -> Result<Self> {
+        let releases = changelog
+            .releases
+            .into_iter()
+            .map(crate::Release::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        let links = changelog
+            .links
+            .into_iter()
+            .map(|link| crate::Link::new(link.anchor, link.url))
+            .collect::<Result<Vec<_>>>()?;
+        let disabled_lints = changelog.lint;
+
+        let mut changelog = ChangelogBuilder::default()
+            .compact(changelog.compact)
+            .flag(changelog.flag)
+            .title(changelog.title)
+            .description(changelog.description)
+            .url(changelog.url)
+            .head(changelog.head)
+            .tag_prefix(changelog.tag_prefix)
+            .footer(changelog.footer)
+            .releases(releases)
+            .build()
+            .map_err(|e| eyre!("Failed to build Changelog: {e}"))?;
+
+        for link in links {
+            changelog.add_link(link.anchor, link.url);
+        }
+
+        for lint in disabled_lints {
+            changelog.disable_lint(&lint);
+        }
+
+        Ok(changelog)
+    }
+}
+
+impl From<&crate::Release> for Release {
+    fn from(release: &crate::Release) -> Self {
+        let sections = release
+            .changes()
+            .sections()
+            .map(|(kind, entries)| Section {
+                kind: kind.heading().to_string(),
+                entries: entries.to_vec(),
+            })
+            .collect();
+
+        Self {
+            version: release.version().as_ref().map(|v| v.to_string()),
+            date: release.date().map(|d| d.to_string()),
+            yanked: *release.yanked(),
+            description: release.description().clone(),
+            sections,
+        }
+    }
+}
+
+impl TryFrom<Release> for crate::Release {
+    type Error = Error;
+
+    fn try_from(release: Release) -> Result<Self> {
+        let mut builder = crate::Release::builder();
+
+        if let Some(version) = release.version {
+            let version = ReleaseVersion::parse(&version)
+                .wrap_err_with(|| format!("Failed to parse version: {version}"))?;
+            builder.version(version);
+        }
+
+        if let Some(date) = release.date {
+            let date =
+                Date::parse_ymd(&date).ok_or_else(|| eyre!("Failed to parse date: {date}"))?;
+            builder.date(date);
+        }
+
+        let mut changes = crate::Changes::builder();
+        for section in release.sections {
+            let kind = ChangeKind::from_str(&section.kind)?;
+            for entry in section.entries {
+                changes.add(kind.clone(), entry);
+            }
+        }
+
+        builder
+            .yanked(release.yanked)
+            .description(release.description)
+            .changes(changes.build()?)
+            .build()
+            .map_err(|e| eyre!("Failed to build Release: {e}"))
+    }
+}
+
+impl From<&crate::Link> for Link {
+    fn from(link: &crate::Link) -> Self {
+        Self {
+            anchor: link.anchor().clone(),
+            url: link.url().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/early_changelog_multiple_sections.md")?;
+        let changelog = crate::Changelog::parse(markdown, None)?;
+
+        let model = Changelog::from(&changelog);
+        let converted = crate::Changelog::try_from(model.clone())?;
+
+        assert_eq!(Changelog::from(&converted), model);
+        assert_eq!(converted.to_string(), changelog.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_version() {
+        let release = Release {
+            version: Some("not a version".to_string()),
+            ..Default::default()
+        };
+
+        assert!(crate::Release::try_from(release).is_err());
+    }
+}
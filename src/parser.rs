@@ -1,47 +1,308 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, str::FromStr, sync::OnceLock};
 
-use eyre::{bail, eyre, Result};
+use eyre::{eyre, Context, Result};
 use regex::Regex;
 use semver::Version;
 
 use crate::{
-    changelog::ChangelogBuilder,
+    changelog::{ChangelogBuilder, NormalizedDate},
+    conflict::find_conflicts,
+    consts::{
+        COMPARE_BASE_FLAG_PREFIX, CONTRIBUTORS_HEADING, DRAFT_FLAG, METADATA_FLAG_PREFIX,
+        TOC_END_FLAG, TOC_FLAG,
+    },
+    convert::parse_date,
+    error::ParseError,
+    format::FormatOptions,
+    link::Link,
+    placeholder::{Marker, MarkerAnchor, RELEASE_DATE_SUFFIX},
     release::{Release, ReleaseBuilder},
-    token::{tokenize, Token, TokenKind},
-    Changelog, ChangelogParseOptions,
+    release_date::ReleaseDate,
+    span::{LineIndex, Span, Unspanned},
+    token::{split_documents, tokenize, Token, TokenKind},
+    utils::parse_pairs,
+    version::ReleaseVersion,
+    CalendarDate, ChangeKind, Changelog, ChangelogParseOptions, Date,
 };
 
+/// Dates accepted with [`ChangelogParseOptions::lenient_dates`], matched against the lowercased
+/// heading, e.g. `2024/06/03`, `june 3rd, 2024` or `3 jun 2024`
+const LENIENT_DATE_REGEX: &str = r"[\d]{4}[-/.][\d]{1,2}[-/.][\d]{1,2}|[a-z]+\.?\s+\d{1,2}(?:st|nd|rd|th)?,?\s+\d{4}|\d{1,2}\s+[a-z]+\.?\s+\d{4}";
+
+/// Regexes of the release headings, shared with the borrowed parser
+pub(crate) struct HeadingRegexes {
+    /// Released version with its date, e.g. `[1.2.3] - 2024-01-01 [YANKED]`
+    pub release: Regex,
+    /// Planned releases without a date, e.g. `## [1.3.0] - TBD`
+    pub unreleased: Regex,
+    /// Year headings of year grouped changelogs, e.g. `## 2024`
+    pub year: Regex,
+    /// Release heading linking to the compare URL, e.g. `## [1.2.3](https://…) - 2024-01-01`
+    pub inline_link: Regex,
+}
+
+impl HeadingRegexes {
+    pub fn new(lenient_dates: bool) -> Result<Self> {
+        let date = match lenient_dates {
+            true => LENIENT_DATE_REGEX,
+            false => r"[\d]{4}-[\d]{1,2}-[\d]{1,2}",
+        };
+        // Month dates like `2019-02`, used by some older changelogs
+        let date = format!(r"{date}|[\d]{{4}}-[\d]{{2}}");
+
+        Ok(Self {
+            release: Regex::new(&format!(r"\[?([^\]]+)\]?\s*-\s*({date})(\s+\[yanked\])?$"))?,
+            unreleased: Regex::new(r"\[?([^\]]+)\]?\s*-\s*(?:unreleased|tbd)(\s+\[yanked\])?$")?,
+            year: Regex::new(r"^\d{4}$")?,
+            inline_link: Regex::new(r"^\[([^\]]+)\]\(([^)]*)\)")?,
+        })
+    }
+}
+
+/// Repository URL of a compare URL, e.g. `https://github.com/owner/repo` in
+/// `https://github.com/owner/repo/compare/v1.2.2...v1.2.3`
+pub(crate) fn compare_repo_url(url: &str) -> Option<&str> {
+    static COMPARE_URL: OnceLock<Regex> = OnceLock::new();
+    let regex = COMPARE_URL.get_or_init(|| Regex::new(r"^(http.*?)\/(?:-\/)?compare\/").unwrap());
+    Some(regex.captures(url)?.get(1)?.as_str())
+}
+
+/// Head reference of a compare URL, e.g. `develop` in `.../compare/v1.2.3...develop`
+pub(crate) fn compare_head(url: &str) -> Option<&str> {
+    static COMPARE_HEAD: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        COMPARE_HEAD.get_or_init(|| Regex::new(r"\/(?:-\/)?compare\/.+?\.{2,3}(.+)$").unwrap());
+    Some(regex.captures(url)?.get(1)?.as_str())
+}
+
+/// Rules disabled by the `markdownlint-disable MD022 MD032` comment
+pub(crate) fn parse_lint(comment: &str) -> Result<HashSet<String>> {
+    static LINT: OnceLock<Regex> = OnceLock::new();
+    let regex =
+        LINT.get_or_init(|| Regex::new(r"markdownlint-disable(?P<lints>( MD\d{3})+)").unwrap());
+
+    let captures = regex
+        .captures(comment)
+        .ok_or_else(|| eyre!("Failed to get lint content"))?;
+    Ok(captures["lints"]
+        .trim()
+        .split(' ')
+        .map(|s| s.to_string())
+        .collect())
+}
+
+/// Number of tokens and depth of the generated table of contents at the start of `tokens`
+pub(crate) fn parse_toc(tokens: &[Token]) -> Option<(usize, usize)> {
+    let is_flag = |token: Option<&Token>, flag: &str| {
+        token.is_some_and(|t| t.kind == TokenKind::Flag && t.content.join("\n") == flag)
+    };
+    if !is_flag(tokens.first(), TOC_FLAG) {
+        return None;
+    }
+
+    let mut idx = 1;
+    let mut depth = 1;
+    while let Some(token) = tokens.get(idx) {
+        if is_flag(Some(token), TOC_END_FLAG) {
+            idx += 1;
+            break;
+        }
+        if token.kind != TokenKind::Li {
+            break;
+        }
+        if token.content.len() > 1 {
+            depth = 2;
+        }
+        idx += 1;
+    }
+
+    Some((idx, depth))
+}
+
+/// Whether the comment following a release heading is one of its flags, e.g. `<!-- draft -->`
+pub(crate) fn is_release_flag(flag: &str) -> bool {
+    flag == DRAFT_FLAG
+        || flag.starts_with(COMPARE_BASE_FLAG_PREFIX)
+        || flag.starts_with(METADATA_FLAG_PREFIX)
+}
+
+/// Text of the paragraph and list item tokens, list items are prefixed with `- `
+pub(crate) fn text_content(tokens: &[Token]) -> String {
+    tokens
+        .iter()
+        .map(|token| match token.kind {
+            TokenKind::Li => format!("- {}", token.content.join("\n")),
+            _ => token.content.join("\n"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 #[derive(Debug)]
-pub struct Parser {
+pub struct Parser<'a> {
     builder: ChangelogBuilder,
-    tokens: Vec<Token>,
+    tokens: Vec<Token<'a>>,
     opts: ChangelogParseOptions,
+    markers: Vec<(Marker, MarkerAnchor)>,
+    #[cfg(feature = "json")]
+    sidecar: bool,
+    /// Depth of the table of contents, if the changelog has one
+    toc: Option<usize>,
+    normalized_dates: Vec<NormalizedDate>,
+    /// Problems found so far, collected instead of failing if set
+    errors: Option<Vec<ParseError>>,
+    lines: LineIndex,
     idx: usize,
 }
 
-impl Parser {
+impl<'a> Parser<'a> {
     pub fn parse(markdown: String, opts: Option<ChangelogParseOptions>) -> Result<Changelog> {
-        let (compact, tokens) = tokenize(markdown)?;
+        Self::parse_with(markdown, opts, false).0
+    }
+
+    /// Parse changelog continuing after malformed releases and sections, returns all problems
+    /// found sorted by line.
+    pub fn parse_recovering(
+        markdown: String,
+        opts: Option<ChangelogParseOptions>,
+    ) -> std::result::Result<Changelog, Vec<ParseError>> {
+        let (result, mut errors) = Self::parse_with(markdown, opts, true);
+
+        match result {
+            Ok(changelog) if errors.is_empty() => return Ok(changelog),
+            Ok(_) => {}
+            Err(e) => errors.push(
+                e.downcast::<ParseError>()
+                    .unwrap_or_else(|e| ParseError::Other(e.to_string())),
+            ),
+        }
+
+        errors.sort_by_key(|e| (e.line().is_none(), e.line()));
+        Err(errors)
+    }
+
+    fn parse_with(
+        markdown: String,
+        opts: Option<ChangelogParseOptions>,
+        recover: bool,
+    ) -> (Result<Changelog>, Vec<ParseError>) {
+        let conflicts = find_conflicts(&markdown);
+        if !conflicts.is_empty() {
+            return (Err(ParseError::MergeConflict(conflicts).into()), vec![]);
+        }
+
+        let opts = opts.unwrap_or_default();
+        #[cfg(not(feature = "json"))]
+        let markdown = markdown.as_str();
+        #[cfg(feature = "json")]
+        let (markdown, sidecar) = crate::sidecar::split(&markdown);
+        #[cfg(feature = "json")]
+        if let Some(sidecar) = sidecar {
+            if let Err(e) = crate::sidecar::verify(markdown, sidecar) {
+                log::warn!("Failed to parse changelog sidecar: {e}");
+            }
+        }
+
+        let mut documents = split_documents(markdown);
+        let markdown = match documents.len() {
+            0 | 1 => markdown.to_string(),
+            _ if opts.first_document_only => documents.swap_remove(0).1,
+            _ => {
+                let lines = documents.into_iter().map(|(line, _)| line).collect();
+                return (Err(ParseError::MultipleDocuments(lines).into()), vec![]);
+            }
+        };
+
+        let lines = LineIndex::new(&markdown);
+        let (compact, tokens) = match tokenize(&markdown) {
+            Ok(tokens) => tokens,
+            Err(e) => return (Err(e), vec![]),
+        };
         let (links, tokens): (Vec<Token>, Vec<Token>) =
             tokens.into_iter().partition(|t| t.kind == TokenKind::Link);
         let builder = ChangelogBuilder::default();
-        let opts = opts.unwrap_or_default();
 
-        let mut parse_output = Self {
+        let mut parse_output = Parser {
             builder,
             tokens,
             opts,
+            markers: vec![],
+            #[cfg(feature = "json")]
+            sidecar: sidecar.is_some(),
+            toc: None,
+            normalized_dates: vec![],
+            errors: recover.then(Vec::new),
+            lines,
             idx: 0,
         };
-        parse_output
-            .parse_opts()?
+        let result = parse_output.run(links, compact);
+        (result, parse_output.errors.unwrap_or_default())
+    }
+
+    fn run(&mut self, links: Vec<Token>, compact: bool) -> Result<Changelog> {
+        self.parse_opts()?
             .parse_meta()?
             .parse_releases()?
             .parse_links(links)?
             .parse_footer()?
             .parse_compact(compact);
-        log::trace!("Parse output: {:#?}", parse_output);
-        parse_output.build()
+
+        log::trace!("Parse output: {:#?}", self);
+        self.build()
+    }
+
+    /// Fail with the error, or collect it and continue if recovering
+    fn report(&mut self, error: ParseError) -> Result<()> {
+        match &mut self.errors {
+            Some(errors) => {
+                errors.push(error);
+                Ok(())
+            }
+            None => Err(error.into()),
+        }
+    }
+
+    /// Parse release version, only semantic versions are accepted with strict versions
+    fn parse_version(&self, version: &str) -> Result<ReleaseVersion> {
+        match self.opts.strict_versions {
+            true => Ok(Version::parse(version)?.into()),
+            false => ReleaseVersion::parse(version),
+        }
+    }
+
+    /// Parse release date matched in the `heading`, non-ISO dates accepted with lenient dates are
+    /// recorded as normalized
+    fn parse_date(
+        &mut self,
+        heading: &str,
+        matched: Option<regex::Match>,
+        line: usize,
+    ) -> Option<ReleaseDate> {
+        let matched = matched?;
+        let text = heading
+            .get(matched.range())
+            .unwrap_or(matched.as_str())
+            .trim();
+
+        if let Some(date) = Date::parse_ymd(text) {
+            if !self.opts.lenient_dates || date.to_string() == text {
+                return Some(ReleaseDate::Day(date));
+            }
+        }
+        if let Ok(date @ ReleaseDate::Month(..)) = text.parse() {
+            return Some(date);
+        }
+        if !self.opts.lenient_dates {
+            return None;
+        }
+
+        let date = parse_date(text)?;
+        self.normalized_dates.push(NormalizedDate {
+            line,
+            original: text.to_string(),
+            date,
+        });
+        Some(ReleaseDate::Day(date))
     }
 
     fn parse_opts(&mut self) -> Result<&mut Self> {
@@ -68,80 +329,272 @@ impl Parser {
             .title(title)
             .description(description);
 
+        self.parse_toc();
         Ok(self)
     }
 
+    /// Skip the generated table of contents, only its depth is kept to render it again
+    fn parse_toc(&mut self) {
+        if let Some((len, depth)) = parse_toc(&self.tokens[self.idx..]) {
+            self.idx += len;
+            self.toc = Some(depth);
+        }
+    }
+
     fn parse_releases(&mut self) -> Result<&mut Self> {
         let mut releases: Vec<Release> = vec![];
-        let unreleased_regex = Regex::new(r"\[?([^\]]+)\]?\s*-\s*unreleased(\s+\[yanked\])?$")?;
-        let release_regex =
-            Regex::new(r"\[?([^\]]+)\]?\s*-\s*([\d]{4}-[\d]{1,2}-[\d]{1,2})(\s+\[yanked\])?$")?;
+        let HeadingRegexes {
+            release: release_regex,
+            unreleased: unreleased_regex,
+            year: year_regex,
+            inline_link: inline_link_regex,
+        } = HeadingRegexes::new(self.opts.lenient_dates)?;
+        let mut group_by_year = false;
+        let mut inline_links = false;
+
+        self.parse_markers(MarkerAnchor::Header)?;
 
         while let (Some(release), token) = self.get_content(vec![TokenKind::H2])? {
+            if year_regex.is_match(release.trim()) {
+                group_by_year = true;
+                continue;
+            }
+
             let mut builder = ReleaseBuilder::default();
+            builder.brackets(release.trim_start().starts_with('['));
+
+            let mut release = release;
+            if let Some(captures) = inline_link_regex.captures(release.trim()) {
+                inline_links = true;
+
+                if self.opts.url.is_none() {
+                    if let Some(url) = compare_repo_url(&captures[2]) {
+                        self.builder.url(Some(url.to_string()));
+                    }
+                }
+                if self.opts.head.is_none() && captures[1].eq_ignore_ascii_case("unreleased") {
+                    if let Some(head) = compare_head(&captures[2]) {
+                        self.builder.head(head.to_string());
+                    }
+                }
+
+                release = inline_link_regex
+                    .replace(release.trim(), "[$1]")
+                    .to_string();
+            }
+
+            let mut valid = true;
             let release_lc = release.clone().to_lowercase();
+            let line = token.as_ref().map_or(0, |token| token.line);
+
+            let release_date = release.ends_with(RELEASE_DATE_SUFFIX);
 
             builder.yanked(release_lc.contains("[yanked]"));
 
             if let Some(captures) = release_regex.captures(&release_lc) {
-                let version = Version::parse(captures[1].trim())
-                    .map_err(|e| eyre!("Failed to parse version: {e}"))?;
-
-                let date = chrono::NaiveDate::parse_from_str(captures[2].trim(), "%Y-%m-%d")
-                    .map_err(|e| eyre!("Failed to parse date: {e}"))?;
+                match self.parse_version(captures[1].trim()) {
+                    Ok(version) => {
+                        builder.version(version);
+                    }
+                    Err(_) => {
+                        valid = false;
+                        self.report(ParseError::InvalidVersion {
+                            line,
+                            version: captures[1].trim().to_string(),
+                        })?;
+                    }
+                }
 
-                builder.version(version).date(date);
-            } else if release_lc.contains("unreleased") {
+                match self.parse_date(&release, captures.get(2), line) {
+                    Some(date) => {
+                        builder.release_date(date);
+                    }
+                    None => {
+                        valid = false;
+                        self.report(ParseError::InvalidDate {
+                            line,
+                            date: captures[2].trim().to_string(),
+                        })?;
+                    }
+                }
+            } else if release_lc.contains("unreleased") || unreleased_regex.is_match(&release_lc) {
                 if let Some(captures) = unreleased_regex.captures(&release_lc) {
-                    builder.version(Version::parse(captures[1].trim())?);
+                    match self.parse_version(captures[1].trim()) {
+                        Ok(version) => {
+                            builder.version(version);
+                        }
+                        Err(_) => {
+                            valid = false;
+                            self.report(ParseError::InvalidVersion {
+                                line,
+                                version: captures[1].trim().to_string(),
+                            })?;
+                        }
+                    }
                 }
             } else {
-                let token = token.expect("Token is None");
-                bail!(
-                    "Failed to parse release token at line: {}, kind: {}, content: `## {release}`. Expected format: `## [VERSION] - [DATE]` or `## [Unreleased]`",
-                    token.line,
-                    token.kind
-                )
+                valid = false;
+                self.report(ParseError::InvalidRelease {
+                    line,
+                    heading: release.clone(),
+                })?;
+            }
+
+            while let Some(token) = self.tokens.get(self.idx) {
+                let flag = token.content.join("\n");
+                let line = token.line;
+                if token.kind != TokenKind::Flag {
+                    break;
+                }
+
+                if flag == DRAFT_FLAG {
+                    builder.draft(true);
+                } else if let Some(base) = flag.strip_prefix(COMPARE_BASE_FLAG_PREFIX) {
+                    builder.compare_base(base.trim());
+                } else if let Some(metadata) = flag.strip_prefix(METADATA_FLAG_PREFIX) {
+                    match parse_pairs(metadata) {
+                        Some(metadata) => {
+                            builder.metadata(metadata);
+                        }
+                        None => self.report(ParseError::InvalidMetadata {
+                            line,
+                            content: metadata.trim().to_string(),
+                        })?,
+                    }
+                } else {
+                    break;
+                }
+
+                self.idx += 1;
             }
 
             builder.description(self.get_text_content()?);
 
-            while let (Some(_), Some(change_kind)) = self.get_content(vec![TokenKind::H3])? {
-                while let (Some(_), Some(change)) = self.get_content(vec![TokenKind::Li])? {
+            while let (Some(heading), Some(change_kind)) = self.get_content(vec![TokenKind::H3])? {
+                if heading.trim().eq_ignore_ascii_case(CONTRIBUTORS_HEADING) {
+                    let mut contributors = vec![];
+                    while let (Some(contributor), _) = self.get_content(vec![TokenKind::Li])? {
+                        contributors.push(contributor);
+                    }
+                    builder.contributors(contributors);
+                    continue;
+                }
+
+                let Ok(kind) = ChangeKind::from_str(&heading) else {
+                    self.report(ParseError::UnknownSection {
+                        line: change_kind.line,
+                        heading,
+                    })?;
+                    while let (Some(_), _) = self.get_content(vec![TokenKind::Li])? {}
+                    continue;
+                };
+
+                while let (Some(text), Some(change)) = self.get_content(vec![TokenKind::Li])? {
+                    if let Some(templates) = &self.opts.templates {
+                        if !templates.matches(&kind, &text) {
+                            let pattern = templates.pattern(&kind).unwrap_or_default().to_string();
+                            self.report(ParseError::InvalidEntry {
+                                line: change.line,
+                                kind: kind.clone(),
+                                pattern,
+                            })?;
+                        }
+                    }
                     builder.add_change(change_kind.clone(), change.clone())?;
+                    if let Some(span) = self.span(&change, &change) {
+                        builder.entry_span(kind.clone(), span);
+                    }
+                }
+            }
+
+            if let (Some(start), Some(end)) = (token.as_ref(), self.tokens.get(self.idx - 1)) {
+                builder.span(self.span(start, end));
+            }
+
+            if valid {
+                let release = builder.build()?;
+                if release_date {
+                    let anchor = MarkerAnchor::Release(release.anchor());
+                    self.markers.push((Marker::ReleaseDate, anchor));
                 }
+                releases.push(release);
             }
 
-            releases.push(builder.build()?);
+            let anchor = releases.last().map_or(MarkerAnchor::Header, |release| {
+                MarkerAnchor::Release(release.anchor())
+            });
+            self.parse_markers(anchor)?;
         }
 
-        self.builder.releases(releases);
+        self.builder
+            .releases(releases)
+            .markers(self.markers.clone())
+            .normalized_dates(self.normalized_dates.clone())
+            .templates(self.opts.templates.clone().unwrap_or_default())
+            .unreleased_template(self.opts.unreleased_template.clone())
+            .format(FormatOptions {
+                group_by_year,
+                inline_links,
+                #[cfg(feature = "json")]
+                sidecar: self.sidecar,
+                toc: self.toc,
+                ..Default::default()
+            });
 
         Ok(self)
     }
 
-    fn parse_links(&mut self, tokens: Vec<Token>) -> Result<&mut Self> {
-        let release_link_regex = Regex::new(r"^\[.*\]\:\s*(http.*?)\/(?:-\/)?compare\/.*$")?;
+    /// Parse the marker comments following the block with the `anchor`
+    fn parse_markers(&mut self, anchor: MarkerAnchor) -> Result<&mut Self> {
+        while let Some(token) = self.tokens.get(self.idx) {
+            if token.kind != TokenKind::Flag {
+                break;
+            }
+
+            match Marker::from_comment(&token.content.join("\n")) {
+                Some(marker) => self.markers.push((marker, anchor.clone())),
+                None => break,
+            }
 
+            self.idx += 1;
+        }
+
+        Ok(self)
+    }
+
+    fn parse_links(&mut self, tokens: Vec<Token>) -> Result<&mut Self> {
         let links = tokens
             .into_iter()
             .map(|t| {
-                let link = t.content.join("\n");
+                let mut link = Link::parse(t.content.join("\n"))?;
+                link.span = Unspanned(self.span(&t, &t));
 
                 if self.opts.url.is_none() {
-                    if let Some(captures) = release_link_regex.captures(&link) {
-                        self.builder.url(Some(captures[1].to_string()));
+                    if let Some(url) = compare_repo_url(&link.url) {
+                        self.builder.url(Some(url.to_string()));
+                    }
+                }
+                if self.opts.head.is_none() && link.anchor.eq_ignore_ascii_case("unreleased") {
+                    if let Some(head) = compare_head(&link.url) {
+                        self.builder.head(head.to_string());
                     }
                 }
 
-                link
+                Ok(link)
             })
-            .collect::<Vec<_>>();
+            .collect::<Result<Vec<_>>>()
+            .wrap_err_with(|| "Failed to parse links")?;
 
-        self.builder.links(links)?;
+        self.builder.parsed_links(links);
         Ok(self)
     }
 
+    /// Span of the source from the `start` token to the end of the `end` token
+    fn span(&self, start: &Token, end: &Token) -> Option<Span> {
+        let end = end.line + end.content.join("\n").matches('\n').count();
+        self.lines.span(start.line, end)
+    }
+
     fn parse_footer(&mut self) -> Result<&mut Self> {
         let (footer, _) = self.get_content(vec![TokenKind::Hr])?;
         self.builder.footer(footer);
@@ -154,13 +607,12 @@ impl Parser {
 
     fn build(&self) -> Result<Changelog> {
         log::debug!("idx is {} and len is {}", self.idx, self.tokens.len());
-        if self.idx != self.tokens.len() {
-            bail!(
-                "Unexpected tokens: {:?}, index: {}, tokens length: {}",
-                self.tokens[self.idx..].to_vec(),
-                self.idx,
-                self.tokens.len(),
-            );
+        if let Some(token) = self.tokens.get(self.idx) {
+            return Err(ParseError::UnexpectedContent {
+                line: token.line,
+                content: token.content.join("\n"),
+            }
+            .into());
         }
 
         self.builder
@@ -168,7 +620,10 @@ impl Parser {
             .map_err(|e| eyre!("Failed to build Changelog: {e}"))
     }
 
-    fn get_content(&mut self, kinds: Vec<TokenKind>) -> Result<(Option<String>, Option<Token>)> {
+    fn get_content(
+        &mut self,
+        kinds: Vec<TokenKind>,
+    ) -> Result<(Option<String>, Option<Token<'a>>)> {
         let token = self.tokens.get(self.idx);
 
         if token.is_none() {
@@ -177,7 +632,7 @@ impl Parser {
 
         let token = token.unwrap().clone();
 
-        if !kinds.iter().any(|k| *k == token.kind) {
+        if !kinds.contains(&token.kind) {
             return Ok((None, Some(token)));
         }
 
@@ -186,31 +641,22 @@ impl Parser {
     }
 
     fn get_text_content(&mut self) -> Result<Option<String>> {
-        let mut lines: Vec<String> = vec![];
-        let kinds = [TokenKind::P, TokenKind::Li];
-
-        while let Some(token) = self.tokens.get(self.idx) {
-            if !kinds.iter().any(|tt| *tt == token.kind) {
-                break;
-            }
-
+        let start = self.idx;
+        while self
+            .tokens
+            .get(self.idx)
+            .is_some_and(|token| [TokenKind::P, TokenKind::Li].contains(&token.kind))
+        {
             self.idx += 1;
-
-            if token.kind == TokenKind::Li {
-                lines.push(format!("- {}", token.content.join("\n")));
-            } else {
-                lines.push(token.content.join("\n"));
-            }
         }
 
-        if lines.is_empty() {
-            return Ok(None);
+        match start == self.idx {
+            true => Ok(None),
+            false => Ok(Some(text_content(&self.tokens[start..self.idx]))),
         }
-
-        Ok(Some(lines.join("\n")))
     }
 
-    fn get_lint_content(&mut self) -> Result<(Option<HashSet<String>>, Option<Token>)> {
+    fn get_lint_content(&mut self) -> Result<(Option<HashSet<String>>, Option<Token<'a>>)> {
         let kinds: Vec<TokenKind> = vec![TokenKind::Lint];
 
         let token = self.tokens.get(self.idx);
@@ -221,32 +667,12 @@ impl Parser {
 
         let token = token.unwrap().clone();
 
-        if !kinds.iter().any(|k| *k == token.kind) {
+        if !kinds.contains(&token.kind) {
             return Ok((None, Some(token)));
         }
 
         self.idx += 1;
-
-        let re = Regex::new(r"markdownlint-disable(?P<lints>( MD\d{3})+)")?;
-
-        if let Some(captures) = re.captures(&token.content[0]) {
-            let lints = captures
-                .name("lints")
-                .unwrap()
-                .as_str()
-                .trim()
-                .split(' ')
-                .map(|s| s.to_string())
-                .collect::<Vec<_>>();
-
-            let mut set = HashSet::new();
-            for s in &lints {
-                set.insert(s.to_string());
-            }
-
-            Ok((Some(set), Some(token)))
-        } else {
-            Err(eyre!("Failed to get lint content"))
-        }
+        let lint = parse_lint(&token.content[0])?;
+        Ok((Some(lint), Some(token)))
     }
 }
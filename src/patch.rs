@@ -0,0 +1,572 @@
+use std::{borrow::Cow, fmt, sync::OnceLock};
+
+use eyre::{Context, OptionExt, Result};
+use regex::Regex;
+
+use crate::{
+    consts::{PREFIX_H1, PREFIX_H2, PREFIX_HR},
+    parser::Parser,
+    placeholder::MarkerAnchor,
+    release::Release,
+    token::{tokenize, TokenKind},
+    utils::DisplayFn,
+    CalendarDate, Changelog, ChangelogParseOptions, Date,
+};
+
+/// Source regions of a CHANGELOG.md file.
+struct Layout<'a> {
+    header: &'a str,
+    blocks: Vec<&'a str>,
+    trailer: &'a str,
+}
+
+/// A piece of the resulting file, either kept from the source or rendered from the model.
+struct Piece<'a> {
+    text: Cow<'a, str>,
+    original: bool,
+}
+
+impl<'a> Piece<'a> {
+    fn original(text: &'a str) -> Self {
+        Self {
+            text: text.into(),
+            original: true,
+        }
+    }
+
+    fn rendered(text: String) -> Self {
+        Self {
+            text: text.into(),
+            original: false,
+        }
+    }
+}
+
+/// Splice the changelog into the source markdown, re-rendering only the changed regions.
+///
+/// Falls back to the full file contents if the source layout could not be mapped onto the model.
+pub(crate) fn apply(changelog: &Changelog, source: &str) -> Result<String> {
+    let opts = parse_options(changelog);
+    let old = Parser::parse(source.to_string(), Some(opts.clone()))
+        .wrap_err_with(|| "Failed to parse the existing changelog")?;
+
+    let layout = match layout(source)? {
+        Some(layout)
+            if old.compact() == changelog.compact()
+                && !old.format().requires_full_render()
+                && !changelog.format().requires_full_render() =>
+        {
+            layout
+        }
+        _ => {
+            log::debug!("Falling back to the full rewrite of the changelog");
+            return Ok(changelog.file_contents());
+        }
+    };
+
+    let old_releases = layout
+        .blocks
+        .iter()
+        .map(|block| Ok((parse_block(block, &opts)?, *block)))
+        .collect::<Result<Vec<(Release, &str)>>>()?;
+
+    let mut pieces = vec![];
+
+    let header_changed = old.lint() != changelog.lint()
+        || old.flag() != changelog.flag()
+        || old.title() != changelog.title()
+        || old.description() != changelog.description();
+
+    if header_changed {
+        pieces.push(Piece::rendered(render(|f| changelog.fmt_header(f, true))));
+    } else {
+        pieces.push(Piece::original(layout.header));
+    }
+
+    for release in changelog.releases() {
+        let unchanged = old_releases
+            .iter()
+            .find(|(old, _)| old == release)
+            .map(|(_, block)| *block);
+
+        match unchanged {
+            Some(block) => pieces.push(Piece::original(block)),
+            None => pieces.push(Piece::rendered(render(|f| {
+                changelog.fmt_release(f, release)?;
+                changelog.fmt_markers(f, &MarkerAnchor::Release(release.anchor()))
+            }))),
+        }
+    }
+
+    let trailer = render(|f| changelog.fmt_trailer(f, true));
+
+    if render(|f| old.fmt_trailer(f, true)) == trailer {
+        pieces.push(Piece::original(layout.trailer));
+    } else {
+        pieces.push(Piece::rendered(trailer));
+    }
+
+    Ok(join(pieces))
+}
+
+/// Insert the release block and its link definition into the source without re-rendering it.
+///
+/// The release is expected to be already added to the changelog, so links could be computed.
+pub(crate) fn insert_release(
+    changelog: &Changelog,
+    source: &str,
+    release: &Release,
+) -> Result<String> {
+    let date_regex = Regex::new(r"(\d{4}-\d{1,2}-\d{1,2})")?;
+    let mut lines = source.lines().map(|l| l.to_string()).collect::<Vec<_>>();
+
+    let headings = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with(PREFIX_H2))
+        .map(|(idx, _)| idx)
+        .collect::<Vec<_>>();
+
+    let is_older = |line: &str| {
+        if line.to_lowercase().contains("unreleased") {
+            return false;
+        }
+
+        let date = date_regex
+            .captures(line)
+            .and_then(|c| Date::parse_ymd(&c[1]));
+
+        match (date, release.date()) {
+            (Some(date), Some(release_date)) => date <= *release_date,
+            _ => true,
+        }
+    };
+
+    let position = match headings.iter().find(|idx| is_older(&lines[**idx])) {
+        Some(idx) => *idx,
+        None => {
+            let last = headings.last().copied().unwrap_or(0);
+            lines
+                .iter()
+                .enumerate()
+                .skip(last + 1)
+                .find(|(_, line)| line.starts_with('[') || line.starts_with(PREFIX_HR))
+                .map(|(idx, _)| idx)
+                .unwrap_or(lines.len())
+        }
+    };
+
+    let block = render(|f| changelog.fmt_release(f, release));
+    let mut block = block.lines().map(|l| l.to_string()).collect::<Vec<_>>();
+
+    if position > 0 && !lines[position - 1].trim().is_empty() {
+        block.insert(0, String::new());
+    }
+    if position == lines.len() {
+        while block.last().is_some_and(|l| l.is_empty()) {
+            block.pop();
+        }
+    }
+
+    lines.splice(position..position, block);
+
+    if changelog.url().is_some() {
+        update_links(changelog, &mut lines, release)?;
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    Ok(result)
+}
+
+/// Insert the release link and refresh the link of the next newer release.
+fn update_links(changelog: &Changelog, lines: &mut Vec<String>, release: &Release) -> Result<()> {
+    let releases = changelog.releases();
+    let index = releases
+        .iter()
+        .position(|r| r == release)
+        .ok_or_eyre("Release not found")?;
+
+    let find_link = |lines: &[String], anchor: &str| {
+        let prefix = format!("[{}]:", anchor).to_lowercase();
+        lines
+            .iter()
+            .position(|line| line.to_lowercase().starts_with(&prefix))
+    };
+
+    if let Some(newer) = index.checked_sub(1).and_then(|idx| releases.get(idx)) {
+        if let (Some(link), Some(anchor)) = (newer.compare_link(changelog)?, anchor(newer)) {
+            if let Some(position) = find_link(lines, &anchor) {
+                lines[position] = link.to_string();
+            }
+        }
+    }
+
+    let Some(link) = release.compare_link(changelog)? else {
+        return Ok(());
+    };
+
+    let older = releases.get(index + 1).and_then(anchor);
+    let newer = index
+        .checked_sub(1)
+        .and_then(|idx| releases.get(idx))
+        .and_then(anchor);
+
+    let position = older
+        .and_then(|anchor| find_link(lines, &anchor))
+        .or_else(|| {
+            newer
+                .and_then(|anchor| find_link(lines, &anchor))
+                .map(|p| p + 1)
+        });
+
+    match position {
+        Some(position) => lines.insert(position, link.to_string()),
+        None => {
+            if lines
+                .last()
+                .is_some_and(|l| !l.trim().is_empty() && !l.starts_with('['))
+            {
+                lines.push(String::new());
+            }
+            lines.push(link.to_string());
+        }
+    }
+
+    Ok(())
+}
+
+fn anchor(release: &Release) -> Option<String> {
+    match (release.version(), release.date()) {
+        (Some(version), _) => Some(version.to_string()),
+        (None, None) => Some("Unreleased".to_string()),
+        _ => None,
+    }
+}
+
+pub(crate) fn parse_options(changelog: &Changelog) -> ChangelogParseOptions {
+    ChangelogParseOptions {
+        url: changelog.url().clone(),
+        tag_prefix: changelog.tag_prefix().clone(),
+        head: Some(changelog.head().clone()),
+        ..Default::default()
+    }
+}
+
+fn render<F>(fmt: F) -> String
+where
+    F: Fn(&mut fmt::Formatter) -> fmt::Result,
+{
+    DisplayFn(fmt).to_string().replace("\n\n\n", "\n\n")
+}
+
+/// Title prepended to release blocks, so they could be parsed as a changelog
+const BLOCK_PREFIX: &str = "# Changelog\n\n";
+
+/// Line, starting from 1, and byte offset of the first release heading other than Unreleased or
+/// link definition of the source, where the elements following the Unreleased block start.
+pub(crate) fn following_start(source: &str) -> Option<(usize, usize)> {
+    static YEAR: OnceLock<Regex> = OnceLock::new();
+    let year = YEAR.get_or_init(|| Regex::new(r"^##\s+\d{4}\s*$").unwrap());
+
+    let mut offset = 0;
+    for (idx, line) in source.split('\n').enumerate() {
+        let is_release = line.starts_with(PREFIX_H2)
+            && !line.to_lowercase().contains("unreleased")
+            && !year.is_match(line);
+        let is_link = line.starts_with('[') && line.contains("]:");
+        if is_release || is_link {
+            return Some((idx + 1, offset));
+        }
+        offset += line.len() + 1;
+    }
+
+    None
+}
+
+/// Re-parse only the Unreleased block of the source, spans refer to the whole source.
+///
+/// Returns `None` if the source has no Unreleased section.
+pub(crate) fn parse_unreleased(changelog: &Changelog, source: &str) -> Result<Option<Release>> {
+    let opts = parse_options(changelog);
+    let lines = source.split('\n').collect::<Vec<_>>();
+    let mut line_starts = vec![0];
+    line_starts.extend(source.match_indices('\n').map(|(idx, _)| idx + 1));
+
+    let is_boundary = |line: &str| {
+        line.starts_with(PREFIX_H1)
+            || line.starts_with(PREFIX_H2)
+            || line.starts_with(PREFIX_HR)
+            || (line.starts_with('[') && line.contains("]:"))
+    };
+
+    let candidates = lines.iter().enumerate().filter(|(_, line)| {
+        line.starts_with(PREFIX_H2) && line.to_lowercase().contains("unreleased")
+    });
+
+    for (idx, _) in candidates {
+        let end = lines
+            .iter()
+            .enumerate()
+            .skip(idx + 1)
+            .find(|(_, line)| is_boundary(line))
+            .map_or(source.len(), |(end, _)| line_starts[end]);
+        let start = line_starts[idx];
+
+        let mut release = parse_block(&source[start..end], &opts)?;
+        if release.version().is_some() || release.date().is_some() {
+            continue;
+        }
+
+        let prefix_lines = BLOCK_PREFIX.matches('\n').count();
+        release.shift_spans(
+            idx as isize - prefix_lines as isize,
+            start as isize - BLOCK_PREFIX.len() as isize,
+        );
+        return Ok(Some(release));
+    }
+
+    Ok(None)
+}
+
+pub(crate) fn parse_block(block: &str, opts: &ChangelogParseOptions) -> Result<Release> {
+    let changelog = Parser::parse(format!("{BLOCK_PREFIX}{block}"), Some(opts.clone()))?;
+    let mut releases = changelog.releases().clone();
+
+    if releases.len() != 1 {
+        eyre::bail!("Expected a single release in the block: {block}");
+    }
+
+    Ok(releases.remove(0))
+}
+
+/// Split the source into the header, release blocks and the trailing links and footer.
+///
+/// Returns `None` if there are no releases or link definitions are interleaved with releases.
+fn layout(source: &str) -> Result<Option<Layout<'_>>> {
+    let (_, tokens) = tokenize(source)?;
+
+    // tokenizer trims the markdown, so line numbers are shifted by the leading blank lines
+    let leading = source.len() - source.trim_start().len();
+    let offset = source[..leading].matches('\n').count();
+
+    let headings = tokens
+        .iter()
+        .filter(|t| t.kind == TokenKind::H2)
+        .map(|t| t.line + offset)
+        .collect::<Vec<_>>();
+
+    let (Some(first), Some(last)) = (headings.first(), headings.last()) else {
+        return Ok(None);
+    };
+
+    let interleaved = tokens
+        .iter()
+        .any(|t| t.kind == TokenKind::Link && t.line + offset > *first && t.line + offset < *last);
+
+    if interleaved {
+        return Ok(None);
+    }
+
+    let mut line_starts = vec![0];
+    line_starts.extend(source.match_indices('\n').map(|(idx, _)| idx + 1));
+    let line_start = |line: usize| line_starts.get(line - 1).copied().unwrap_or(source.len());
+
+    let trailer_start = tokens
+        .iter()
+        .map(|t| (t.line + offset, &t.kind))
+        .filter(|(line, kind)| *line > *last && matches!(kind, TokenKind::Link | TokenKind::Hr))
+        .map(|(line, _)| line_start(line))
+        .min()
+        .unwrap_or(source.len());
+
+    let mut bounds = headings
+        .iter()
+        .map(|line| line_start(*line))
+        .collect::<Vec<_>>();
+    bounds.push(trailer_start);
+
+    Ok(Some(Layout {
+        header: &source[..bounds[0]],
+        blocks: bounds.windows(2).map(|w| &source[w[0]..w[1]]).collect(),
+        trailer: &source[trailer_start..],
+    }))
+}
+
+fn join(pieces: Vec<Piece>) -> String {
+    let mut result = String::new();
+    let mut previous_original = true;
+
+    for piece in pieces {
+        if piece.text.is_empty() {
+            continue;
+        }
+
+        // original pieces are kept with their own spacing, only rendered ones are separated
+        let separated = result.is_empty() || result.ends_with("\n\n");
+        let both_original = previous_original && piece.original;
+        if !separated && !both_original {
+            if !result.ends_with('\n') {
+                result.push('\n');
+            }
+            result.push('\n');
+        }
+
+        result.push_str(&piece.text);
+        previous_original = piece.original;
+    }
+
+    if !previous_original {
+        result = result.trim_end_matches('\n').to_string();
+        result.push('\n');
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use semver::Version;
+
+    use super::*;
+    use crate::ChangeKind;
+
+    const SOURCE: &str = "# Changelog\n\
+        \n\
+        Hand-written description.\n\
+        \n\
+        ## [Unreleased]\n\
+        \n\
+        ## [0.1.1] - 2024-05-18\n\
+        \n\
+        ### Fixed\n\
+        \n\
+        * Hand-formatted   entry\n\
+        \n\
+        ## [0.1.0] - 2024-04-28\n\
+        \n\
+        ### Added\n\
+        \n\
+        - Initial release\n\
+        \n\
+        [Unreleased]: https://github.com/owner/repo/compare/0.1.1...HEAD\n\
+        [0.1.1]: https://github.com/owner/repo/compare/0.1.0...0.1.1\n\
+        [0.1.0]: https://github.com/owner/repo/releases/tag/0.1.0\n";
+
+    #[test]
+    fn test_insert_release_matches_full_render() -> Result<()> {
+        let mut changelog = Changelog::parse(SOURCE.to_string(), None)?;
+        let original = changelog.file_contents();
+
+        let release = Release::builder()
+            .version(Version::parse("0.2.0")?)
+            .date(Date::new(2024, 6, 1).unwrap())
+            .changes(crate::Changes::builder().added("Another feature").build()?)
+            .build()?;
+        changelog.add_release(release.clone());
+
+        let result = insert_release(&changelog, &original, &release)?;
+        assert_eq!(result, changelog.file_contents());
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_older_release() -> Result<()> {
+        let mut changelog = Changelog::parse(SOURCE.to_string(), None)?;
+        let original = changelog.file_contents();
+
+        let release = Release::builder()
+            .version(Version::parse("0.0.1")?)
+            .date(Date::new(2024, 1, 1).unwrap())
+            .build()?;
+        changelog.add_release(release.clone());
+
+        let result = insert_release(&changelog, &original, &release)?;
+        assert_eq!(result, changelog.file_contents());
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_unchanged() -> Result<()> {
+        let changelog = Changelog::parse(SOURCE.to_string(), None)?;
+        assert_eq!(apply(&changelog, SOURCE)?, SOURCE);
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_keeps_untouched_regions() -> Result<()> {
+        let mut changelog = Changelog::parse(SOURCE.to_string(), None)?;
+        changelog
+            .get_unreleased_mut()
+            .unwrap()
+            .added("New feature".to_string());
+
+        let result = apply(&changelog, SOURCE)?;
+
+        assert!(result.starts_with("# Changelog\n\nHand-written description.\n\n"));
+        assert!(result.contains("## [Unreleased]\n\n### Added\n\n- New feature\n\n## [0.1.1]"));
+        assert!(result.contains("* Hand-formatted   entry\n"));
+        assert!(result.ends_with("[0.1.0]: https://github.com/owner/repo/releases/tag/0.1.0\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_new_release() -> Result<()> {
+        let mut changelog = Changelog::parse(SOURCE.to_string(), None)?;
+        let mut release = Release::builder()
+            .version(Version::parse("0.2.0")?)
+            .date(Date::new(2024, 6, 1).unwrap())
+            .build()?;
+        release.added("Another feature".to_string());
+        changelog.add_release(release);
+
+        let result = apply(&changelog, SOURCE)?;
+
+        assert!(result.contains(
+            "## [Unreleased]\n\n## [0.2.0] - 2024-06-01\n\n### Added\n\n- Another feature\n\n## [0.1.1]"
+        ));
+        assert!(result.contains("* Hand-formatted   entry\n"));
+        assert!(result.contains(
+            "[Unreleased]: https://github.com/owner/repo/compare/0.2.0...HEAD\n\
+            [0.2.0]: https://github.com/owner/repo/compare/0.1.1...0.2.0\n"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reparse_unreleased() -> Result<()> {
+        let mut changelog = Changelog::parse(SOURCE.to_string(), None)?;
+        let source = SOURCE.replace(
+            "## [Unreleased]\n",
+            "## [Unreleased]\n\n### Added\n\n- New feature\n  spanning two lines\n",
+        );
+
+        let spans = |changelog: &Changelog| {
+            let releases = changelog.releases().iter().map(|r| r.span().cloned());
+            let links = changelog.links().iter().map(|l| l.span().cloned());
+            releases.chain(links).collect::<Vec<_>>()
+        };
+
+        changelog.reparse_unreleased(&source)?;
+        let parsed = Changelog::parse(source.clone(), None)?;
+        assert_eq!(changelog.releases(), parsed.releases());
+        assert_eq!(spans(&changelog), spans(&parsed));
+
+        let unreleased = changelog.get_unreleased().unwrap();
+        let span = unreleased.entry_span(&ChangeKind::Added, 0).unwrap();
+        assert_eq!(span.lines, 9..=10);
+        assert_eq!(
+            &source[span.bytes.clone()],
+            "- New feature\n  spanning two lines"
+        );
+
+        let source = source.replace(
+            "## [Unreleased]\n\n### Added\n\n- New feature\n  spanning two lines\n",
+            "",
+        );
+        changelog.reparse_unreleased(&source)?;
+        assert!(changelog.get_unreleased().is_none());
+        assert_eq!(spans(&changelog), spans(&Changelog::parse(source, None)?));
+        Ok(())
+    }
+}
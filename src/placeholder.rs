@@ -0,0 +1,367 @@
+//! Placeholders and markers used by release automation tools such as cargo-release.
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use eyre::{bail, Error, Result};
+
+use crate::{ChangeKind, Changelog, Changes, Date, Release, ReleaseVersion};
+
+pub(crate) const PLACEHOLDER_VERSION: &str = "{{version}}";
+pub(crate) const PLACEHOLDER_DATE: &str = "{{date}}";
+pub(crate) const RELEASE_DATE_SUFFIX: &str = " - ReleaseDate";
+
+/// Marker nodes used by release automation tools.
+///
+/// A typical changelog prepared for [cargo-release](https://github.com/crate-ci/cargo-release)
+/// looks like:
+///
+/// ```markdown
+/// <!-- next-header -->
+/// ## [Unreleased] - ReleaseDate
+///
+/// <!-- next-url -->
+/// [Unreleased]: https://github.com/owner/repo/compare/v1.0.0...HEAD
+/// ```
+///
+/// Markers are preserved on output at their [`MarkerAnchor`]s, while `{{version}}` and `{{date}}`
+/// placeholders in the text are substituted by [`Changelog::expand_placeholders`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Marker {
+    /// `<!-- next-header -->` comment placed before the first release
+    NextHeader,
+    /// `<!-- next-url -->` comment placed before the release links
+    NextUrl,
+    /// `ReleaseDate` placeholder in the Unreleased heading: `## [Unreleased] - ReleaseDate`
+    ReleaseDate,
+}
+
+impl Marker {
+    /// Marker of the `<!-- comment -->` with the given text, `ReleaseDate` is only a heading
+    /// suffix and never a comment
+    pub(crate) fn from_comment(comment: &str) -> Option<Self> {
+        match comment {
+            "next-header" => Some(Self::NextHeader),
+            "next-url" => Some(Self::NextUrl),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Marker {
+    type Err = Error;
+
+    /// Parse the marker as it's written in the changelog, e.g. `<!-- next-header -->`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "<!-- next-header -->" => Ok(Self::NextHeader),
+            "<!-- next-url -->" => Ok(Self::NextUrl),
+            "ReleaseDate" => Ok(Self::ReleaseDate),
+            _ => bail!("Unknown marker: {}", s),
+        }
+    }
+}
+
+impl Display for Marker {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Marker::NextHeader => write!(f, "<!-- next-header -->"),
+            Marker::NextUrl => write!(f, "<!-- next-url -->"),
+            Marker::ReleaseDate => write!(f, "ReleaseDate"),
+        }
+    }
+}
+
+/// Block of the changelog a [`Marker`] follows, the marker is written back right after it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum MarkerAnchor {
+    /// The title, description and table of contents, i.e. before the first release
+    Header,
+    /// The release with the anchor, e.g. `Unreleased` or `1.0.0`. A
+    /// [`Marker::ReleaseDate`] is the suffix of the heading of this release.
+    ///
+    /// If the release isn't rendered anymore, the marker is written before the release links.
+    Release(String),
+}
+
+/// Placeholder entries of a fresh Unreleased release, see
+/// [`Changelog::set_unreleased_template`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnreleasedTemplate {
+    /// Sections with a placeholder entry
+    pub kinds: Vec<ChangeKind>,
+    /// Text of the placeholder entries, recognized case-insensitively and with or without a
+    /// trailing period
+    pub placeholder: String,
+}
+
+impl Default for UnreleasedTemplate {
+    fn default() -> Self {
+        Self {
+            kinds: vec![ChangeKind::Added],
+            placeholder: "Nothing yet".to_string(),
+        }
+    }
+}
+
+impl UnreleasedTemplate {
+    /// Whether the entry is a placeholder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::UnreleasedTemplate;
+    ///
+    /// let template = UnreleasedTemplate::default();
+    /// assert!(template.is_placeholder("nothing yet."));
+    /// assert!(!template.is_placeholder("Nothing yet, except the parser"));
+    /// ```
+    pub fn is_placeholder(&self, text: &str) -> bool {
+        let normalize = |text: &str| text.trim().trim_end_matches('.').to_lowercase();
+        normalize(text) == normalize(&self.placeholder)
+    }
+
+    /// Unreleased release with the placeholder entries
+    pub(crate) fn release(&self) -> Release {
+        let mut release = Release::builder()
+            .build()
+            .expect("Unreleased release is valid");
+        self.kinds.iter().for_each(|kind| {
+            release.add(kind.clone(), self.placeholder.clone());
+        });
+        release
+    }
+}
+
+impl Changelog {
+    /// Pre-populate fresh Unreleased releases with placeholder entries, e.g. `- Nothing yet`
+    ///
+    /// Placeholders are dropped from the Unreleased release when a real entry is added with
+    /// [`Changelog::add_unreleased_change`] and when it's promoted with
+    /// [`Changelog::promote_unreleased`], which adds a new Unreleased release with the
+    /// placeholders. To recognize the placeholders of a parsed changelog, pass the template in
+    /// [`ChangelogParseOptions::unreleased_template`](crate::ChangelogParseOptions::unreleased_template).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{
+    ///     CalendarDate, ChangeKind, Changelog, Date, UnreleasedTemplate, Version,
+    /// };
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Added\n- Nothing yet\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    /// changelog.set_unreleased_template(UnreleasedTemplate::default());
+    ///
+    /// changelog.add_unreleased_change(ChangeKind::Fixed, "Bug fix");
+    /// let entries = changelog.get_unreleased().unwrap().changes().iter().count();
+    /// assert_eq!(entries, 1);
+    ///
+    /// let date = Date::new(2024, 6, 1).unwrap();
+    /// changelog.promote_unreleased(Version::new(0, 1, 0), date).unwrap();
+    /// assert!(changelog
+    ///     .to_string()
+    ///     .contains("## [Unreleased]\n### Added\n- Nothing yet\n\n## [0.1.0] - 2024-06-01\n"));
+    /// ```
+    pub fn set_unreleased_template(&mut self, template: UnreleasedTemplate) -> &mut Self {
+        self.unreleased_template = Some(template);
+        self
+    }
+
+    /// Remove the placeholder entries from the Unreleased release
+    pub(crate) fn drop_placeholders(&mut self) {
+        let Some(template) = self.unreleased_template.clone() else {
+            return;
+        };
+        let Some(unreleased) = self.get_unreleased_mut() else {
+            return;
+        };
+
+        let changes = unreleased
+            .changes()
+            .iter()
+            .filter(|(_, text)| !template.is_placeholder(text))
+            .map(|(kind, text)| (kind, text.clone()))
+            .collect::<Vec<_>>();
+        unreleased.set_changes(Changes::from(changes));
+    }
+
+    /// Expand release automation placeholders
+    ///
+    /// Promotes the Unreleased release to the given version and date (see
+    /// [`Changelog::promote_unreleased`]) and substitutes `{{version}}` and `{{date}}`
+    /// placeholders in its description and entries, in the links and in the footer, the same way
+    /// cargo-release does on `cargo release`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{CalendarDate, Changelog, Date, Version};
+    ///
+    /// let markdown = "# Changelog\n<!-- next-header -->\n## [Unreleased] - ReleaseDate\n\
+    ///     \n### Added\n- Feature available since {{version}}\n";
+    ///
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    /// changelog
+    ///     .expand_placeholders(
+    ///         Version::parse("1.0.0").unwrap(),
+    ///         Date::new(2024, 6, 1).unwrap(),
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let output = changelog.to_string();
+    /// assert!(output.contains("<!-- next-header -->\n## [Unreleased] - ReleaseDate\n"));
+    /// assert!(output.contains("## [1.0.0] - 2024-06-01\n"));
+    /// assert!(output.contains("- Feature available since 1.0.0\n"));
+    /// ```
+    pub fn expand_placeholders<V: Into<ReleaseVersion>>(
+        &mut self,
+        version: V,
+        date: Date,
+    ) -> Result<&mut Self> {
+        let version = version.into();
+        let version_str = version.to_string();
+        let date_str = date.to_string();
+        let expand = |text: &str| {
+            text.replace(PLACEHOLDER_VERSION, &version_str)
+                .replace(PLACEHOLDER_DATE, &date_str)
+        };
+
+        self.promote_unreleased(version.clone(), date)?;
+
+        if let Some(release) = self.find_release_mut(version_str.clone())? {
+            if let Some(description) = release.description().clone() {
+                release.set_description(expand(&description));
+            }
+
+            let mut changes = release.changes().clone();
+            changes
+                .iter_mut()
+                .for_each(|(_, change)| *change = expand(change));
+            release.set_changes(changes);
+        }
+
+        self.expand_links_and_footer(&expand);
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CalendarDate;
+
+    #[test]
+    fn test_expand_placeholders() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/cargo_release_changelog.md")?;
+        let mut changelog = Changelog::parse(markdown, None)?;
+        let release = |anchor: &str| MarkerAnchor::Release(anchor.to_string());
+        assert_eq!(
+            changelog.markers(),
+            &vec![
+                (Marker::NextHeader, MarkerAnchor::Header),
+                (Marker::ReleaseDate, release("Unreleased")),
+                (Marker::NextUrl, release("0.1.0")),
+            ]
+        );
+
+        changelog.expand_placeholders(
+            ReleaseVersion::parse("0.2.0")?,
+            Date::new(2024, 6, 1).unwrap(),
+        )?;
+
+        let output = changelog.to_string();
+        assert!(output.contains(
+            "<!-- next-header -->\n## [Unreleased] - ReleaseDate\n\n## [0.2.0] - 2024-06-01\n"
+        ));
+        assert!(output.contains("- Feature available since 0.2.0\n"));
+        assert!(output.contains("<!-- next-url -->\n[Unreleased]: https://github.com/napalmpapalam/keep-a-changelog-rs/compare/0.2.0...HEAD\n"));
+        assert!(!output.contains(PLACEHOLDER_VERSION));
+        Ok(())
+    }
+
+    #[test]
+    fn test_marker_anchors() -> Result<()> {
+        let markdown = "# Changelog\n\n## [Unreleased]\n\n### Added\n\n- Feature\n\n\
+            <!-- next-header -->\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n";
+        let mut changelog = Changelog::parse(markdown.to_string(), None)?;
+        assert_eq!(
+            changelog.markers(),
+            &vec![(
+                Marker::NextHeader,
+                MarkerAnchor::Release("Unreleased".to_string())
+            )]
+        );
+        assert!(changelog
+            .to_string()
+            .contains("- Feature\n\n<!-- next-header -->\n## [0.1.0] - 2024-04-28\n"));
+
+        // the marker of a removed release is kept before the links
+        changelog.releases_mut().remove(0);
+        assert!(changelog
+            .to_string()
+            .ends_with("- Initial release\n\n<!-- next-header -->\n"));
+
+        assert_eq!("<!-- next-url -->".parse::<Marker>()?, Marker::NextUrl);
+        assert!("<!-- releasedate -->".parse::<Marker>().is_err());
+        assert!(Marker::from_comment("releasedate").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_promote_without_unreleased() -> Result<()> {
+        let mut changelog = Changelog::parse(
+            "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n".to_string(),
+            None,
+        )?;
+
+        let result = changelog.expand_placeholders(
+            ReleaseVersion::parse("0.2.0")?,
+            Date::new(2024, 6, 1).unwrap(),
+        );
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unreleased_template() -> Result<()> {
+        let markdown =
+            "# Changelog\n## [Unreleased]\n### Added\n- Nothing yet.\n### Fixed\n- Nothing yet\n\
+            \n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+        let template = UnreleasedTemplate {
+            kinds: vec![ChangeKind::Added, ChangeKind::Fixed],
+            ..Default::default()
+        };
+        let opts = crate::ChangelogParseOptions {
+            unreleased_template: Some(template.clone()),
+            ..Default::default()
+        };
+
+        let mut changelog = Changelog::parse(markdown.to_string(), Some(opts))?;
+        assert_eq!(
+            changelog.to_string(),
+            Changelog::parse(markdown.to_string(), None)?.to_string()
+        );
+
+        let date = Date::new(2024, 6, 1).unwrap();
+        changelog.promote_unreleased(ReleaseVersion::parse("0.2.0")?, date)?;
+        let promoted = changelog.find_release("0.2.0".to_string())?.unwrap();
+        assert!(promoted.changes().is_empty());
+
+        let unreleased = changelog.get_unreleased().unwrap().changes().clone();
+        assert_eq!(unreleased, *template.release().changes());
+
+        changelog.add_entry(None, ChangeKind::Added, "Dark mode")?;
+        let entries = changelog
+            .get_unreleased()
+            .unwrap()
+            .changes()
+            .iter()
+            .map(|(kind, text)| (kind, text.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(entries, [(ChangeKind::Added, "Dark mode".to_string())]);
+        Ok(())
+    }
+}
@@ -1,44 +1,130 @@
 use std::{
+    collections::BTreeMap,
     fmt::{self, Display, Formatter},
     str::FromStr,
 };
 
-use chrono::NaiveDate;
 use derive_builder::Builder;
 use derive_getters::Getters;
 use derive_setters::Setters;
 use eyre::{eyre, OptionExt, Result};
-use semver::Version;
 
 use crate::{
-    changes::{ChangeKind, Changes},
+    changes::{ChangeEntry, ChangeKind, Changes},
+    consts::{CONTRIBUTORS_HEADING, NO_NOTABLE_CHANGES},
+    format::{FormatOptions, HeadingStyle},
     link::Link,
+    release_date::ReleaseDate,
+    span::{Span, Unspanned},
     token::Token,
-    Changelog,
+    version::ReleaseVersion,
+    CalendarDate, Changelog, Date,
 };
 
-#[derive(Debug, Clone, Builder, Getters, Setters, PartialEq, Eq)]
+#[derive(Debug, Clone, Builder, Getters, Setters)]
 #[setters(prefix = "set_")]
 pub struct Release {
     #[setters(strip_option, into, borrow_self)]
     #[builder(setter(strip_option, into), default)]
-    version: Option<Version>,
+    version: Option<ReleaseVersion>,
     #[builder(default = "false")]
     yanked: bool,
+    /// Draft releases are kept in CHANGELOG.md with a `<!-- draft -->` marker below the heading,
+    /// but are excluded from the rendered output and exports
+    #[builder(default = "false")]
+    #[setters(borrow_self)]
+    draft: bool,
+    /// Arbitrary metadata for release automation, e.g. commit SHA or build ID, kept in
+    /// CHANGELOG.md as a `<!-- kac: sha=abc123 -->` comment below the heading
+    #[builder(default)]
+    #[setters(borrow_self)]
+    metadata: BTreeMap<String, String>,
+    /// Git ref to compare the release against instead of the previous release, e.g. a rewritten
+    /// tag or a fork point, kept in CHANGELOG.md as a `<!-- compare-base: v1.0.0 -->` comment
+    /// below the heading
+    #[setters(strip_option, into, borrow_self)]
+    #[builder(setter(strip_option, into), default)]
+    compare_base: Option<String>,
     #[setters(strip_option, into, borrow_self)]
     #[builder(setter(into), default)]
     description: Option<String>,
-    #[setters(strip_option, into, borrow_self)]
+    /// Date of the release, the first day of the month if only the month is known, see
+    /// [`Release::release_date`]
+    #[setters(skip)]
     #[builder(setter(strip_option, into), default)]
-    date: Option<NaiveDate>,
+    date: Option<Date>,
+    /// Only the month of the date is known, e.g. `## [0.3.0] - 2019-02`
+    #[builder(setter(custom), default)]
+    #[setters(skip)]
+    #[getter(skip)]
+    month_date: bool,
+    /// Whether the parsed heading wrapped the version in brackets, see
+    /// [`FormatOptions::heading_style`]
+    #[builder(setter(custom), default)]
+    #[setters(skip)]
+    #[getter(skip)]
+    pub(crate) brackets: Option<bool>,
     #[setters(strip_option, into, borrow_self)]
     #[builder(default)]
     changes: Changes,
+    /// Contributors credited in the `### Contributors` section of the release
+    #[builder(default)]
+    #[setters(borrow_self)]
+    contributors: Vec<String>,
+    #[builder(setter(custom), default)]
+    #[setters(skip)]
+    #[getter(skip)]
+    span: Unspanned<Option<Span>>,
+    #[builder(setter(custom), default)]
+    #[setters(skip)]
+    #[getter(skip)]
+    entry_spans: Unspanned<Vec<(ChangeKind, Span)>>,
     #[builder(private, default)]
     #[setters(skip)]
     compact: bool,
+    #[builder(private, default)]
+    #[setters(skip)]
+    format: FormatOptions,
+}
+
+impl PartialEq for Release {
+    /// Releases are equal if they have the same contents, the spans and render settings are
+    /// ignored
+    fn eq(&self, other: &Self) -> bool {
+        let Release {
+            version,
+            yanked,
+            draft,
+            metadata,
+            compare_base,
+            description,
+            date,
+            month_date,
+            brackets,
+            changes,
+            contributors,
+            span: _,
+            entry_spans: _,
+            compact: _,
+            format: _,
+        } = self;
+
+        *version == other.version
+            && *yanked == other.yanked
+            && *draft == other.draft
+            && *metadata == other.metadata
+            && *compare_base == other.compare_base
+            && *description == other.description
+            && *date == other.date
+            && *month_date == other.month_date
+            && *brackets == other.brackets
+            && *changes == other.changes
+            && *contributors == other.contributors
+    }
 }
 
+impl Eq for Release {}
+
 impl ReleaseBuilder {
     pub fn add_change(&mut self, kind_token: Token, change_token: Token) -> Result<&mut Self> {
         let mut changes = self.changes.clone().unwrap_or_default();
@@ -54,6 +140,30 @@ impl ReleaseBuilder {
         self.changes = Some(changes);
         Ok(self)
     }
+
+    pub(crate) fn span(&mut self, span: Option<Span>) -> &mut Self {
+        self.span = Some(Unspanned(span));
+        self
+    }
+
+    pub(crate) fn brackets(&mut self, brackets: bool) -> &mut Self {
+        self.brackets = Some(Some(brackets));
+        self
+    }
+
+    pub(crate) fn release_date(&mut self, date: ReleaseDate) -> &mut Self {
+        self.date = Some(date.first_day());
+        self.month_date = Some(date.is_partial());
+        self
+    }
+
+    pub(crate) fn entry_span(&mut self, kind: ChangeKind, span: Span) -> &mut Self {
+        self.entry_spans
+            .get_or_insert_with(Default::default)
+            .0
+            .push((kind, span));
+        self
+    }
 }
 
 impl Release {
@@ -61,11 +171,11 @@ impl Release {
     /// # Examples
     ///
     /// ```
-    /// use keep_a_changelog::{Changelog, Release, NaiveDate, Version};
+    /// use keep_a_changelog::{CalendarDate, Changelog, Date, Release, Version};
     ///
     /// let release = Release::builder()
     ///    .version(Version::parse("0.1.0").unwrap())
-    ///    .date(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
+    ///    .date(Date::new(2021, 1, 1).unwrap())
     ///    .build();
     ///
     /// ```
@@ -73,7 +183,148 @@ impl Release {
         ReleaseBuilder::default()
     }
 
+    /// Whether the release is planned but not dated yet, rendered as `## [1.3.0] - TBD`
+    ///
+    /// Planned releases are sorted above the dated ones and aren't the Unreleased release.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n\n## [1.3.0] - TBD\n### Added\n- Plugins\n\
+    ///     \n## [1.2.0] - 2024-04-28\n### Added\n- Themes\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let planned = changelog.find_release("1.3.0".to_string()).unwrap().unwrap();
+    /// assert!(planned.is_tbd());
+    /// assert!(changelog.get_unreleased().unwrap().changes().is_empty());
+    /// assert!(changelog.to_string().contains("## [1.3.0] - TBD\n"));
+    /// ```
+    pub fn is_tbd(&self) -> bool {
+        self.version.is_some() && self.date.is_none()
+    }
+
+    /// Set the date of the release
+    pub fn set_date(&mut self, date: impl Into<Date>) -> &mut Self {
+        self.set_release_date(ReleaseDate::Day(date.into()))
+    }
+
+    /// Date of the release as written in its heading
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{CalendarDate, Changelog, Date, ReleaseDate};
+    ///
+    /// let markdown = "# Changelog\n## [0.3.0] - 2019-02\n### Added\n- Plugins\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let release = &mut changelog.releases_mut()[0];
+    /// assert_eq!(release.release_date(), ReleaseDate::Month(2019, 2));
+    /// assert_eq!(*release.date(), Date::new(2019, 2, 1));
+    /// assert!(changelog.to_string().contains("## [0.3.0] - 2019-02\n"));
+    /// ```
+    pub fn release_date(&self) -> ReleaseDate {
+        match (self.date, self.month_date) {
+            (Some(date), true) => {
+                let (year, month, _) = date.ymd();
+                ReleaseDate::Month(year, month)
+            }
+            (date, _) => ReleaseDate::from(date),
+        }
+    }
+
+    /// Set the date of the release, [`Release::date`] is the first day of a
+    /// [`ReleaseDate::Month`]
+    pub fn set_release_date(&mut self, date: ReleaseDate) -> &mut Self {
+        self.date = date.first_day();
+        self.month_date = date.is_partial();
+        self
+    }
+
+    /// Anchor of the reference link for this release, the version or `Unreleased`.
+    pub fn anchor(&self) -> String {
+        match &self.version {
+            Some(version) => version.to_string(),
+            None => "Unreleased".to_string(),
+        }
+    }
+
+    /// Get the existing reference link for this release, anchors are matched
+    /// case-insensitively.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n## [0.1.0] - 2024-04-28\n- Initial release\n\n\
+    ///     [unreleased]: https://github.com/owner/repo/compare/0.1.0...HEAD\n\
+    ///     [0.1.0]: https://github.com/owner/repo/releases/tag/0.1.0\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let unreleased = changelog.get_unreleased().unwrap();
+    /// assert_eq!(unreleased.anchor(), "Unreleased");
+    /// assert_eq!(
+    ///     unreleased.link(&changelog).unwrap().url(),
+    ///     "https://github.com/owner/repo/compare/0.1.0...HEAD"
+    /// );
+    /// ```
+    pub fn link<'a>(&self, changelog: &'a Changelog) -> Option<&'a Link> {
+        let anchor = self.anchor().to_lowercase();
+        changelog
+            .links()
+            .iter()
+            .find(|link| link.anchor.to_lowercase() == anchor)
+    }
+
+    /// Location of the release in the parsed source, from the heading to the last line of its
+    /// content, `None` if the release wasn't parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{ChangeKind, Changelog};
+    ///
+    /// let markdown = "# Changelog\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    /// let release = changelog.releases().first().unwrap();
+    ///
+    /// assert_eq!(release.span().unwrap().lines, 3..=7);
+    ///
+    /// let span = release.entry_span(&ChangeKind::Added, 0).unwrap();
+    /// assert_eq!(&markdown[span.bytes.clone()], "- Initial release");
+    /// ```
+    pub fn span(&self) -> Option<&Span> {
+        self.span.0.as_ref()
+    }
+
+    pub(crate) fn shift_spans(&mut self, lines: isize, bytes: isize) {
+        if let Some(span) = self.span.0.as_mut() {
+            *span = span.shifted(lines, bytes);
+        }
+        for (_, span) in self.entry_spans.0.iter_mut() {
+            *span = span.shifted(lines, bytes);
+        }
+    }
+
+    /// Location of the `index`-th change of `kind` in the parsed source, `None` if the release
+    /// wasn't parsed.
+    pub fn entry_span(&self, kind: &ChangeKind, index: usize) -> Option<&Span> {
+        self.entry_spans
+            .0
+            .iter()
+            .filter(|(k, _)| k == kind)
+            .nth(index)
+            .map(|(_, span)| span)
+    }
+
     /// Get compare link for this release.
+    ///
+    /// The release is compared against the previous dated release which isn't a draft, yanked
+    /// releases are skipped if [`FormatOptions::skip_yanked`](crate::FormatOptions) is set.
+    /// [`Release::compare_base`] overrides the previous release.
     pub fn compare_link(&self, changelog: &Changelog) -> Result<Option<Link>> {
         let index = changelog
             .releases()
@@ -84,8 +335,9 @@ impl Release {
         let mut offset = 1_usize;
         let mut previous = changelog.releases().get(index + offset);
 
+        let skip_yanked = changelog.format().skip_yanked;
         while let Some(prv) = previous {
-            if prv.date().is_some() {
+            if prv.date().is_some() && !prv.draft && !(skip_yanked && prv.yanked) {
                 break;
             }
 
@@ -93,17 +345,191 @@ impl Release {
             previous = changelog.releases().get(index + offset);
         }
 
-        if previous.is_none() && (self.date.is_none() || self.version.is_none()) {
+        let unreleased = self.date.is_none() || self.version.is_none();
+        if previous.is_none() && unreleased && self.compare_base.is_none() {
             return Ok(None);
         }
 
         changelog.compare_link(self, previous)
     }
 
+    /// Rendered release without the heading, used as release notes of hosted releases
+    #[cfg(any(feature = "github", feature = "gitlab"))]
+    pub(crate) fn notes(&self) -> String {
+        let rendered = self.to_string();
+        let notes = rendered.split_once('\n').map_or("", |(_, notes)| notes);
+        notes.trim().to_string()
+    }
+
+    /// Get breaking changes of this release, see [`ChangeEntry::breaking`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, Release};
+    ///
+    /// let changes = Changes::builder()
+    ///     .breaking("Drop support for Rust 1.60")
+    ///     .changed("**BREAKING** Rename `parse` to `from_str`")
+    ///     .fixed("Bug fix")
+    ///     .build()
+    ///     .unwrap();
+    /// let release = Release::builder().changes(changes).build().unwrap();
+    ///
+    /// assert_eq!(release.breaking_changes().len(), 2);
+    /// ```
+    pub fn breaking_changes(&self) -> Vec<ChangeEntry> {
+        self.changes.entries().filter(|e| *e.breaking()).collect()
+    }
+
+    /// Whether the release only says "No notable changes.", e.g. a maintenance release
+    ///
+    /// Such releases have no sections and aren't reported as empty by
+    /// [`Changelog::assert_releasable`](crate::Changelog::assert_releasable).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n\n## [0.1.1] - 2024-05-01\n\nNo notable changes.\n\
+    ///     \n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// assert!(changelog.releases()[0].is_empty_release());
+    /// assert!(!changelog.releases()[1].is_empty_release());
+    /// assert!(changelog
+    ///     .to_string()
+    ///     .contains("## [0.1.1] - 2024-05-01\n\nNo notable changes.\n\n## [0.1.0]"));
+    /// ```
+    pub fn is_empty_release(&self) -> bool {
+        let normalize = |text: &str| text.trim().trim_end_matches('.').to_lowercase();
+        self.changes.is_empty()
+            && self
+                .description
+                .as_deref()
+                .is_some_and(|description| normalize(description) == normalize(NO_NOTABLE_CHANGES))
+    }
+
+    /// Replace the changes and the description with "No notable changes.", see
+    /// [`Release::is_empty_release`]
+    pub fn set_no_notable_changes(&mut self) -> &mut Self {
+        self.empty_changes()
+            .set_description(NO_NOTABLE_CHANGES.to_string())
+    }
+
+    /// Merge another release into this one
+    ///
+    /// Changes of `other` missing in this release are appended to their sections, descriptions
+    /// are concatenated and the earlier date is kept. Version and yanked flag of this release
+    /// are preserved, contributors and metadata keys missing in this release are copied from
+    /// `other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{CalendarDate, Changes, Date, Release, Version};
+    ///
+    /// let mut release = Release::builder()
+    ///     .version(Version::parse("1.0.0").unwrap())
+    ///     .date(Date::new(2024, 6, 2).unwrap())
+    ///     .changes(Changes::builder().added("New feature").build().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// let hotfix = Release::builder()
+    ///     .version(Version::parse("1.0.1").unwrap())
+    ///     .date(Date::new(2024, 6, 1).unwrap())
+    ///     .changes(Changes::builder().added("New feature").fixed("Bug fix").build().unwrap())
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// release.merge(hotfix);
+    /// assert_eq!(release.version().as_ref().unwrap().to_string(), "1.0.0");
+    /// assert_eq!(release.date().unwrap().to_string(), "2024-06-01");
+    /// assert_eq!(release.changes().iter().count(), 2);
+    /// ```
+    pub fn merge(&mut self, other: Release) -> &mut Self {
+        for (kind, change) in other.changes.iter() {
+            if !self.changes.iter().any(|(k, c)| k == kind && c == change) {
+                self.changes.add(kind, change.clone());
+            }
+        }
+
+        let date = match (self.date, other.date) {
+            (Some(date), Some(other_date)) if other_date < date => other.release_date(),
+            (None, _) => other.release_date(),
+            _ => self.release_date(),
+        };
+        self.set_release_date(date);
+
+        self.description = match (self.description.take(), other.description) {
+            (Some(description), Some(other)) => Some(format!(
+                "{}\n\n{}",
+                description.trim_end(),
+                other.trim_start()
+            )),
+            (description, other) => description.or(other),
+        };
+
+        for contributor in other.contributors {
+            self.add_contributor(contributor);
+        }
+
+        for (key, value) in other.metadata {
+            self.metadata.entry(key).or_insert(value);
+        }
+
+        self.compare_base = self.compare_base.take().or(other.compare_base);
+
+        self
+    }
+
+    /// Credit a contributor, contributors which are already credited are ignored
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{CalendarDate, Date, Release, Version};
+    ///
+    /// let mut release = Release::builder()
+    ///     .version(Version::parse("1.0.0").unwrap())
+    ///     .date(Date::new(2024, 6, 1).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// release
+    ///     .fixed("Bug fix".to_string())
+    ///     .add_contributor("@octocat")
+    ///     .add_contributor("@octocat");
+    ///
+    /// assert_eq!(release.contributors(), &["@octocat"]);
+    /// assert!(release.to_string().ends_with("### Contributors\n\n- @octocat\n\n"));
+    /// ```
+    pub fn add_contributor(&mut self, name: impl Into<String>) -> &mut Self {
+        let name = name.into();
+        if !self.contributors.contains(&name) {
+            self.contributors.push(name);
+        }
+        self
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut BTreeMap<String, String> {
+        &mut self.metadata
+    }
+
     pub fn empty_changes(&mut self) -> &mut Self {
         self.set_changes(Changes::default())
     }
 
+    pub fn add(&mut self, kind: ChangeKind, change: String) -> &mut Self {
+        self.changes.add(kind, change);
+        self
+    }
+
+    pub fn breaking(&mut self, change: String) -> &mut Self {
+        self.changes.add(ChangeKind::Breaking, change);
+        self
+    }
+
     pub fn added(&mut self, change: String) -> &mut Self {
         self.changes.add(ChangeKind::Added, change);
         self
@@ -138,11 +564,20 @@ impl Release {
         self.compact = value;
         self
     }
+
+    pub(crate) fn set_format(&mut self, format: FormatOptions) -> &mut Self {
+        self.format = format;
+        self
+    }
 }
 
 impl Ord for Release {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.date.cmp(&other.date)
+        // Releases without a date aren't released yet, so they are the newest
+        let date = |release: &Self| (release.date.is_none(), release.date);
+        date(self)
+            .cmp(&date(other))
+            .then_with(|| self.version.cmp(&other.version))
     }
 }
 
@@ -156,16 +591,18 @@ impl Display for Release {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let yanked = if self.yanked { " [YANKED]" } else { "" };
 
+        let brackets = match self.format.heading_style {
+            HeadingStyle::Bracketed => true,
+            HeadingStyle::Plain => false,
+            HeadingStyle::Preserve => self.brackets.unwrap_or(true),
+        };
+        let (open, close) = if brackets { ("[", "]") } else { ("", "") };
+
         if let Some(version) = self.version.clone() {
-            let date = self
-                .date
-                .ok_or_eyre(format!("Missing date: {version}"))
-                .map_err(|_| std::fmt::Error)?
-                .format("%Y-%m-%d")
-                .to_string();
-            writeln!(f, "## [{version}] - {date}{yanked}")?;
+            let date = self.release_date();
+            writeln!(f, "## {open}{version}{close} - {date}{yanked}")?;
         } else {
-            writeln!(f, "## [Unreleased]")?;
+            writeln!(f, "## {open}Unreleased{close}")?;
         }
 
         if !self.compact {
@@ -178,9 +615,28 @@ impl Display for Release {
 
         if !self.changes.is_empty() {
             let mut changes = self.changes.clone(); // clone the changes so that we mutate if required = release.clone(); // clone the release so that we mutate if required
-            changes.set_compact(self.compact);
+            changes
+                .set_compact(self.compact)
+                .set_format(self.format.clone());
             write!(f, "{}", changes)?;
-        } else if self.compact {
+        } else if self.description.is_some() && !self.compact {
+            // Separate the description from the next heading
+            writeln!(f)?;
+        } else if self.compact && self.contributors.is_empty() {
+            writeln!(f)?;
+        }
+
+        if !self.contributors.is_empty() {
+            if !self.changes.is_empty() {
+                writeln!(f)?;
+            }
+            writeln!(f, "### {CONTRIBUTORS_HEADING}")?;
+            if !self.compact {
+                writeln!(f)?;
+            }
+            for contributor in &self.contributors {
+                writeln!(f, "- {contributor}")?;
+            }
             writeln!(f)?;
         }
 
@@ -0,0 +1,120 @@
+//! Release dates of varying precision, see [`ReleaseDate`].
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use eyre::{bail, Error, Result};
+
+use crate::{consts::TBD_DATE, CalendarDate, Date};
+
+/// Release date as written in the release heading
+///
+/// Some older changelogs only record the month of a release, e.g. `## [0.3.0] - 2019-02`. Such
+/// dates are kept as [`ReleaseDate::Month`] and written back the same way, while
+/// [`Release::date`](crate::Release::date) is the first day of the month, so the release is
+/// still sorted and validated by its date.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{CalendarDate, Date, ReleaseDate};
+///
+/// let date: ReleaseDate = "2019-02".parse().unwrap();
+/// assert_eq!(date, ReleaseDate::Month(2019, 2));
+/// assert_eq!(date.first_day(), Date::new(2019, 2, 1));
+/// assert_eq!(date.to_string(), "2019-02");
+///
+/// let date: ReleaseDate = "2024-06-01".parse().unwrap();
+/// assert_eq!(date.to_string(), "2024-06-01");
+/// assert!("2019-13".parse::<ReleaseDate>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ReleaseDate {
+    Day(Date),
+    /// Year and month of the release
+    Month(i32, u32),
+    /// Not dated, e.g. the Unreleased release or a planned `## [1.3.0] - TBD` one
+    #[default]
+    Unknown,
+}
+
+impl ReleaseDate {
+    /// The date, or the first day of the month for [`ReleaseDate::Month`]
+    pub fn first_day(&self) -> Option<Date> {
+        match *self {
+            ReleaseDate::Day(date) => Some(date),
+            ReleaseDate::Month(year, month) => Date::new(year, month, 1),
+            ReleaseDate::Unknown => None,
+        }
+    }
+
+    pub fn is_partial(&self) -> bool {
+        matches!(self, ReleaseDate::Month(..))
+    }
+}
+
+impl From<Date> for ReleaseDate {
+    fn from(date: Date) -> Self {
+        ReleaseDate::Day(date)
+    }
+}
+
+impl From<Option<Date>> for ReleaseDate {
+    fn from(date: Option<Date>) -> Self {
+        date.map_or(ReleaseDate::Unknown, ReleaseDate::Day)
+    }
+}
+
+impl FromStr for ReleaseDate {
+    type Err = Error;
+
+    /// Parse `YYYY-MM-DD` or `YYYY-MM` date
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case(TBD_DATE) {
+            return Ok(ReleaseDate::Unknown);
+        }
+        if let Some(date) = Date::parse_ymd(s) {
+            return Ok(ReleaseDate::Day(date));
+        }
+
+        let month = s
+            .split_once('-')
+            .filter(|(year, month)| year.len() == 4 && month.len() == 2)
+            .and_then(|(year, month)| Some((year.parse().ok()?, month.parse().ok()?)))
+            .filter(|&(year, month)| Date::new(year, month, 1).is_some());
+        match month {
+            Some((year, month)) => Ok(ReleaseDate::Month(year, month)),
+            None => bail!("Invalid release date: {s}"),
+        }
+    }
+}
+
+impl Display for ReleaseDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ReleaseDate::Day(date) => write!(f, "{date}"),
+            ReleaseDate::Month(year, month) => write!(f, "{year:04}-{month:02}"),
+            ReleaseDate::Unknown => f.write_str(TBD_DATE),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case::day("2024-06-01", Some(ReleaseDate::Day(Date::new(2024, 6, 1).unwrap())))]
+    #[case::month("2019-02", Some(ReleaseDate::Month(2019, 2)))]
+    #[case::tbd("tbd", Some(ReleaseDate::Unknown))]
+    #[case::short_month("2019-2", None)]
+    #[case::invalid_month("2019-13", None)]
+    #[case::invalid_day("2019-02-30", None)]
+    fn test_parse(#[case] date: &str, #[case] expected: Option<ReleaseDate>) {
+        assert_eq!(date.parse::<ReleaseDate>().ok(), expected);
+    }
+}
@@ -0,0 +1,169 @@
+use regex::Regex;
+
+use crate::{export::plain_text, ChangeKind, Changelog, Release, ReleaseVersion};
+
+/// Reference to a change entry matched by [`Changelog::find_entries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryRef<'a> {
+    /// Version of the release, `None` for the Unreleased release
+    pub version: Option<&'a ReleaseVersion>,
+    pub kind: ChangeKind,
+    /// Index of the entry within the changes of its kind
+    pub index: usize,
+    /// Whole entry text
+    pub entry: &'a str,
+    /// Text matched by the pattern
+    pub matched: &'a str,
+}
+
+impl Changelog {
+    /// Find change entries matching the pattern across all releases
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{ChangeKind, Changelog};
+    /// use regex::Regex;
+    ///
+    /// let markdown = "# Changelog\n## [0.1.1] - 2024-05-18\n### Fixed\n- Error readability\n\
+    ///     - Parsing anchor links (#42)\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let entries = changelog.find_entries(&Regex::new(r"#\d+").unwrap());
+    /// assert_eq!(entries.len(), 1);
+    /// assert_eq!(entries[0].version.unwrap().to_string(), "0.1.1");
+    /// assert_eq!(entries[0].kind, ChangeKind::Fixed);
+    /// assert_eq!(entries[0].index, 1);
+    /// assert_eq!(entries[0].matched, "#42");
+    /// ```
+    pub fn find_entries(&self, pattern: &Regex) -> Vec<EntryRef<'_>> {
+        self.releases()
+            .iter()
+            .flat_map(indexed_entries)
+            .filter_map(|(version, kind, index, entry)| {
+                pattern.find(entry).map(|matched| EntryRef {
+                    version,
+                    kind,
+                    index,
+                    entry,
+                    matched: matched.as_str(),
+                })
+            })
+            .collect()
+    }
+
+    /// Find pairs of duplicate entries in the first `window` releases, Unreleased included
+    ///
+    /// Entries are compared ignoring case, inline Markdown formatting, whitespace and a trailing
+    /// period, regardless of their kind. The `matched` text of the returned references is the
+    /// whole entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Fixed\n- Fix `parse` panic\n\
+    ///     \n## [0.1.1] - 2024-05-18\n### Fixed\n- fix parse panic.\n\
+    ///     \n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let duplicates = changelog.find_duplicate_entries(2);
+    /// assert_eq!(duplicates.len(), 1);
+    /// assert_eq!(duplicates[0].0.version, None);
+    /// assert_eq!(duplicates[0].1.version.unwrap().to_string(), "0.1.1");
+    /// ```
+    pub fn find_duplicate_entries(&self, window: usize) -> Vec<(EntryRef<'_>, EntryRef<'_>)> {
+        let entries = self
+            .releases()
+            .iter()
+            .take(window)
+            .flat_map(indexed_entries)
+            .map(|(version, kind, index, entry)| {
+                let entry_ref = EntryRef {
+                    version,
+                    kind,
+                    index,
+                    entry,
+                    matched: entry,
+                };
+                (normalize(entry), entry_ref)
+            })
+            .collect::<Vec<_>>();
+
+        let mut duplicates = vec![];
+        for (i, (normalized, first)) in entries.iter().enumerate() {
+            entries[i + 1..]
+                .iter()
+                .filter(|(other, _)| other == normalized)
+                .for_each(|(_, second)| duplicates.push((first.clone(), second.clone())));
+        }
+
+        duplicates
+    }
+}
+
+/// Entries of the release with their version, kind and index within the changes of the kind.
+fn indexed_entries(release: &Release) -> Vec<(Option<&ReleaseVersion>, ChangeKind, usize, &str)> {
+    release
+        .changes()
+        .sections()
+        .flat_map(|(kind, entries)| {
+            entries.iter().enumerate().map(move |(index, entry)| {
+                (
+                    release.version().as_ref(),
+                    kind.clone(),
+                    index,
+                    entry.as_str(),
+                )
+            })
+        })
+        .collect()
+}
+
+fn normalize(entry: &str) -> String {
+    plain_text(entry).trim_end_matches('.').to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_find_entries() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/early_changelog_multiple_sections.md")?;
+        let changelog = Changelog::parse(markdown, None)?;
+
+        let entries = changelog.find_entries(&Regex::new("(?i)bug fix")?);
+        assert_eq!(
+            entries
+                .iter()
+                .map(|e| (e.version, e.kind.clone(), e.index))
+                .collect::<Vec<_>>(),
+            vec![(None, ChangeKind::Fixed, 0), (None, ChangeKind::Fixed, 1)]
+        );
+
+        let entries = changelog.find_entries(&Regex::new("readability")?);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].version, Some(&ReleaseVersion::parse("0.1.1")?));
+        assert_eq!(entries[0].entry, "Error readability");
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_duplicate_entries() -> Result<()> {
+        let markdown = "# Changelog\n## [Unreleased]\n### Added\n- New **feature**\n- Docs\n\n## [0.1.1] - 2024-05-18\n### Changed\n- new feature.\n\n## [0.1.0] - 2024-04-28\n### Added\n- New feature\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        let duplicates = changelog.find_duplicate_entries(2);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].0.kind, ChangeKind::Added);
+        assert_eq!(duplicates[0].1.kind, ChangeKind::Changed);
+
+        assert_eq!(changelog.find_duplicate_entries(3).len(), 3);
+        assert!(changelog.find_duplicate_entries(1).is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+//! Machine-readable summary of the changelog embedded at the bottom of CHANGELOG.md, see
+//! [`FormatOptions::sidecar`](crate::FormatOptions).
+use eyre::{bail, Result};
+use serde_json::{json, Value};
+
+use crate::{model::v1, utils::fnv1a, Changelog};
+
+const SIDECAR_PREFIX: &str = "<!-- kac-sidecar ";
+const SIDECAR_SUFFIX: &str = " -->";
+const SIDECAR_VERSION: u64 = 2;
+
+/// Render the sidecar comment for the CHANGELOG.md `contents` it is appended to.
+pub(crate) fn render(changelog: &Changelog, contents: &str) -> String {
+    let sidecar = json!({
+        "version": SIDECAR_VERSION,
+        "checksum": checksum(contents),
+        "releases": releases(changelog),
+    });
+
+    // `-->` inside of strings would terminate the comment
+    let sidecar = sidecar.to_string().replace("-->", "--\\u003e");
+    format!("{SIDECAR_PREFIX}{sidecar}{SIDECAR_SUFFIX}\n")
+}
+
+/// Split CHANGELOG.md into the Markdown contents and the sidecar, if present.
+pub(crate) fn split(markdown: &str) -> (&str, Option<&str>) {
+    let Some(start) = markdown.rfind(&format!("\n{SIDECAR_PREFIX}")) else {
+        return (markdown, None);
+    };
+
+    let (contents, sidecar) = markdown.split_at(start + 1);
+    let sidecar = sidecar
+        .trim_end()
+        .strip_prefix(SIDECAR_PREFIX)
+        .and_then(|sidecar| sidecar.strip_suffix(SIDECAR_SUFFIX));
+
+    match sidecar {
+        Some(sidecar) => (contents, Some(sidecar)),
+        None => (markdown, None),
+    }
+}
+
+/// Check that the Markdown `contents` weren't modified after the sidecar was written, warns
+/// about manual edits otherwise.
+pub(crate) fn verify(contents: &str, sidecar: &str) -> Result<bool> {
+    let sidecar: Value = serde_json::from_str(sidecar)?;
+    let Some(expected) = sidecar["checksum"].as_str() else {
+        bail!("Missing checksum");
+    };
+
+    let matches = expected == checksum(contents);
+    if !matches {
+        log::warn!("Changelog sidecar checksum mismatch, CHANGELOG.md was modified manually");
+    }
+    Ok(matches)
+}
+
+fn checksum(contents: &str) -> String {
+    format!("{:016x}", fnv1a(contents))
+}
+
+/// Summary of the releases, written from the stable [`v1`] model so it doesn't change with the
+/// crate types
+fn releases(changelog: &Changelog) -> Value {
+    v1::Changelog::from(changelog)
+        .releases
+        .into_iter()
+        .map(|release| {
+            let sections = release
+                .sections
+                .into_iter()
+                .map(|section| json!({ "kind": section.kind, "entries": section.entries }))
+                .collect::<Vec<_>>();
+            json!({
+                "version": release.version,
+                "date": release.date,
+                "yanked": release.yanked,
+                "sections": sections,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FormatOptions;
+
+    #[test]
+    fn test_sidecar() -> Result<()> {
+        let mut changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+        changelog.set_format_options(FormatOptions {
+            sidecar: true,
+            ..Default::default()
+        });
+
+        let contents = changelog.file_contents();
+        assert!(contents.contains("\n<!-- kac-sidecar {\""));
+
+        let (markdown, sidecar) = split(&contents);
+        let sidecar: Value = serde_json::from_str(sidecar.unwrap())?;
+        assert_eq!(sidecar["releases"][1]["version"], "0.1.2");
+        assert!(verify(markdown, &sidecar.to_string())?);
+
+        let parsed = Changelog::parse(contents.clone(), None)?;
+        assert_eq!(parsed.file_contents(), contents);
+        assert!(parsed.releases()[0].span().is_some());
+
+        let tampered = contents.replace("- Error readability", "- Better error readability");
+        let (markdown, sidecar) = split(&tampered);
+        assert!(!verify(markdown, sidecar.unwrap())?);
+
+        let changelog = Changelog::parse(tampered, None)?;
+        assert!(changelog.format().sidecar);
+        assert!(changelog
+            .file_contents()
+            .contains("- Better error readability"));
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+//! Locations of parsed elements in the CHANGELOG.md source.
+use std::ops::{Range, RangeInclusive};
+
+/// Location of a parsed element in the CHANGELOG.md source.
+///
+/// Spans are recorded while parsing and aren't updated when the changelog is modified. Elements
+/// of included changelogs refer to the included files.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// Line numbers of the first and the last line, starting from 1
+    pub lines: RangeInclusive<usize>,
+    /// Byte range from the start of the first line to the end of the last line, without the
+    /// line break
+    pub bytes: Range<usize>,
+}
+
+/// Value which is ignored when comparing, so that the same content parsed from different
+/// sources is equal.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Unspanned<T>(pub(crate) T);
+
+impl<T> PartialEq for Unspanned<T> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> Eq for Unspanned<T> {}
+
+impl Span {
+    /// Span moved by `lines` and `bytes`, e.g. from a parsed region to the whole source
+    pub(crate) fn shifted(&self, lines: isize, bytes: isize) -> Span {
+        let line = |line: usize| line.saturating_add_signed(lines);
+        let byte = |byte: usize| byte.saturating_add_signed(bytes);
+
+        Span {
+            lines: line(*self.lines.start())..=line(*self.lines.end()),
+            bytes: byte(self.bytes.start)..byte(self.bytes.end),
+        }
+    }
+}
+
+/// Maps token line numbers, which start from the first non-blank line, to spans of the source.
+#[derive(Debug)]
+pub(crate) struct LineIndex {
+    /// Lines skipped before the first non-blank line
+    skipped: usize,
+    /// Byte ranges of the lines
+    lines: Vec<Range<usize>>,
+}
+
+impl LineIndex {
+    pub(crate) fn new(source: &str) -> Self {
+        let leading = &source[..source.len() - source.trim_start().len()];
+        let skipped = leading.matches('\n').count();
+
+        let mut offset = 0;
+        let lines = source
+            .split('\n')
+            .map(|line| {
+                let start = offset;
+                offset += line.len() + 1;
+                start..start + line.trim_end_matches('\r').len()
+            })
+            .collect();
+
+        Self { skipped, lines }
+    }
+
+    /// Span of the token lines `start` to `end`
+    pub(crate) fn span(&self, start: usize, end: usize) -> Option<Span> {
+        let first = self.lines.get(start + self.skipped - 1)?;
+        let last = self.lines.get(end.max(start) + self.skipped - 1)?;
+
+        Some(Span {
+            lines: start + self.skipped..=end.max(start) + self.skipped,
+            bytes: first.start..last.end,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use crate::{ChangeKind, Changelog};
+
+    #[test]
+    fn test_spans() -> Result<()> {
+        let markdown = "\n\n# Changelog\n\n## [0.1.1] - 2024-05-01\n\n### Fixed\n\n- First fix\n- Second fix\n  spanning two lines\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n\n[docs]: https://example.com/docs\n  \"Documentation\"\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+        let text = |span: Option<&super::Span>| &markdown[span.unwrap().bytes.clone()];
+
+        let release = &changelog.releases()[0];
+        assert_eq!(release.span().unwrap().lines, 5..=11);
+        assert!(text(release.span()).starts_with("## [0.1.1] - 2024-05-01"));
+        assert!(text(release.span()).ends_with("spanning two lines"));
+        assert_eq!(
+            text(release.entry_span(&ChangeKind::Fixed, 1)),
+            "- Second fix\n  spanning two lines"
+        );
+        assert!(release.entry_span(&ChangeKind::Fixed, 2).is_none());
+
+        let link = changelog.links().first().unwrap();
+        assert_eq!(link.span().unwrap().lines, 19..=20);
+        assert_eq!(
+            text(link.span()),
+            "[docs]: https://example.com/docs\n  \"Documentation\""
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,320 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    sync::OnceLock,
+};
+
+use regex::Regex;
+
+use crate::{validation::Violation, Changelog};
+
+/// Common non-imperative verb forms and their imperative counterparts.
+const IMPERATIVE_VERBS: &[(&str, &str)] = &[
+    ("added", "Add"),
+    ("adds", "Add"),
+    ("allowed", "Allow"),
+    ("allows", "Allow"),
+    ("bumped", "Bump"),
+    ("bumps", "Bump"),
+    ("changed", "Change"),
+    ("changes", "Change"),
+    ("deprecated", "Deprecate"),
+    ("deprecates", "Deprecate"),
+    ("dropped", "Drop"),
+    ("drops", "Drop"),
+    ("fixed", "Fix"),
+    ("fixes", "Fix"),
+    ("implemented", "Implement"),
+    ("implements", "Implement"),
+    ("improved", "Improve"),
+    ("improves", "Improve"),
+    ("introduced", "Introduce"),
+    ("introduces", "Introduce"),
+    ("made", "Make"),
+    ("makes", "Make"),
+    ("moved", "Move"),
+    ("moves", "Move"),
+    ("refactored", "Refactor"),
+    ("refactors", "Refactor"),
+    ("removed", "Remove"),
+    ("removes", "Remove"),
+    ("renamed", "Rename"),
+    ("renames", "Rename"),
+    ("replaced", "Replace"),
+    ("replaces", "Replace"),
+    ("supported", "Support"),
+    ("supports", "Support"),
+    ("updated", "Update"),
+    ("updates", "Update"),
+];
+
+/// Style rules for change entries, used by [`Changelog::check_style`] and
+/// [`Changelog::fix_style`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StylePolicy {
+    /// Entry must start with a capital letter
+    pub capitalize: bool,
+    /// Entry must not end with a period
+    pub no_trailing_period: bool,
+    /// Entry must start with a verb in imperative mood, e.g. "Add" instead of "Added"
+    pub imperative_mood: bool,
+    /// Maximum entry length in characters
+    pub max_length: Option<usize>,
+}
+
+impl Default for StylePolicy {
+    fn default() -> Self {
+        Self {
+            capitalize: true,
+            no_trailing_period: true,
+            imperative_mood: false,
+            max_length: None,
+        }
+    }
+}
+
+/// Style rule broken by an entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StyleRule {
+    Capitalize,
+    TrailingPeriod,
+    /// Non-imperative verb with the suggested replacement
+    ImperativeMood {
+        found: String,
+        suggested: String,
+    },
+    MaxLength {
+        length: usize,
+        max: usize,
+    },
+}
+
+impl Display for StyleRule {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            StyleRule::Capitalize => write!(f, "should start with a capital letter"),
+            StyleRule::TrailingPeriod => write!(f, "should not end with a period"),
+            StyleRule::ImperativeMood { found, suggested } => {
+                write!(
+                    f,
+                    "should use imperative mood, \"{suggested}\" instead of \"{found}\""
+                )
+            }
+            StyleRule::MaxLength { length, max } => {
+                write!(f, "is {length} characters long, maximum is {max}")
+            }
+        }
+    }
+}
+
+impl StylePolicy {
+    /// Check entry text against the policy.
+    pub fn check(&self, text: &str) -> Vec<StyleRule> {
+        let (_, body) = split_prefix(text);
+        let mut rules = vec![];
+
+        if self.capitalize && body.starts_with(|c: char| c.is_lowercase()) {
+            rules.push(StyleRule::Capitalize);
+        }
+
+        if self.no_trailing_period && has_trailing_period(body) {
+            rules.push(StyleRule::TrailingPeriod);
+        }
+
+        if self.imperative_mood {
+            if let Some((found, suggested)) = non_imperative_verb(body) {
+                rules.push(StyleRule::ImperativeMood {
+                    found: found.to_string(),
+                    suggested: suggested.to_string(),
+                });
+            }
+        }
+
+        if let Some(max) = self.max_length {
+            let length = text.chars().count();
+            if length > max {
+                rules.push(StyleRule::MaxLength { length, max });
+            }
+        }
+
+        rules
+    }
+
+    /// Fix entry text according to the policy, the maximum length is not fixable.
+    pub fn fix(&self, text: &str) -> String {
+        let (prefix, body) = split_prefix(text);
+        let mut body = body.to_string();
+
+        if self.imperative_mood {
+            if let Some((found, suggested)) = non_imperative_verb(&body) {
+                body = format!("{suggested}{}", &body[found.len()..]);
+            }
+        }
+
+        if self.capitalize {
+            let mut chars = body.chars();
+            if let Some(first) = chars.next().filter(|c| c.is_lowercase()) {
+                body = first.to_uppercase().chain(chars).collect();
+            }
+        }
+
+        if self.no_trailing_period && has_trailing_period(&body) {
+            body = body.trim_end().trim_end_matches('.').to_string();
+        }
+
+        format!("{prefix}{body}")
+    }
+}
+
+impl Changelog {
+    /// Check all entries against the style policy
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{style::StylePolicy, Changelog};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Fixed\n- fixed the parser.\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let policy = StylePolicy {
+    ///     imperative_mood: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(changelog.check_style(&policy).len(), 3);
+    /// ```
+    pub fn check_style(&self, policy: &StylePolicy) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for release in self.releases() {
+            for (kind, entries) in release.changes().sections() {
+                for (index, entry) in entries.iter().enumerate() {
+                    violations.extend(policy.check(entry).into_iter().map(|rule| {
+                        Violation::Style {
+                            version: release.version().clone(),
+                            kind: kind.clone(),
+                            index,
+                            rule,
+                        }
+                    }));
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Apply automatic style corrections to all entries
+    ///
+    /// Returns the number of changed entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{style::StylePolicy, Changelog};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Fixed\n- **parser:** fixed the parser.\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let policy = StylePolicy {
+    ///     imperative_mood: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(changelog.fix_style(&policy), 1);
+    /// assert!(changelog.to_string().contains("- **parser:** Fix the parser\n"));
+    /// ```
+    pub fn fix_style(&mut self, policy: &StylePolicy) -> usize {
+        let mut fixed = 0;
+
+        for release in self.releases_mut() {
+            let mut changes = release.changes().clone();
+
+            changes.iter_mut().for_each(|(_, entry)| {
+                let text = policy.fix(entry);
+                if text != *entry {
+                    *entry = text;
+                    fixed += 1;
+                }
+            });
+
+            release.set_changes(changes);
+        }
+
+        fixed
+    }
+}
+
+/// Split entry into `**BREAKING**` and `**scope:**` prefixes and the rest of the text.
+fn split_prefix(text: &str) -> (&str, &str) {
+    static PREFIX: OnceLock<Regex> = OnceLock::new();
+    let prefix = PREFIX
+        .get_or_init(|| Regex::new(r"^\s*(\*\*BREAKING\*\*:?\s*)?(\*\*[^*:]+:\*\*\s*)?").unwrap());
+
+    let end = prefix.find(text).map_or(0, |m| m.end());
+    text.split_at(end)
+}
+
+fn has_trailing_period(text: &str) -> bool {
+    let text = text.trim_end();
+    text.ends_with('.') && !text.ends_with("..")
+}
+
+fn non_imperative_verb(text: &str) -> Option<(&str, &'static str)> {
+    let word = text.split(|c: char| !c.is_alphabetic()).next()?;
+    let lower = word.to_lowercase();
+
+    IMPERATIVE_VERBS
+        .iter()
+        .find(|(verb, _)| *verb == lower)
+        .map(|(_, imperative)| (word, *imperative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        let policy = StylePolicy {
+            imperative_mood: true,
+            max_length: Some(35),
+            ..Default::default()
+        };
+
+        assert!(policy.check("Add `parse` method").is_empty());
+        assert!(policy
+            .check("**BREAKING** **cli:** Drop `--old`")
+            .is_empty());
+        assert!(policy.check("Wait for it...").is_empty());
+        assert_eq!(
+            policy.check("**cli:** added flags to the command."),
+            vec![
+                StyleRule::Capitalize,
+                StyleRule::TrailingPeriod,
+                StyleRule::ImperativeMood {
+                    found: "added".to_string(),
+                    suggested: "Add".to_string(),
+                },
+                StyleRule::MaxLength {
+                    length: 36,
+                    max: 35
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fix() {
+        let policy = StylePolicy {
+            imperative_mood: true,
+            ..Default::default()
+        };
+
+        assert_eq!(policy.fix("updates deps."), "Update deps");
+        assert_eq!(
+            policy.fix("**BREAKING** removed API."),
+            "**BREAKING** Remove API"
+        );
+        assert_eq!(policy.fix("`parse` is faster"), "`parse` is faster");
+        assert_eq!(policy.fix("Error readability"), "Error readability");
+    }
+}
@@ -0,0 +1,146 @@
+//! Short digests of releases, e.g. for social posts and app store release notes, see
+//! [`Release::summary`].
+use crate::{ChangeEntry, ChangeKind, Release};
+
+/// Order of the sections in a digest, breaking entries of any section come first.
+const PRIORITY: [ChangeKind; 7] = [
+    ChangeKind::Breaking,
+    ChangeKind::Security,
+    ChangeKind::Added,
+    ChangeKind::Fixed,
+    ChangeKind::Changed,
+    ChangeKind::Deprecated,
+    ChangeKind::Removed,
+];
+
+/// Produces a digest of a release with at most `max_entries` entries.
+///
+/// Implemented for closures, so the digest can be written by an external tool, e.g. a
+/// language model or a length-limited template.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{summary::Summarizer, Changes, Release};
+///
+/// let release = Release::builder()
+///     .changes(Changes::builder().added("New feature").fixed("Bug fix").build().unwrap())
+///     .build()
+///     .unwrap();
+///
+/// let count = |release: &Release, _: usize| format!("{} changes", release.changes().iter().count());
+/// assert_eq!(release.summary_with(&count, 1), "2 changes");
+/// ```
+pub trait Summarizer: Send + Sync {
+    /// Digest of the `release` with at most `max_entries` entries
+    fn summarize(&self, release: &Release, max_entries: usize) -> String;
+}
+
+impl<F> Summarizer for F
+where
+    F: Fn(&Release, usize) -> String + Send + Sync,
+{
+    fn summarize(&self, release: &Release, max_entries: usize) -> String {
+        self(release, max_entries)
+    }
+}
+
+/// Default [`Summarizer`]: the first lines of the top entries by section priority, one per line,
+/// followed by `+ N more` if some entries are left out.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DigestSummarizer;
+
+impl Summarizer for DigestSummarizer {
+    fn summarize(&self, release: &Release, max_entries: usize) -> String {
+        let entries = prioritized(release);
+        let mut lines = entries
+            .iter()
+            .take(max_entries)
+            .map(|entry| {
+                format!(
+                    "- {}",
+                    entry.text().lines().next().unwrap_or_default().trim()
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let more = entries.len().saturating_sub(max_entries);
+        if more > 0 {
+            lines.push(format!("+ {more} more"));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Entries of the release, breaking first and then by [`PRIORITY`] of their sections
+fn prioritized(release: &Release) -> Vec<ChangeEntry> {
+    let mut entries = release.changes().entries().collect::<Vec<_>>();
+    entries.sort_by_key(|entry| {
+        let section = PRIORITY.iter().position(|kind| kind == entry.kind());
+        (!*entry.breaking(), section)
+    });
+    entries
+}
+
+impl Release {
+    /// Short digest of the release with at most `max_entries` entries, see [`DigestSummarizer`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changes, Release};
+    ///
+    /// let changes = Changes::builder()
+    ///     .fixed("Crash on empty lists")
+    ///     .changed("Faster parsing")
+    ///     .added("Dark mode")
+    ///     .changed("**BREAKING** Drop the v1 API")
+    ///     .build()
+    ///     .unwrap();
+    /// let release = Release::builder().changes(changes).build().unwrap();
+    ///
+    /// assert_eq!(
+    ///     release.summary(2),
+    ///     "- **BREAKING** Drop the v1 API\n- Dark mode\n+ 2 more"
+    /// );
+    /// ```
+    pub fn summary(&self, max_entries: usize) -> String {
+        self.summary_with(&DigestSummarizer, max_entries)
+    }
+
+    /// Digest of the release produced by the `summarizer`
+    pub fn summary_with(&self, summarizer: &dyn Summarizer, max_entries: usize) -> String {
+        summarizer.summarize(self, max_entries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::Changes;
+
+    #[rstest]
+    #[case::all(
+        5,
+        "- Escape links\n- Dark mode\n- Crash on empty lists\n- Faster parsing"
+    )]
+    #[case::top(1, "- Escape links\n+ 3 more")]
+    #[case::none(0, "+ 4 more")]
+    fn test_summary(#[case] max_entries: usize, #[case] expected: &str) {
+        let changes = Changes::builder()
+            .changed("Faster parsing")
+            .fixed("Crash on empty lists\n  - Reported in #12")
+            .added("Dark mode")
+            .security("Escape links")
+            .build()
+            .unwrap();
+        let release = Release::builder().changes(changes).build().unwrap();
+
+        assert_eq!(release.summary(max_entries), expected);
+        let empty = Release::builder().build().unwrap();
+        assert_eq!(empty.summary(max_entries), "");
+    }
+}
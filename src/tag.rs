@@ -0,0 +1,44 @@
+//! Git tag names of releases, used for generating release and compare links.
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use crate::ReleaseVersion;
+
+/// Resolves the git tag name of a release version.
+///
+/// Implemented for closures, so tags which can't be described by a prefix, e.g.
+/// `crate-name-v1.2.3-final`, can be resolved as well.
+///
+/// # Examples
+///
+/// ```
+/// use keep_a_changelog::{tag::TagNameResolver, ReleaseVersion};
+///
+/// let resolver = |version: &ReleaseVersion| format!("release-{version}");
+/// assert_eq!(resolver.tag_name(&"1.2.3".parse().unwrap()), "release-1.2.3");
+/// ```
+pub trait TagNameResolver: Send + Sync {
+    /// Tag name of the release `version`
+    fn tag_name(&self, version: &ReleaseVersion) -> String;
+}
+
+impl<F> TagNameResolver for F
+where
+    F: Fn(&ReleaseVersion) -> String + Send + Sync,
+{
+    fn tag_name(&self, version: &ReleaseVersion) -> String {
+        self(version)
+    }
+}
+
+/// Shared [`TagNameResolver`], so that the changelog stays cloneable.
+#[derive(Clone)]
+pub(crate) struct SharedResolver(pub(crate) Arc<dyn TagNameResolver>);
+
+impl Debug for SharedResolver {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("SharedResolver(..)")
+    }
+}
@@ -0,0 +1,194 @@
+//! Template-based rendering of changelogs.
+//!
+//! Templates use the [Handlebars](https://handlebarsjs.com) syntax and are rendered without
+//! HTML escaping, since the output is usually Markdown.
+//!
+//! The document template receives the following context:
+//!
+//! - `title`, `description`, `url`, `head`, `tag_prefix`, `footer` - changelog metadata;
+//! - `releases` - list of releases, see below;
+//! - `links` - list of `{ anchor, url }` objects, including generated release links.
+//!
+//! Every release (and the release template) receives:
+//!
+//! - `version`, `date`, `yanked`, `unreleased`, `description` - release metadata;
+//! - `anchor` - the anchor used for the release link, e.g. "1.2.3" or "Unreleased";
+//! - `link` - release link `{ anchor, url }` if the repository URL is known;
+//! - `sections` - list of `{ kind, entries }` objects, in the rendering order;
+//! - `rendered` - the output of the release template, if one is provided.
+use eyre::{eyre, Result};
+use handlebars::Handlebars;
+use serde_json::{json, Value};
+
+use crate::{Changelog, Release};
+
+const DOCUMENT_TEMPLATE: &str = "document";
+const RELEASE_TEMPLATE: &str = "release";
+
+/// Templates used by [`Changelog::render_with_template`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangelogTemplate {
+    /// Template of the whole document
+    pub document: String,
+    /// Optional template of a single release, its output is available as `rendered` on every
+    /// release in the document template
+    pub release: Option<String>,
+}
+
+impl ChangelogTemplate {
+    pub fn new<S: Into<String>>(document: S) -> Self {
+        Self {
+            document: document.into(),
+            release: None,
+        }
+    }
+
+    pub fn with_release<S: Into<String>>(mut self, release: S) -> Self {
+        self.release = Some(release.into());
+        self
+    }
+}
+
+impl Changelog {
+    /// Render the changelog using user supplied templates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{templates::ChangelogTemplate, Changelog};
+    ///
+    /// let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let template = ChangelogTemplate::new("{{#each releases}}{{rendered}}{{/each}}")
+    ///     .with_release(
+    ///         "{{version}} ({{date}})\n{{#each sections}}{{#each entries}}* {{this}}\n{{/each}}{{/each}}",
+    ///     );
+    ///
+    /// let output = changelog.render_with_template(&template).unwrap();
+    /// assert_eq!(output, "0.1.0 (2024-04-28)\n* Initial release\n");
+    /// ```
+    pub fn render_with_template(&self, template: &ChangelogTemplate) -> Result<String> {
+        let mut registry = Handlebars::new();
+        registry.register_escape_fn(handlebars::no_escape);
+        registry
+            .register_template_string(DOCUMENT_TEMPLATE, &template.document)
+            .map_err(|e| eyre!("Failed to parse document template: {e}"))?;
+
+        if let Some(release) = &template.release {
+            registry
+                .register_template_string(RELEASE_TEMPLATE, release)
+                .map_err(|e| eyre!("Failed to parse release template: {e}"))?;
+        }
+
+        let mut releases = vec![];
+        let mut links: Vec<Value> = self
+            .links()
+            .iter()
+            .map(|link| json!({ "anchor": link.anchor(), "url": link.url() }))
+            .collect();
+
+        for release in self.releases().iter().filter(|r| !r.draft()) {
+            let mut context = self.release_context(release)?;
+
+            if template.release.is_some() {
+                let rendered = registry
+                    .render(RELEASE_TEMPLATE, &context)
+                    .map_err(|e| eyre!("Failed to render release template: {e}"))?;
+                context["rendered"] = Value::String(rendered);
+            }
+
+            if !context["link"].is_null() {
+                links.retain(|link| link["anchor"] != context["link"]["anchor"]);
+                links.push(context["link"].clone());
+            }
+
+            releases.push(context);
+        }
+
+        let context = json!({
+            "title": self.title(),
+            "description": self.description(),
+            "url": self.url(),
+            "head": self.head(),
+            "tag_prefix": self.tag_prefix(),
+            "footer": self.footer(),
+            "releases": releases,
+            "links": links,
+        });
+
+        registry
+            .render(DOCUMENT_TEMPLATE, &context)
+            .map_err(|e| eyre!("Failed to render document template: {e}"))
+    }
+
+    fn release_context(&self, release: &Release) -> Result<Value> {
+        let unreleased = release.version().is_none() && release.date().is_none();
+
+        let sections = release
+            .changes()
+            .sections()
+            .map(|(kind, entries)| json!({ "kind": kind.heading(), "entries": entries }))
+            .collect::<Vec<_>>();
+
+        let link = match self.url() {
+            Some(_) => release
+                .compare_link(self)?
+                .map(|link| json!({ "anchor": link.anchor(), "url": link.url() })),
+            None => None,
+        };
+
+        let anchor = match release.version() {
+            Some(version) => version.to_string(),
+            None => "Unreleased".to_string(),
+        };
+
+        Ok(json!({
+            "version": release.version().as_ref().map(|v| v.to_string()),
+            "date": release.date().map(|d| d.to_string()),
+            "yanked": release.yanked(),
+            "unreleased": unreleased,
+            "description": release.description(),
+            "anchor": anchor,
+            "link": link,
+            "sections": sections,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_document_with_links() -> Result<()> {
+        let changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+
+        let template = ChangelogTemplate::new(
+            "{{title}}\n{{#each releases}}{{#if unreleased}}Next{{else}}{{version}}{{/if}}\n{{/each}}\
+            {{#each links}}{{anchor}} {{url}}\n{{/each}}",
+        );
+
+        let output = changelog.render_with_template(&template)?;
+
+        assert!(output.starts_with("Changelog\nNext\n0.1.2\n0.1.1\n0.1.0\n"));
+        assert!(output.contains(
+            "Unreleased https://github.com/napalmpapalam/keep-a-changelog-rs/compare/0.1.2...HEAD\n"
+        ));
+        assert!(output.ends_with(
+            "0.1.0 https://github.com/napalmpapalam/keep-a-changelog-rs/releases/tag/0.1.0\n"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_template() {
+        let changelog = crate::changelog::ChangelogBuilder::default()
+            .build()
+            .unwrap();
+        let template = ChangelogTemplate::new("{{#each releases}}");
+
+        assert!(changelog.render_with_template(&template).is_err());
+    }
+}
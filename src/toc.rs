@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use eyre::Result;
+
+use crate::{
+    consts::{CHANGELOG_TITLE, CONTRIBUTORS_HEADING, TOC_END_FLAG, TOC_FLAG},
+    placeholder::{Marker, RELEASE_DATE_SUFFIX},
+    utils::get_file_url,
+    CalendarDate, Changelog, Release,
+};
+
+impl Changelog {
+    /// Linked table of contents of the releases, for long changelogs browsed on forges
+    ///
+    /// Depth 1 lists the releases, depth 2 also lists their sections. Anchors are the heading
+    /// slugs generated by GitHub, duplicate headings get a numeric suffix, e.g. `#added-1`.
+    ///
+    /// Set [`FormatOptions::toc`](crate::FormatOptions::toc) to insert it after the description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let changelog = Changelog::parse_from_file("tests/data/early_changelog.md", None).unwrap();
+    /// let toc = changelog.toc(1);
+    ///
+    /// assert!(toc.starts_with("- [Unreleased](#unreleased)\n"));
+    /// assert!(toc.contains("- [0.1.0 - 2024-04-28](#010---2024-04-28)\n"));
+    /// ```
+    pub fn toc(&self, depth: usize) -> String {
+        self.render_toc(depth, false)
+    }
+
+    pub(crate) fn fmt_toc(&self, f: &mut fmt::Formatter, drafts: bool) -> fmt::Result {
+        let Some(depth) = self.format().toc else {
+            return Ok(());
+        };
+
+        let toc = self.render_toc(depth, drafts);
+        if *self.compact() || toc.is_empty() {
+            writeln!(f, "<!-- {TOC_FLAG} -->\n{toc}<!-- {TOC_END_FLAG} -->\n")
+        } else {
+            writeln!(f, "<!-- {TOC_FLAG} -->\n\n{toc}\n<!-- {TOC_END_FLAG} -->\n")
+        }
+    }
+
+    /// Headings which aren't listed are still slugged, so that suffixes of duplicates match the
+    /// anchors of the rendered document
+    fn render_toc(&self, depth: usize, drafts: bool) -> String {
+        let mut slugs = Slugs::default();
+        let mut toc = String::new();
+
+        let title = self.title().as_deref().unwrap_or(CHANGELOG_TITLE);
+        slugs.next(title);
+
+        let mut year = None;
+        for release in self.rendered_releases(drafts) {
+            if self.format().group_by_year {
+                let release_year = release.date().map(|date| date.ymd().0);
+                if release_year.is_some() && release_year != year {
+                    year = release_year;
+                    slugs.next(&release_year.unwrap_or_default().to_string());
+                }
+            }
+
+            let heading = self.toc_heading(release);
+            let slug = slugs.next(&heading);
+            if depth > 0 {
+                let _ = writeln!(toc, "- [{heading}](#{slug})");
+            }
+
+            let mut headings = release
+                .changes()
+                .sections()
+                .map(|(kind, _)| kind.heading())
+                .collect::<Vec<_>>();
+            if !release.contributors().is_empty() {
+                headings.push(CONTRIBUTORS_HEADING);
+            }
+            for heading in headings {
+                let slug = slugs.next(heading);
+                if depth > 1 {
+                    let _ = writeln!(toc, "  - [{heading}](#{slug})");
+                }
+            }
+        }
+
+        toc
+    }
+
+    /// Permalink to the section of the release `version` in the rendered CHANGELOG.md, e.g.
+    /// `https://github.com/owner/repo/blob/main/CHANGELOG.md#123---2024-06-01`
+    ///
+    /// `None` if the release isn't found or the changelog has no repository URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, ChangelogParseOptions};
+    ///
+    /// let opts = ChangelogParseOptions {
+    ///     url: Some("https://github.com/owner/repo".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let changelog =
+    ///     Changelog::parse_from_file("tests/data/early_changelog.md", Some(opts)).unwrap();
+    ///
+    /// assert_eq!(
+    ///     changelog.permalink("0.1.0".to_string()).unwrap().unwrap(),
+    ///     "https://github.com/owner/repo/blob/HEAD/CHANGELOG.md#010---2024-04-28"
+    /// );
+    /// ```
+    pub fn permalink(&self, version: String) -> Result<Option<String>> {
+        let Some(url) = self.url() else {
+            return Ok(None);
+        };
+
+        Ok(self.find_release(version)?.map(|release| {
+            let file_url = get_file_url(url, self.head(), "CHANGELOG.md");
+            format!("{file_url}#{}", release.heading_slug())
+        }))
+    }
+
+    /// Text of the rendered release heading, without link brackets
+    fn toc_heading(&self, release: &Release) -> String {
+        let mut heading = release.heading_text();
+        if release.version().is_none() && self.has_marker(Marker::ReleaseDate) {
+            heading.push_str(RELEASE_DATE_SUFFIX);
+        }
+        heading
+    }
+}
+
+impl Release {
+    /// Anchor slug of the release heading generated by GitHub and GitLab, e.g.
+    /// `123---2024-06-01` for `## [1.2.3] - 2024-06-01`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let changelog = Changelog::parse_from_file("tests/data/early_changelog.md", None).unwrap();
+    ///
+    /// assert_eq!(changelog.releases()[0].heading_slug(), "unreleased");
+    /// assert_eq!(changelog.releases()[1].heading_slug(), "012---2024-05-20");
+    /// ```
+    pub fn heading_slug(&self) -> String {
+        slug(&self.heading_text())
+    }
+
+    fn heading_text(&self) -> String {
+        match self.yanked() {
+            true => format!("{} [YANKED]", self.title()),
+            false => self.title(),
+        }
+    }
+}
+
+/// GitHub heading slugs, counting duplicates in document order
+#[derive(Default)]
+struct Slugs(HashMap<String, usize>);
+
+impl Slugs {
+    fn next(&mut self, heading: &str) -> String {
+        let slug = slug(heading);
+        let count = self.0.entry(slug.clone()).or_default();
+        *count += 1;
+
+        match *count {
+            1 => slug,
+            n => format!("{slug}-{}", n - 1),
+        }
+    }
+}
+
+/// Lowercase the heading, drop punctuation but `-` and `_`, and replace spaces with `-`
+fn slug(heading: &str) -> String {
+    heading
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+        .map(|c| if c == ' ' { '-' } else { c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ChangelogParseOptions, FormatOptions};
+
+    #[test]
+    fn test_toc() -> Result<()> {
+        let mut changelog =
+            Changelog::parse_from_file("tests/data/early_changelog_multiple_sections.md", None)?;
+        changelog.set_format_options(FormatOptions {
+            toc: Some(2),
+            ..Default::default()
+        });
+
+        let toc = changelog.toc(2);
+        assert!(toc.starts_with("- [Unreleased](#unreleased)\n  - [Added](#added)\n"));
+        assert!(toc.contains("- [0.1.2 - 2024-05-20](#012---2024-05-20)\n  - [Fixed](#fixed-1)\n"));
+
+        let contents = changelog.file_contents();
+        assert!(contents.contains(&format!("<!-- toc -->\n\n{toc}\n<!-- tocstop -->\n")));
+
+        let parsed = Changelog::parse(contents.clone(), Some(ChangelogParseOptions::default()))?;
+        assert_eq!(parsed.format().toc, Some(2));
+        assert_eq!(parsed.releases(), changelog.releases());
+        assert_eq!(parsed.file_contents(), contents);
+        Ok(())
+    }
+
+    #[test]
+    fn test_slug() {
+        assert_eq!(
+            slug("1.0.0 - 2024-01-01 [YANKED]"),
+            "100---2024-01-01-yanked"
+        );
+        assert_eq!(slug("Breaking Changes"), "breaking-changes");
+    }
+}
@@ -1,12 +1,9 @@
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display, sync::OnceLock};
 
 use eyre::Result;
 use regex::Regex;
 
-use crate::{
-    consts::*,
-    utils::{is_empty_str, is_empty_str_vec, substring},
-};
+use crate::consts::*;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum TokenKind {
@@ -39,15 +36,16 @@ impl Display for TokenKind {
     }
 }
 
+/// Token of the source, lines are borrowed unless they had to be rewritten
 #[derive(Debug, Clone, PartialEq)]
-pub struct Token {
+pub struct Token<'a> {
     pub line: usize,
     pub kind: TokenKind,
-    pub content: Vec<String>,
+    pub content: Vec<Cow<'a, str>>,
 }
 
-impl Token {
-    pub fn new(line: usize, kind: TokenKind, content: Vec<String>) -> Self {
+impl<'a> Token<'a> {
+    pub fn new(line: usize, kind: TokenKind, content: Vec<Cow<'a, str>>) -> Self {
         Self {
             line,
             kind,
@@ -56,150 +54,333 @@ impl Token {
     }
 }
 
-pub fn tokenize(markdown: String) -> Result<(bool, Vec<Token>)> {
-    let tokens: Vec<Token> = extract_tokens(markdown);
-    log::trace!("Tokens: {:#?}", tokens);
-
+pub fn tokenize(markdown: &str) -> Result<(bool, Vec<Token<'_>>)> {
     let mut compact = false;
-
-    for (idx, token) in tokens.iter().enumerate() {
-        if token.kind == TokenKind::H1 && !tokens[idx + 1].content[0].is_empty() {
-            compact = true;
-            break;
-        }
-    }
-
-    let regex = Regex::new(r"^\s\s")?;
+    let mut prev_kind = None;
     let mut result: Vec<Token> = vec![];
+    // width of the list marker and the spaces after it, see `list_item_indent`
+    let mut indent = 0;
 
-    for (idx, token) in tokens.into_iter().enumerate() {
+    for token in extract_tokens(markdown) {
         let Token {
             line,
             kind,
-            content,
+            mut content,
         } = token;
-        let content = content[0].clone();
 
-        if idx > 0 {
-            let prev_item_idx = result.len() - 1;
-            let prev_token_kind = result[prev_item_idx].kind.clone();
+        if prev_kind == Some(TokenKind::H1) && !content[0].is_empty() {
+            compact = true;
+        }
+        prev_kind = Some(kind.clone());
 
-            if kind == TokenKind::P {
-                if prev_token_kind == TokenKind::P {
-                    result[prev_item_idx].content.push(content);
+        if kind == TokenKind::P {
+            match result.last_mut() {
+                Some(prev) if prev.kind == TokenKind::P => {
+                    prev.content.append(&mut content);
                     continue;
                 }
-
-                if prev_token_kind == TokenKind::Li {
-                    result[prev_item_idx]
-                        .content
-                        .push(regex.replace(&content, "").to_string());
+                Some(prev) if prev.kind == TokenKind::Li => {
+                    // continuation lines of list items, also after blank lines of loose lists,
+                    // are indented up to the content of the item
+                    let content = match content.swap_remove(0) {
+                        Cow::Borrowed(line) => Cow::Borrowed(strip_indent(line, indent)),
+                        Cow::Owned(line) => Cow::Owned(strip_indent(&line, indent).to_string()),
+                    };
+                    prev.content.push(content);
                     continue;
                 }
+                _ => {}
             }
         }
 
-        result.push(Token::new(line, kind, vec![content]));
+        if kind == TokenKind::Li {
+            indent = list_item_indent(&content[0]);
+            content[0] = match &content[0] {
+                Cow::Borrowed(line) => Cow::Borrowed(line.trim()),
+                Cow::Owned(line) => Cow::Owned(line.trim().to_string()),
+            };
+        }
+
+        result.push(Token::new(line, kind, content));
     }
 
-    Ok((
-        compact,
-        result
-            .into_iter()
-            .filter(|t| !is_empty_str_vec(t.content.clone()))
-            .map(|mut token| {
-                while is_empty_str(token.content[token.content.len() - 1].clone()) {
-                    token.content.pop();
-                }
+    result.retain_mut(|token| {
+        let is_empty = |line: &Cow<str>| line.trim().is_empty();
+        let Some(last) = token.content.iter().rposition(|line| !is_empty(line)) else {
+            return false;
+        };
+        token.content.truncate(last + 1);
+
+        let first = token
+            .content
+            .iter()
+            .position(|line| !is_empty(line))
+            .unwrap_or(0);
+        token.content.drain(..first);
+        true
+    });
+
+    log::trace!("Tokens: {:#?}", result);
+    Ok((compact, result))
+}
 
-                while is_empty_str(token.content[0].clone()) {
-                    token.content.remove(0);
-                }
+/// Column width of the list item marker and the whitespace after it, continuation lines are
+/// indented by the same width, see CommonMark list items. Tabs stop at multiples of 4 columns.
+fn list_item_indent(content: &str) -> usize {
+    let mut column = 1;
+    for c in content.chars() {
+        match c {
+            ' ' => column += 1,
+            '\t' => column += 4 - column % 4,
+            _ => break,
+        }
+    }
 
-                token
-            })
-            .collect(),
-    ))
+    // empty items and items starting with an indented code block are followed by one space
+    match column - 1 {
+        1..=4 if !content.trim().is_empty() => column,
+        _ => 2,
+    }
 }
 
-fn extract_tokens(markdown: String) -> Vec<Token> {
-    let link_regex: Regex = Regex::new(r"^\[.*\]\:\s*http.*$").unwrap();
-    let link_ref_regex: Regex = Regex::new(r"^\[.*\]\:$").unwrap();
-    let comment_regex: Regex = Regex::new(r"^<!--(.*)-->$").unwrap();
-    let link_prefix_regex: Regex = Regex::new(r"\s+http.*$").unwrap();
+/// Line without up to `width` columns of indentation
+fn strip_indent(line: &str, width: usize) -> &str {
+    let mut column = 0;
+    for (idx, c) in line.char_indices() {
+        if column >= width {
+            return &line[idx..];
+        }
+        match c {
+            ' ' => column += 1,
+            '\t' => column += 4 - column % 4,
+            _ => return &line[idx..],
+        }
+    }
+
+    ""
+}
+
+/// Split markdown into documents starting with a `# ` heading, returns the line of the heading
+/// and the document. Lines before the first heading belong to the first document.
+pub fn split_documents(markdown: &str) -> Vec<(usize, String)> {
+    let headings = extract_tokens(markdown)
+        .into_iter()
+        .filter(|token| token.kind == TokenKind::H1)
+        .map(|token| token.line)
+        .collect::<Vec<_>>();
+
+    if headings.len() < 2 {
+        return vec![(headings.first().copied().unwrap_or(1), markdown.to_string())];
+    }
 
     let lines = markdown.trim().split('\n').collect::<Vec<_>>();
-    let mut empty_next_line = false;
+    let mut starts = headings.clone();
+    starts[0] = 1;
+    starts.push(lines.len() + 1);
 
-    lines
-        .clone()
+    headings
         .into_iter()
-        .enumerate()
-        .filter_map(|(idx, line)| {
-            let ln = idx + 1;
-            let mut line = line.to_string();
-
-            if empty_next_line {
-                line = "".to_string();
-                empty_next_line = false;
-            }
+        .zip(starts.windows(2))
+        .map(|(heading, range)| {
+            let document = lines[range[0] - 1..range[1] - 1].join("\n");
+            (heading, format!("{}\n", document.trim_end()))
+        })
+        .collect()
+}
 
-            if line.starts_with(PREFIX_HR) {
-                return Some(Token::new(ln, TokenKind::Hr, vec!["-".to_string()]));
+fn extract_tokens<'a>(markdown: &'a str) -> Vec<Token<'a>> {
+    let regexes = regexes();
+    let lines = markdown.trim().split('\n').collect::<Vec<_>>();
+    let mut tokens = Vec::with_capacity(lines.len());
+    let mut skip_lines = 0;
+
+    // Title of a link definition could be placed on the following line
+    let with_title = |link: Cow<'a, str>, next_idx: usize, skip_lines: &mut usize| match lines
+        .get(next_idx)
+        .filter(|line| regexes.link_title.is_match(line))
+    {
+        Some(title) => {
+            *skip_lines += 1;
+            Cow::Owned(format!("{link}\n{}", title.trim_end()))
+        }
+        None => link,
+    };
+
+    for (idx, &line) in lines.iter().enumerate() {
+        let ln = idx + 1;
+        let line = match skip_lines {
+            0 => line,
+            _ => {
+                skip_lines -= 1;
+                ""
             }
+        };
 
-            if line.starts_with(PREFIX_H1) {
-                return Some(Token::new(ln, TokenKind::H1, vec![substring(line, 1)]));
+        let token = |kind, content| Token::new(ln, kind, vec![Cow::Borrowed(content)]);
+
+        if line.starts_with(PREFIX_HR) {
+            tokens.push(token(TokenKind::Hr, "-"));
+        } else if line.starts_with(PREFIX_H1) {
+            tokens.push(token(TokenKind::H1, heading_text(&line[1..])));
+        } else if line.starts_with(PREFIX_H2) {
+            tokens.push(token(TokenKind::H2, heading_text(&line[2..])));
+        } else if line.starts_with(PREFIX_H3) {
+            tokens.push(token(TokenKind::H3, heading_text(&line[3..])));
+        } else if line.starts_with(PREFIX_LI) || line.starts_with(PREFIX_LI2) {
+            // the indentation of the content is kept to find the continuation lines
+            tokens.push(token(TokenKind::Li, line[1..].trim_end()));
+        } else if let Some(captures) = regexes.link.captures(line) {
+            let mut link = Cow::Borrowed(line.trim());
+            if captures.get(1).is_none() {
+                link = with_title(link, idx + 1, &mut skip_lines);
             }
-
-            if line.starts_with(PREFIX_H2) {
-                return Some(Token::new(ln, TokenKind::H2, vec![substring(line, 2)]));
+            tokens.push(Token::new(ln, TokenKind::Link, vec![link]));
+        } else if regexes.link_ref.is_match(line) {
+            let Some(next_line) = lines.get(idx + 1) else {
+                continue;
+            };
+            if let Some(captures) = regexes.link_prefix.captures(next_line) {
+                skip_lines += 1;
+                let mut link = Cow::Owned(format!("{}\n{}", line.trim(), next_line.trim_end()));
+                if captures.get(1).is_none() {
+                    link = with_title(link, idx + 2, &mut skip_lines);
+                }
+                tokens.push(Token::new(ln, TokenKind::Link, vec![link]));
             }
-
-            if line.starts_with(PREFIX_H3) {
-                return Some(Token::new(ln, TokenKind::H3, vec![substring(line, 3)]));
+        } else if let Some(captures) = regexes.comment.captures(line) {
+            log::debug!("Found comments: {:#?}", captures);
+            let comment = captures.get(1).map_or("", |m| m.as_str()).trim();
+            if comment.starts_with("markdownlint-disable") {
+                tokens.push(token(TokenKind::Lint, comment));
+            } else {
+                tokens.push(token(TokenKind::Flag, comment));
             }
+        } else if let Some(kind) = setext_heading(&lines, idx, line) {
+            // normalized to the ATX heading, the underline is skipped
+            skip_lines += 1;
+            tokens.push(token(kind, line.trim()));
+        } else {
+            tokens.push(token(TokenKind::P, line.trim_end()));
+        }
+    }
 
-            if line.starts_with(PREFIX_LI) || line.starts_with(PREFIX_LI2) {
-                return Some(Token::new(ln, TokenKind::Li, vec![substring(line, 1)]));
-            }
+    tokens
+}
 
-            if link_regex.is_match(&line) {
-                return Some(Token::new(
-                    ln,
-                    TokenKind::Link,
-                    vec![line.clone().trim().to_string()],
-                ));
-            }
+/// Text of an ATX heading without the optional closing sequence, e.g. `## [1.0.0] ##`
+fn heading_text(heading: &str) -> &str {
+    let heading = heading.trim();
+    let text = heading.trim_end_matches('#');
+    // the closing sequence has to be preceded by a space, `## C#` keeps its hash
+    match text.is_empty() || text.ends_with(char::is_whitespace) {
+        true => text.trim_end(),
+        false => heading,
+    }
+}
 
-            if link_ref_regex.is_match(&line) {
-                let next_line = lines.get(idx + 1);
+/// Kind of the setext heading (`Changelog\n=========`) starting at the line `idx`, the heading
+/// has to follow a blank line, so the underline isn't taken for a continuation of a paragraph.
+fn setext_heading(lines: &[&str], idx: usize, line: &str) -> Option<TokenKind> {
+    if line.trim().is_empty() || line.starts_with(char::is_whitespace) {
+        return None;
+    }
+    if idx > 0 && !lines[idx - 1].trim().is_empty() {
+        return None;
+    }
 
-                if let Some(next_line) = next_line {
-                    if link_prefix_regex.is_match(next_line) {
-                        empty_next_line = true;
-                        let line = format!("{}\n{}", line.trim(), next_line.trim_end());
-                        return Some(Token::new(ln, TokenKind::Link, vec![line]));
-                    }
-                }
-                return None;
-            }
+    let captures = regexes().setext.captures(lines.get(idx + 1)?)?;
+    match captures.get(1) {
+        Some(_) => Some(TokenKind::H1),
+        None => Some(TokenKind::H2),
+    }
+}
 
-            if let Some(captures) = comment_regex.captures(&line) {
-                log::debug!("Found comments: {:#?}", captures);
-                let line = captures[1].trim().to_string();
-                if line.starts_with("markdownlint-disable") {
-                    return Some(Token::new(ln, TokenKind::Lint, vec![line]));
-                } else {
-                    return Some(Token::new(ln, TokenKind::Flag, vec![line]));
-                }
-            }
+struct Regexes {
+    link: Regex,
+    link_ref: Regex,
+    link_prefix: Regex,
+    link_title: Regex,
+    comment: Regex,
+    setext: Regex,
+}
 
-            Some(Token::new(
-                ln,
-                TokenKind::P,
-                vec![line.trim_end().to_string()],
-            ))
-        })
-        .collect::<Vec<_>>()
+fn regexes() -> &'static Regexes {
+    static REGEXES: OnceLock<Regexes> = OnceLock::new();
+    REGEXES.get_or_init(|| {
+        // Link destination and title, see CommonMark link reference definitions
+        let destination = r"(?:<[^<>]*>|\S+)";
+        let title = r#"(?:"(?:[^"\\]|\\.)*"|'(?:[^'\\]|\\.)*'|\((?:[^()\\]|\\.)*\))"#;
+
+        Regexes {
+            link: Regex::new(&format!(r"^\[[^\]]+\]\:\s*{destination}(\s+{title})?\s*$")).unwrap(),
+            link_ref: Regex::new(r"^\[[^\]]+\]\:$").unwrap(),
+            link_prefix: Regex::new(&format!(r"^\s+{destination}(\s+{title})?\s*$")).unwrap(),
+            link_title: Regex::new(&format!(r"^\s+{title}\s*$")).unwrap(),
+            comment: Regex::new(r"^<!--(.*)-->$").unwrap(),
+            setext: Regex::new(r"^ {0,3}(?:(=+)|-{3,})\s*$").unwrap(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[test]
+    fn test_tokenize() -> Result<()> {
+        let (compact, tokens) = tokenize("# Changelog\n\n## Unreleased\n- Entry\n  continued\n\n")?;
+        assert!(!compact);
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(1, TokenKind::H1, vec!["Changelog".into()]),
+                Token::new(3, TokenKind::H2, vec!["Unreleased".into()]),
+                Token::new(4, TokenKind::Li, vec!["Entry".into(), "continued".into()]),
+            ]
+        );
+
+        let (compact, tokens) = tokenize("# Changelog")?;
+        assert!(!compact);
+        assert_eq!(tokens.len(), 1);
+
+        let (compact, _) = tokenize("# Changelog\nDescription\n")?;
+        assert!(compact);
+
+        let (_, tokens) =
+            tokenize("Changelog\n=========\n\nUnreleased\n----------\n- Entry\n---\n")?;
+        assert_eq!(
+            tokens,
+            vec![
+                Token::new(1, TokenKind::H1, vec!["Changelog".into()]),
+                Token::new(4, TokenKind::H2, vec!["Unreleased".into()]),
+                Token::new(6, TokenKind::Li, vec!["Entry".into()]),
+                Token::new(7, TokenKind::Hr, vec!["-".into()]),
+            ]
+        );
+
+        let (_, tokens) =
+            tokenize("# Changelog #\n## [1.0.0] - 2024-01-01 ##\n### C# ###\n### C#\n")?;
+        let headings = tokens
+            .into_iter()
+            .map(|token| token.content[0].clone())
+            .collect::<Vec<_>>();
+        assert_eq!(headings, ["Changelog", "[1.0.0] - 2024-01-01", "C#", "C#"]);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::two_spaces("- Entry\n  continued", "Entry\ncontinued")]
+    #[case::tab("- Entry\n\tcontinued", "Entry\ncontinued")]
+    #[case::wide_marker("-   Entry\n    continued\n      nested", "Entry\ncontinued\n  nested")]
+    #[case::tab_marker("*\tEntry\n\tcontinued", "Entry\ncontinued")]
+    #[case::loose("- Entry\n\n  continued\n\n\tmore", "Entry\n\ncontinued\n\nmore")]
+    #[case::lazy("- Entry\ncontinued", "Entry\ncontinued")]
+    fn test_list_item_continuation(#[case] markdown: &str, #[case] expected: &str) -> Result<()> {
+        let (_, tokens) = tokenize(markdown)?;
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].content.join("\n"), expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,65 @@
+use chrono::{NaiveDate, Utc};
+use chrono_tz::Tz;
+use eyre::Result;
+
+use crate::{Changelog, Release, ReleaseVersion};
+
+/// Current calendar date in the time zone `tz`
+fn today(tz: Tz) -> NaiveDate {
+    Utc::now().with_timezone(&tz).date_naive()
+}
+
+impl Release {
+    /// Set the release date to the current date in the time zone `tz`, e.g. the time zone of the
+    /// maintainers rather than UTC of the CI runner
+    pub fn released_today(&mut self, tz: Tz) -> &mut Self {
+        self.set_date(today(tz))
+    }
+}
+
+impl Changelog {
+    /// Promote the Unreleased release to the given version, dated with the current date in the
+    /// time zone `tz`, see [`Changelog::promote_unreleased`]
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, Tz, Version};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Added\n- New feature\n";
+    /// let mut changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// changelog
+    ///     .promote_unreleased_today(Version::parse("0.1.0").unwrap(), Tz::Europe__Kyiv)
+    ///     .unwrap();
+    ///
+    /// let release = changelog.find_release("0.1.0".to_string()).unwrap().unwrap();
+    /// assert!(release.date().is_some());
+    /// ```
+    pub fn promote_unreleased_today<V: Into<ReleaseVersion>>(
+        &mut self,
+        version: V,
+        tz: Tz,
+    ) -> Result<&mut Self> {
+        self.promote_unreleased(version, today(tz))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_released_today() {
+        let mut release = Release::builder().build().unwrap();
+
+        let date = *release.released_today(Tz::Pacific__Kiritimati).date();
+        let utc = Utc::now().date_naive();
+        assert!(date == Some(utc) || date == Some(utc + Duration::days(1)));
+
+        let date = *release.released_today(Tz::Pacific__Pago_Pago).date();
+        assert!(date == Some(utc) || date == Some(utc - Duration::days(1)));
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt::Write;
+
+use semver::Version;
+
+use crate::{changes::BREAKING_PREFIX, ChangeEntry, ChangeKind, Changelog, Visibility};
+
+/// Placeholder for the migration steps of an entry
+const TODO: &str = "TODO: describe the migration steps";
+
+impl Changelog {
+    /// Skeleton of an upgrade guide from the release `from` to the release `to`
+    ///
+    /// Collects the breaking changes, removals and deprecations of the releases after `from` up
+    /// to and including `to`, from the oldest to the newest. Every entry is followed by a TODO
+    /// placeholder for its migration steps. Draft releases, releases which don't follow semantic
+    /// versioning and internal entries, see [`ChangeEntry::visibility`], are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{Changelog, Version};
+    ///
+    /// let markdown = "# Changelog\n## [2.0.0] - 2024-06-01\n### Changed\n\
+    ///     - **BREAKING** Rename `parse` to `from_str`\n- Faster parsing\n### Removed\n\
+    ///     - `Changelog::legacy`\n\
+    ///     \n## [1.1.0] - 2024-05-01\n### Deprecated\n- `Changelog::legacy`\n\
+    ///     \n## [1.0.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let guide = changelog.upgrade_guide(&Version::new(1, 0, 0), &Version::new(2, 0, 0));
+    /// assert!(guide.starts_with("# Upgrading from 1.0.0 to 2.0.0\n\n## Breaking changes\n\n\
+    ///     - Rename `parse` to `from_str` (2.0.0)\n\n  TODO: describe the migration steps\n"));
+    /// assert!(guide.contains("## Removed\n\n- `Changelog::legacy` (2.0.0)\n"));
+    /// assert!(guide.contains("## Deprecated\n\n- `Changelog::legacy` (1.1.0)\n"));
+    /// assert!(!guide.contains("Faster parsing"));
+    /// ```
+    pub fn upgrade_guide(&self, from: &Version, to: &Version) -> String {
+        let mut breaking = vec![];
+        let mut removed = vec![];
+        let mut deprecated = vec![];
+
+        // releases are sorted from the newest to the oldest
+        for release in self.releases().iter().rev().filter(|r| !r.draft()) {
+            let Some(version) = release.version().as_ref().and_then(|v| v.semver()) else {
+                continue;
+            };
+            if version <= from || version > to {
+                continue;
+            }
+
+            for entry in release.changes().entries() {
+                if *entry.visibility() == Visibility::Internal {
+                    continue;
+                }
+
+                let section = match entry.kind() {
+                    ChangeKind::Removed => &mut removed,
+                    ChangeKind::Deprecated => &mut deprecated,
+                    ChangeKind::Breaking | ChangeKind::Changed if *entry.breaking() => {
+                        &mut breaking
+                    }
+                    _ => continue,
+                };
+                section.push((version, entry));
+            }
+        }
+
+        let mut guide = format!("# Upgrading from {from} to {to}\n");
+        for (heading, entries) in [
+            ("Breaking changes", breaking),
+            ("Removed", removed),
+            ("Deprecated", deprecated),
+        ] {
+            if !entries.is_empty() {
+                write!(guide, "\n## {heading}\n").unwrap();
+                entries
+                    .into_iter()
+                    .for_each(|(version, entry)| write_entry(&mut guide, version, &entry));
+            }
+        }
+
+        if guide.lines().count() == 1 {
+            guide.push_str("\nNo breaking changes, removals or deprecations.\n");
+        }
+
+        guide
+    }
+}
+
+fn write_entry(guide: &mut String, version: &Version, entry: &ChangeEntry) {
+    let text = entry.text().trim();
+    let text = text
+        .strip_prefix(BREAKING_PREFIX)
+        .unwrap_or(text)
+        .trim_start();
+    write!(guide, "\n- {text} ({version})\n\n  {TODO}\n").unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_upgrade_guide() -> Result<()> {
+        let markdown = "# Changelog\n## [Unreleased]\n### Removed\n- Unreleased removal\n\
+            \n## [1.3.0] - 2024-07-01\n<!-- draft -->\n### Removed\n- Embargoed removal\n\
+            \n## [1.2.0] - 2024-06-01\n### BREAKING\n- Drop Rust 1.60\n### Removed\n- Old flag\n\
+            - Drop the staging bucket <!-- internal -->\n\
+            \n## [1.1.0] - 2024-05-01\n### Changed\n- **BREAKING** New config format\n\
+            \n## [1.0.0] - 2024-04-28\n### Removed\n- Before the range\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        assert_eq!(
+            changelog.upgrade_guide(&Version::new(1, 0, 0), &Version::new(1, 2, 0)),
+            "# Upgrading from 1.0.0 to 1.2.0\n\
+            \n## Breaking changes\n\
+            \n- New config format (1.1.0)\n\n  TODO: describe the migration steps\n\
+            \n- Drop Rust 1.60 (1.2.0)\n\n  TODO: describe the migration steps\n\
+            \n## Removed\n\
+            \n- Old flag (1.2.0)\n\n  TODO: describe the migration steps\n"
+        );
+        assert_eq!(
+            changelog.upgrade_guide(&Version::new(1, 2, 0), &Version::new(1, 3, 0)),
+            "# Upgrading from 1.2.0 to 1.3.0\n\nNo breaking changes, removals or deprecations.\n"
+        );
+        Ok(())
+    }
+}
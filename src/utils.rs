@@ -1,14 +1,47 @@
+use std::{borrow::Cow, collections::BTreeMap, iter::Peekable, str::Chars};
+
+use eyre::{bail, eyre, Result};
+
 pub fn get_release_url(repo_url: String, version: String) -> String {
     let mut url_body = "/-/tags/";
-    if repo_url.starts_with("https://github.com") {
+    if is_github_url(&repo_url) {
         url_body = "/releases/tag/";
     }
 
     format!("{repo_url}{url_body}{version}")
 }
 
+/// Validate repository URL, which must be an absolute http(s) URL, trailing slashes are removed.
+pub fn normalize_repo_url(url: &str) -> Result<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| eyre!("Repository URL must use http or https scheme: {url}"))?;
+
+    if rest.split('/').next().unwrap_or_default().is_empty() {
+        bail!("Repository URL has no host: {url}");
+    }
+
+    if url.chars().any(char::is_whitespace) {
+        bail!("Repository URL contains whitespace: {url}");
+    }
+
+    Ok(url.trim_end_matches('/').to_string())
+}
+
+/// FNV-1a hash of the contents, stable across platforms and Rust versions.
+pub fn fnv1a(contents: &str) -> u64 {
+    contents.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
 pub fn get_compare_url(repo_url: String, previous: String, current: String) -> String {
-    format!("{repo_url}/compare/{previous}...{current}")
+    if is_github_url(&repo_url) {
+        return format!("{repo_url}/compare/{previous}...{current}");
+    }
+
+    format!("{repo_url}/-/compare/{previous}...{current}")
 }
 
 pub fn substring(str: String, from: usize) -> String {
@@ -19,10 +52,160 @@ pub fn substring(str: String, from: usize) -> String {
         .to_string()
 }
 
-pub fn is_empty_str(val: String) -> bool {
-    val.is_empty() || val.trim().is_empty()
+pub fn is_github_url(repo_url: &str) -> bool {
+    repo_url.starts_with("https://github.com")
+}
+
+/// Percent-encode a path segment, e.g. `group/project` to `group%2Fproject`
+#[cfg(any(feature = "github", feature = "gitlab"))]
+pub fn encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
 }
 
-pub fn is_empty_str_vec(val: Vec<String>) -> bool {
-    is_empty_str(val.join(""))
+pub fn get_file_url(repo_url: &str, head: &str, path: &str) -> String {
+    if is_github_url(repo_url) {
+        return format!("{repo_url}/blob/{head}/{path}");
+    }
+
+    format!("{repo_url}/-/blob/{head}/{path}")
+}
+
+pub fn get_pull_request_url(repo_url: &str, number: u64) -> String {
+    if is_github_url(repo_url) {
+        return format!("{repo_url}/pull/{number}");
+    }
+
+    format!("{repo_url}/-/merge_requests/{number}")
+}
+
+pub fn get_issue_url(repo_url: &str, number: u64) -> String {
+    if is_github_url(repo_url) {
+        return format!("{repo_url}/issues/{number}");
+    }
+
+    format!("{repo_url}/-/issues/{number}")
+}
+
+pub fn get_commit_url(repo_url: &str, sha: &str) -> String {
+    if is_github_url(repo_url) {
+        return format!("{repo_url}/commit/{sha}");
+    }
+
+    format!("{repo_url}/-/commit/{sha}")
+}
+
+/// Match `/` separated path against a glob pattern, `*` and `?` match within a path segment,
+/// `**` matches any number of segments.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.split('/').collect::<Vec<_>>();
+    let path = path.split('/').collect::<Vec<_>>();
+    match_segments(&pattern, &path)
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match (pattern.split_first(), path.split_first()) {
+        (None, None) => true,
+        (Some((&"**", rest)), _) => {
+            match_segments(rest, path) || (!path.is_empty() && match_segments(pattern, &path[1..]))
+        }
+        (Some((segment, rest)), Some((name, path))) => {
+            match_segment(segment.as_bytes(), name.as_bytes()) && match_segments(rest, path)
+        }
+        _ => false,
+    }
+}
+
+fn match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match (pattern.split_first(), name.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            match_segment(rest, name) || (!name.is_empty() && match_segment(pattern, &name[1..]))
+        }
+        (Some((b'?', rest)), Some((_, name))) => match_segment(rest, name),
+        (Some((c, rest)), Some((n, name))) => c == n && match_segment(rest, name),
+        _ => false,
+    }
+}
+
+/// Parse whitespace separated `key=value` pairs, keys and values containing whitespace, `=` or
+/// `"` are quoted, e.g. `sha=abc123 note="Approved by QA"`.
+pub fn parse_pairs(text: &str) -> Option<BTreeMap<String, String>> {
+    let mut pairs = BTreeMap::new();
+    let mut chars = text.trim().chars().peekable();
+
+    while chars.peek().is_some() {
+        let key = scan_word(&mut chars)?;
+        if key.is_empty() || chars.next() != Some('=') {
+            return None;
+        }
+
+        let value = scan_word(&mut chars)?;
+        if chars.next().is_some_and(|c| !c.is_whitespace()) {
+            return None;
+        }
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+        pairs.insert(key, value);
+    }
+
+    Some(pairs)
+}
+
+fn scan_word(chars: &mut Peekable<Chars>) -> Option<String> {
+    let mut word = String::new();
+
+    if chars.next_if_eq(&'"').is_none() {
+        while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != '=' && c != '"') {
+            word.push(c);
+        }
+        return Some(word);
+    }
+
+    loop {
+        match chars.next()? {
+            '"' => return Some(word),
+            '\\' => word.push(chars.next()?),
+            c => word.push(c),
+        }
+    }
+}
+
+/// Format pairs as parsed by [`parse_pairs`].
+pub fn fmt_pairs(pairs: &BTreeMap<String, String>) -> String {
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", quote(key), quote(value)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn quote(word: &str) -> Cow<'_, str> {
+    let plain = |c: char| !c.is_whitespace() && !matches!(c, '=' | '"' | '\\');
+    match !word.is_empty() && word.chars().all(plain) {
+        true => Cow::Borrowed(word),
+        false => Cow::Owned(format!(
+            "\"{}\"",
+            word.replace('\\', "\\\\").replace('"', "\\\"")
+        )),
+    }
+}
+
+/// Adapter to render a formatting closure with `to_string`.
+pub struct DisplayFn<F>(pub F);
+
+impl<F> std::fmt::Display for DisplayFn<F>
+where
+    F: Fn(&mut std::fmt::Formatter) -> std::fmt::Result,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        (self.0)(f)
+    }
 }
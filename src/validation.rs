@@ -0,0 +1,373 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    time::Duration,
+};
+
+use crate::{
+    date::{self, CalendarDate},
+    style::StyleRule,
+    ChangeKind, Changelog, Date, ReleaseVersion,
+};
+
+const SECS_PER_DAY: u64 = 86_400;
+
+/// Options for [`Changelog::validate`], rules are disabled unless configured.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    /// Date to validate against, defaults to [`CalendarDate::today`]
+    pub today: Option<Date>,
+    /// Flag the Unreleased section with entries if no release has been cut for longer than this
+    pub stale_unreleased_after: Option<Duration>,
+}
+
+/// Represents a validation rule violation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The Unreleased section has entries, but no release has been cut for longer than the
+    /// threshold.
+    StaleUnreleased { age: Duration, threshold: Duration },
+    /// An entry breaks a [`StylePolicy`](crate::style::StylePolicy) rule, `index` is the index of
+    /// the entry within the changes of its kind.
+    Style {
+        version: Option<ReleaseVersion>,
+        kind: ChangeKind,
+        index: usize,
+        rule: StyleRule,
+    },
+    /// An entry doesn't match the [template](crate::TemplateRegistry) of its kind, `index` is the
+    /// index of the entry within the changes of its kind.
+    Template {
+        version: Option<ReleaseVersion>,
+        kind: ChangeKind,
+        index: usize,
+        pattern: String,
+    },
+    /// The release isn't in the changelog and there is no Unreleased section to promote to it
+    ReleaseNotFound { version: ReleaseVersion },
+    /// The release has no entries
+    EmptyRelease { version: ReleaseVersion },
+    /// The release isn't dated with the expected release date
+    ReleaseDate {
+        version: ReleaseVersion,
+        date: Option<Date>,
+        expected: Date,
+    },
+    /// The link of the release can't be generated, e.g. the repository URL is missing
+    ReleaseLink {
+        version: ReleaseVersion,
+        reason: String,
+    },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Violation::StaleUnreleased { age, threshold } => write!(
+                f,
+                "Unreleased changes are waiting for {} days, release at least every {} days",
+                age.as_secs() / SECS_PER_DAY,
+                threshold.as_secs() / SECS_PER_DAY
+            ),
+            Violation::Style {
+                version,
+                kind,
+                index,
+                rule,
+            } => {
+                let version = version
+                    .as_ref()
+                    .map_or_else(|| "Unreleased".to_string(), |v| v.to_string());
+                write!(
+                    f,
+                    "Entry {} of {} in {version} {rule}",
+                    index + 1,
+                    kind.heading()
+                )
+            }
+            Violation::Template {
+                version,
+                kind,
+                index,
+                pattern,
+            } => {
+                let version = version
+                    .as_ref()
+                    .map_or_else(|| "Unreleased".to_string(), |v| v.to_string());
+                write!(
+                    f,
+                    "Entry {} of {} in {version} doesn't match the template `{pattern}`",
+                    index + 1,
+                    kind.heading()
+                )
+            }
+            Violation::ReleaseNotFound { version } => write!(
+                f,
+                "Release {version} not found and there are no Unreleased changes to promote"
+            ),
+            Violation::EmptyRelease { version } => write!(f, "Release {version} has no entries"),
+            Violation::ReleaseDate {
+                version,
+                date: Some(date),
+                expected,
+            } => write!(f, "Release {version} is dated {date}, expected {expected}"),
+            Violation::ReleaseDate {
+                version,
+                date: None,
+                expected,
+            } => write!(f, "Release {version} has no date, expected {expected}"),
+            Violation::ReleaseLink { version, reason } => {
+                write!(f, "Release {version} has no valid link: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Violation {}
+
+impl Changelog {
+    /// Time since the latest release, if the Unreleased section has entries
+    ///
+    /// Returns `None` if there is no Unreleased section, it has no entries or no release has
+    /// been cut yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{CalendarDate, Changelog, Date};
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Added\n- New feature\n\
+    ///     \n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let today = Date::new(2024, 5, 28).unwrap();
+    /// assert_eq!(changelog.unreleased_age(today).unwrap().as_secs(), 30 * 86_400);
+    /// ```
+    pub fn unreleased_age(&self, today: Date) -> Option<Duration> {
+        let unreleased = self.get_unreleased()?;
+        if unreleased.changes().is_empty() {
+            return None;
+        }
+
+        let latest = self.releases().iter().filter_map(|r| *r.date()).max()?;
+        let days = date::epoch_days(today) - date::epoch_days(latest);
+        Some(Duration::from_secs(days.max(0) as u64 * SECS_PER_DAY))
+    }
+
+    /// Validate changelog against the rules configured in `opts`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use keep_a_changelog::{
+    ///     validation::{ValidationOptions, Violation},
+    ///     CalendarDate, Changelog, Date,
+    /// };
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Added\n- New feature\n\
+    ///     \n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let violations = changelog.validate(&ValidationOptions {
+    ///     today: Date::new(2024, 8, 1),
+    ///     stale_unreleased_after: Some(Duration::from_secs(30 * 86_400)),
+    /// });
+    ///
+    /// assert!(matches!(violations[..], [Violation::StaleUnreleased { .. }]));
+    /// ```
+    pub fn validate(&self, opts: &ValidationOptions) -> Vec<Violation> {
+        let today = opts.today.unwrap_or_else(Date::today);
+        let mut violations = vec![];
+
+        if let Some(threshold) = opts.stale_unreleased_after {
+            match self.unreleased_age(today) {
+                Some(age) if age > threshold => {
+                    violations.push(Violation::StaleUnreleased { age, threshold })
+                }
+                _ => {}
+            }
+        }
+
+        violations
+    }
+
+    /// Check that the release `version` can be tagged, the single call of a release pipeline
+    /// before tagging
+    ///
+    /// The release must exist, or the Unreleased section is checked as if it was promoted to
+    /// `version`. It must have entries, be dated with `date` ([`CalendarDate::today`] by default) and
+    /// have a release or compare link, i.e. the changelog needs a repository URL.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{
+    ///     CalendarDate, Changelog, ChangelogParseOptions, Date, Version, Violation,
+    /// };
+    ///
+    /// let markdown = "# Changelog\n## [Unreleased]\n### Added\n- New feature\n\
+    ///     \n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n";
+    /// let opts = ChangelogParseOptions {
+    ///     url: Some("https://github.com/owner/repo".to_string()),
+    ///     ..Default::default()
+    /// };
+    /// let changelog = Changelog::parse(markdown.to_string(), Some(opts)).unwrap();
+    /// let date = Date::new(2024, 6, 1);
+    ///
+    /// assert!(changelog.assert_releasable(Version::new(0, 2, 0), date).is_ok());
+    ///
+    /// let violations = changelog.assert_releasable(Version::new(0, 1, 0), date).unwrap_err();
+    /// assert!(matches!(violations[..], [Violation::ReleaseDate { .. }]));
+    /// ```
+    pub fn assert_releasable<V: Into<ReleaseVersion>>(
+        &self,
+        version: V,
+        date: Option<Date>,
+    ) -> Result<(), Vec<Violation>> {
+        let version = version.into();
+        let expected = date.unwrap_or_else(Date::today);
+
+        let exists = self
+            .releases()
+            .iter()
+            .any(|release| release.version().as_ref() == Some(&version));
+        // The promotion is hypothetical, so it's hidden from the observers
+        let mut changelog = self.clone();
+        changelog.observers = Default::default();
+        if !exists
+            && changelog
+                .promote_unreleased(version.clone(), expected)
+                .is_err()
+        {
+            return Err(vec![Violation::ReleaseNotFound { version }]);
+        }
+
+        let release = changelog
+            .releases()
+            .iter()
+            .find(|release| release.version().as_ref() == Some(&version))
+            .expect("release exists or is promoted");
+
+        let mut violations = vec![];
+        if release.changes().is_empty() && !release.is_empty_release() {
+            violations.push(Violation::EmptyRelease {
+                version: version.clone(),
+            });
+        }
+
+        if *release.date() != Some(expected) {
+            violations.push(Violation::ReleaseDate {
+                version: version.clone(),
+                date: *release.date(),
+                expected,
+            });
+        }
+
+        let reason = match release.compare_link(&changelog) {
+            _ if changelog.url().is_none() => Some("missing repository URL".to_string()),
+            Ok(Some(_)) => None,
+            Ok(None) => Some("no previous release to compare with".to_string()),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(reason) = reason {
+            violations.push(Violation::ReleaseLink { version, reason });
+        }
+
+        match violations.is_empty() {
+            true => Ok(()),
+            false => Err(violations),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use super::*;
+    use crate::ChangelogParseOptions;
+
+    fn days(days: u64) -> Duration {
+        Duration::from_secs(days * SECS_PER_DAY)
+    }
+
+    #[test]
+    fn test_unreleased_age() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/early_changelog_multiple_sections.md")?;
+        let changelog = Changelog::parse(markdown, None)?;
+        let today = Date::new(2024, 6, 1).unwrap();
+
+        assert_eq!(changelog.unreleased_age(today), Some(days(12)));
+
+        let opts = ValidationOptions {
+            today: Some(today),
+            stale_unreleased_after: Some(days(14)),
+        };
+        assert!(changelog.validate(&opts).is_empty());
+
+        let opts = ValidationOptions {
+            stale_unreleased_after: Some(days(7)),
+            ..opts
+        };
+        assert_eq!(
+            changelog.validate(&opts),
+            vec![Violation::StaleUnreleased {
+                age: days(12),
+                threshold: days(7),
+            }]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_releasable() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/early_changelog.md")?;
+        let (without_links, _) = markdown.split_once("[Unreleased]:").unwrap();
+        let changelog = Changelog::parse(without_links.to_string(), None)?;
+        let date = Date::new(2024, 5, 20);
+
+        let version = ReleaseVersion::parse("0.1.2")?;
+        assert_eq!(
+            changelog.assert_releasable(version.clone(), date),
+            Err(vec![Violation::ReleaseLink {
+                version: version.clone(),
+                reason: "missing repository URL".to_string(),
+            }])
+        );
+
+        let opts = ChangelogParseOptions {
+            url: Some("https://github.com/owner/repo".to_string()),
+            ..Default::default()
+        };
+        let mut changelog = Changelog::parse(markdown, Some(opts))?;
+        assert_eq!(changelog.assert_releasable(version, date), Ok(()));
+
+        let version = ReleaseVersion::parse("0.2.0")?;
+        assert_eq!(
+            changelog.assert_releasable(version.clone(), date),
+            Err(vec![Violation::EmptyRelease {
+                version: version.clone()
+            }])
+        );
+
+        changelog.releases_mut().remove(0);
+        assert_eq!(
+            changelog.assert_releasable(version.clone(), date),
+            Err(vec![Violation::ReleaseNotFound { version }])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_unreleased() -> Result<()> {
+        let markdown = std::fs::read_to_string("tests/data/early_changelog_compact.md")?;
+        let changelog = Changelog::parse(markdown, None)?;
+
+        assert_eq!(
+            changelog.unreleased_age(Date::new(2030, 1, 1).unwrap()),
+            None
+        );
+        Ok(())
+    }
+}
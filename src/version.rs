@@ -0,0 +1,400 @@
+//! Versions of releases, which aren't required to follow semantic versioning.
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use eyre::{bail, Error, Result};
+use regex::Regex;
+use semver::Version;
+
+use crate::{
+    date::{self, CalendarDate},
+    Date,
+};
+
+/// Version of a release.
+///
+/// Semantic versions are preferred, calendar versions like `2024.06.1` and any other versions
+/// starting with a digit and without whitespace, e.g. `42` or `1.2.3.4`, are kept as they are.
+/// Versions are ordered by their numeric segments, so releases of different schemes could still
+/// be sorted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReleaseVersion {
+    SemVer(Version),
+    CalVer(CalVer),
+    Raw(String),
+}
+
+/// Calendar version, e.g. `2024.06.1` or `2024.06`, starting with a four digit year.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CalVer {
+    segments: Vec<u64>,
+    text: String,
+}
+
+impl CalVer {
+    /// Parse a calendar version, segments are separated by dots and could have leading zeros.
+    pub fn parse(version: &str) -> Result<Self> {
+        static CALVER: OnceLock<Regex> = OnceLock::new();
+        let calver = CALVER.get_or_init(|| Regex::new(r"^\d{4}\.\d{1,2}(\.\d+){0,2}$").unwrap());
+
+        if !calver.is_match(version) {
+            bail!("Invalid calendar version: {version}");
+        }
+
+        Ok(Self {
+            segments: version
+                .split('.')
+                .map(|s| s.parse())
+                .collect::<Result<_, _>>()?,
+            text: version.to_string(),
+        })
+    }
+
+    /// Numeric segments, the first one is the year.
+    pub fn segments(&self) -> &[u64] {
+        &self.segments
+    }
+}
+
+impl Display for CalVer {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Calendar versioning scheme, e.g. `YYYY.0M.MICRO`, see <https://calver.org>.
+///
+/// Segments are separated by dots. Supported segments are `YYYY`, `YY`, `0Y`, `MM`, `0M`,
+/// `WW`, `0W`, `DD`, `0D` and `MICRO`, which could only be the last one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalVerScheme {
+    segments: Vec<CalVerSegment>,
+}
+
+/// Segment of a [`CalVerScheme`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalVerSegment {
+    /// Full year, e.g. `2006`
+    FullYear,
+    /// Short year, e.g. `6` or `16`
+    ShortYear,
+    /// Zero-padded short year, e.g. `06`
+    ZeroPaddedYear,
+    /// Month, e.g. `1` or `11`
+    Month,
+    /// Zero-padded month, e.g. `01`
+    ZeroPaddedMonth,
+    /// ISO week of the year, e.g. `1` or `33`
+    Week,
+    /// Zero-padded ISO week of the year, e.g. `01`
+    ZeroPaddedWeek,
+    /// Day of the month, e.g. `1` or `31`
+    Day,
+    /// Zero-padded day of the month, e.g. `01`
+    ZeroPaddedDay,
+    /// Release number within the period, starting from 0
+    Micro,
+}
+
+impl FromStr for CalVerScheme {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let segments = s
+            .split('.')
+            .map(|segment| match segment {
+                "YYYY" => Ok(CalVerSegment::FullYear),
+                "YY" => Ok(CalVerSegment::ShortYear),
+                "0Y" => Ok(CalVerSegment::ZeroPaddedYear),
+                "MM" => Ok(CalVerSegment::Month),
+                "0M" => Ok(CalVerSegment::ZeroPaddedMonth),
+                "WW" => Ok(CalVerSegment::Week),
+                "0W" => Ok(CalVerSegment::ZeroPaddedWeek),
+                "DD" => Ok(CalVerSegment::Day),
+                "0D" => Ok(CalVerSegment::ZeroPaddedDay),
+                "MICRO" => Ok(CalVerSegment::Micro),
+                _ => bail!("Unknown calendar version segment `{segment}` in scheme: {s}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if !matches!(
+            segments.first(),
+            Some(
+                CalVerSegment::FullYear | CalVerSegment::ShortYear | CalVerSegment::ZeroPaddedYear
+            )
+        ) {
+            bail!("Calendar version scheme must start with a year: {s}");
+        }
+
+        if segments[..segments.len() - 1].contains(&CalVerSegment::Micro) {
+            bail!("MICRO must be the last segment of the calendar version scheme: {s}");
+        }
+
+        Ok(Self { segments })
+    }
+}
+
+impl CalVerScheme {
+    /// Whether the scheme ends with the `MICRO` segment.
+    pub fn has_micro(&self) -> bool {
+        self.segments.last() == Some(&CalVerSegment::Micro)
+    }
+
+    /// Format version of a release made on `date`, `micro` is ignored without the `MICRO` segment.
+    pub fn format(&self, date: Date, micro: u64) -> String {
+        let (year, month, day) = date.ymd();
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                CalVerSegment::FullYear => year.to_string(),
+                CalVerSegment::ShortYear => (year % 100).to_string(),
+                CalVerSegment::ZeroPaddedYear => format!("{:02}", year % 100),
+                CalVerSegment::Month => month.to_string(),
+                CalVerSegment::ZeroPaddedMonth => format!("{month:02}"),
+                CalVerSegment::Week => date::iso_week(date).to_string(),
+                CalVerSegment::ZeroPaddedWeek => format!("{:02}", date::iso_week(date)),
+                CalVerSegment::Day => day.to_string(),
+                CalVerSegment::ZeroPaddedDay => format!("{day:02}"),
+                CalVerSegment::Micro => micro.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    /// Numeric segments of the version with short years expanded, `None` if the version doesn't
+    /// follow the scheme.
+    pub(crate) fn segments(&self, version: &ReleaseVersion) -> Option<Vec<u64>> {
+        let version = version.to_string();
+        let parts = version.split('.').collect::<Vec<_>>();
+        if parts.len() != self.segments.len() {
+            return None;
+        }
+
+        parts
+            .iter()
+            .zip(&self.segments)
+            .map(|(part, segment)| {
+                let value = part.parse::<u64>().ok()?;
+                match segment {
+                    CalVerSegment::ShortYear | CalVerSegment::ZeroPaddedYear => Some(2000 + value),
+                    _ => Some(value),
+                }
+            })
+            .collect()
+    }
+
+    /// Compare versions by the calendar, e.g. `24.12.0` is older than `2025.01.0`
+    ///
+    /// Versions following the scheme are newer than others, which are compared as usual.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::{version::CalVerScheme, ReleaseVersion};
+    ///
+    /// let scheme = "YY.0M.MICRO".parse::<CalVerScheme>().unwrap();
+    /// let mut versions = ["24.10.1", "9.12.0", "24.09.0"]
+    ///     .map(|v| ReleaseVersion::parse(v).unwrap());
+    ///
+    /// versions.sort_by(|a, b| scheme.compare(b, a));
+    /// assert_eq!(versions.map(|v| v.to_string()), ["24.10.1", "24.09.0", "9.12.0"]);
+    /// ```
+    pub fn compare(&self, a: &ReleaseVersion, b: &ReleaseVersion) -> Ordering {
+        match (self.segments(a), self.segments(b)) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => Ordering::Greater,
+            (None, Some(_)) => Ordering::Less,
+            (None, None) => a.cmp(b),
+        }
+    }
+}
+
+impl ReleaseVersion {
+    /// Parse a version, trying semantic and calendar versions first
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::ReleaseVersion;
+    ///
+    /// assert!(ReleaseVersion::parse("1.2.3").unwrap().semver().is_some());
+    /// assert!(matches!(
+    ///     ReleaseVersion::parse("2024.06.1").unwrap(),
+    ///     ReleaseVersion::CalVer(_)
+    /// ));
+    /// assert!(matches!(
+    ///     ReleaseVersion::parse("1.2.3.4").unwrap(),
+    ///     ReleaseVersion::Raw(_)
+    /// ));
+    /// assert!(ReleaseVersion::parse("1.2.3").unwrap() < ReleaseVersion::parse("1.2.3.4").unwrap());
+    /// assert!(ReleaseVersion::parse("next").is_err());
+    /// ```
+    pub fn parse(version: &str) -> Result<Self> {
+        if let Ok(version) = Version::parse(version) {
+            return Ok(Self::SemVer(version));
+        }
+
+        if let Ok(version) = CalVer::parse(version) {
+            return Ok(Self::CalVer(version));
+        }
+
+        if !version.starts_with(|c: char| c.is_ascii_digit())
+            || version.contains(char::is_whitespace)
+        {
+            bail!("Invalid version: {version}");
+        }
+
+        Ok(Self::Raw(version.to_string()))
+    }
+
+    /// Semantic version, if the version follows semantic versioning.
+    pub fn semver(&self) -> Option<&Version> {
+        match self {
+            Self::SemVer(version) => Some(version),
+            _ => None,
+        }
+    }
+
+    /// Numeric segments used for ordering versions of different schemes.
+    fn segments(&self) -> Vec<u64> {
+        match self {
+            Self::SemVer(version) => vec![version.major, version.minor, version.patch],
+            Self::CalVer(version) => version.segments.clone(),
+            Self::Raw(version) => version
+                .split(|c: char| !c.is_ascii_digit())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse().unwrap_or(u64::MAX))
+                .collect(),
+        }
+    }
+
+    fn scheme(&self) -> u8 {
+        match self {
+            Self::SemVer(_) => 0,
+            Self::CalVer(_) => 1,
+            Self::Raw(_) => 2,
+        }
+    }
+}
+
+impl Ord for ReleaseVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::SemVer(a), Self::SemVer(b)) => a.cmp(b),
+            _ => self
+                .segments()
+                .cmp(&other.segments())
+                .then_with(|| self.scheme().cmp(&other.scheme()))
+                .then_with(|| self.to_string().cmp(&other.to_string())),
+        }
+    }
+}
+
+impl PartialOrd for ReleaseVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for ReleaseVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::SemVer(version) => write!(f, "{version}"),
+            Self::CalVer(version) => write!(f, "{version}"),
+            Self::Raw(version) => write!(f, "{version}"),
+        }
+    }
+}
+
+impl FromStr for ReleaseVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl From<Version> for ReleaseVersion {
+    fn from(version: Version) -> Self {
+        Self::SemVer(version)
+    }
+}
+
+impl From<CalVer> for ReleaseVersion {
+    fn from(version: CalVer) -> Self {
+        Self::CalVer(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    #[case("2024.06.1", "2024.06.2")]
+    #[case("2024.06.2", "2024.10.1")]
+    #[case("9", "42")]
+    #[case("1.2.3", "1.2.3.4")]
+    #[case("1.2.3-rc.1", "1.2.3")]
+    #[case("2023.12.1", "2024.01")]
+    fn test_ordering(#[case] older: &str, #[case] newer: &str) -> Result<()> {
+        assert!(ReleaseVersion::parse(older)? < ReleaseVersion::parse(newer)?);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("YYYY.0M.MICRO", 3, "2024.06.3")]
+    #[case("YY.MM", 0, "24.6")]
+    #[case("0Y.0W.DD", 0, "24.22.1")]
+    #[case("YYYY.0M.0D", 0, "2024.06.01")]
+    fn test_calver_scheme_format(
+        #[case] scheme: &str,
+        #[case] micro: u64,
+        #[case] expected: &str,
+    ) -> Result<()> {
+        let date = Date::new(2024, 6, 1).unwrap();
+        assert_eq!(
+            scheme.parse::<CalVerScheme>()?.format(date, micro),
+            expected
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_calver_scheme() {
+        assert!("0M.YYYY".parse::<CalVerScheme>().is_err());
+        assert!("YYYY.MICRO.MM".parse::<CalVerScheme>().is_err());
+        assert!("YYYY.QQ".parse::<CalVerScheme>().is_err());
+    }
+
+    #[test]
+    fn test_suggest_next_calver_without_micro() -> Result<()> {
+        let markdown = "# Changelog\n## [2024.06.01] - 2024-06-01\n### Added\n- Initial release\n";
+        let changelog = crate::Changelog::parse(markdown.to_string(), None)?;
+        let scheme = "YYYY.0M.0D".parse()?;
+
+        let today = Date::new(2024, 6, 1).unwrap();
+        assert!(changelog.suggest_next_calver(&scheme, today).is_err());
+
+        let today = Date::new(2024, 6, 2).unwrap();
+        assert_eq!(
+            changelog.suggest_next_calver(&scheme, today)?.to_string(),
+            "2024.06.02"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_round_trip() -> Result<()> {
+        for version in ["1.2.3", "2024.06.1", "42", "1.2.3.4"] {
+            assert_eq!(ReleaseVersion::parse(version)?.to_string(), version);
+        }
+        Ok(())
+    }
+}
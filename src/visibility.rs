@@ -0,0 +1,83 @@
+use crate::{
+    changes::{ChangeEntry, Visibility, INTERNAL_MARKER},
+    Changelog, Changes,
+};
+
+impl Changelog {
+    /// Render the customer-facing changelog, internal entries are left out
+    ///
+    /// Entries are marked as internal with a trailing `<!-- internal -->` comment, see
+    /// [`ChangeEntry::visibility`]. Sections without public entries are omitted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let markdown = "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n\
+    ///     \n### Changed\n- Tune the cache size <!-- internal -->\n";
+    /// let changelog = Changelog::parse(markdown.to_string(), None).unwrap();
+    ///
+    /// let public = changelog.render_public();
+    /// assert!(public.contains("- Initial release\n"));
+    /// assert!(!public.contains("### Changed"));
+    ///
+    /// let internal = changelog.render_internal();
+    /// assert!(internal.contains("- Tune the cache size\n"));
+    /// assert!(!internal.contains("<!-- internal -->"));
+    /// ```
+    pub fn render_public(&self) -> String {
+        self.render_visible(Visibility::Public)
+    }
+
+    /// Render the internal release notes with all entries, the visibility markers are removed
+    pub fn render_internal(&self) -> String {
+        self.render_visible(Visibility::Internal)
+    }
+
+    fn render_visible(&self, audience: Visibility) -> String {
+        let mut changelog = self.clone();
+        for release in changelog.releases_mut() {
+            let changes = release
+                .changes()
+                .iter()
+                .map(|(kind, text)| ChangeEntry::new(kind, text.clone()))
+                .filter(|entry| audience == Visibility::Internal || *entry.visibility() == audience)
+                .map(|entry| {
+                    let text = entry.text().trim_end();
+                    let text = text.strip_suffix(INTERNAL_MARKER).unwrap_or(text);
+                    (entry.kind().clone(), text.trim_end().to_string())
+                })
+                .collect::<Vec<_>>();
+            release.set_changes(Changes::from(changes));
+        }
+
+        changelog.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+
+    use super::*;
+
+    #[test]
+    fn test_render_visibility() -> Result<()> {
+        let markdown = "# Changelog\n\n## [Unreleased]\n\n### Fixed\n\n- Crash on empty lists\n- Retry flaky uploads <!-- internal -->\n\n## [0.1.0] - 2024-04-28\n\n### Added\n\n- Initial release\n";
+        let changelog = Changelog::parse(markdown.to_string(), None)?;
+
+        let public = changelog.render_public();
+        assert!(public.contains("### Fixed\n\n- Crash on empty lists\n\n## [0.1.0]"));
+        assert!(!public.contains("Retry flaky uploads"));
+
+        let internal = changelog.render_internal();
+        assert!(internal
+            .contains("### Fixed\n\n- Crash on empty lists\n- Retry flaky uploads\n\n## [0.1.0]"));
+
+        assert!(changelog
+            .file_contents()
+            .contains("- Retry flaky uploads <!-- internal -->\n"));
+        Ok(())
+    }
+}
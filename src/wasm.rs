@@ -0,0 +1,110 @@
+//! WebAssembly bindings for browser and Node usage.
+//!
+//! The crate is built as a `rlib` by default, build the `cdylib` explicitly and generate the
+//! bindings with [wasm-bindgen](https://rustwasm.github.io/wasm-bindgen/reference/cli.html):
+//!
+//! ```sh
+//! cargo rustc --lib --release --crate-type cdylib --target wasm32-unknown-unknown --features wasm
+//! wasm-bindgen --target nodejs --out-dir pkg \
+//!     target/wasm32-unknown-unknown/release/keep_a_changelog.wasm
+//! ```
+//!
+//! ```js
+//! const { parse } = require("keep-a-changelog");
+//!
+//! const changelog = parse(markdown);
+//! changelog.promoteUnreleased("1.0.0", "2024-06-01");
+//! fs.writeFileSync("CHANGELOG.md", changelog.toString());
+//! ```
+use eyre::{Context, OptionExt, Result};
+use wasm_bindgen::prelude::*;
+
+use crate::{CalendarDate, Changelog, Date, Release, ReleaseVersion};
+
+/// Changelog handle exported to JavaScript as `Changelog`.
+#[wasm_bindgen(js_name = Changelog)]
+pub struct JsChangelog(Changelog);
+
+/// Parse changelog from Markdown, throws on invalid changelog.
+#[wasm_bindgen]
+pub fn parse(markdown: String) -> Result<JsChangelog, JsError> {
+    Changelog::parse(markdown, None)
+        .map(JsChangelog)
+        .map_err(to_js_error)
+}
+
+#[wasm_bindgen(js_class = Changelog)]
+impl JsChangelog {
+    /// Render changelog to Markdown.
+    #[wasm_bindgen(js_name = toString)]
+    #[allow(clippy::inherent_to_string)]
+    pub fn to_string(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Add an empty release, `date` is an ISO 8601 date, e.g. "2024-06-01".
+    #[wasm_bindgen(js_name = addRelease)]
+    pub fn add_release(&mut self, version: String, date: Option<String>) -> Result<(), JsError> {
+        self.try_add_release(&version, date.as_deref())
+            .map_err(to_js_error)
+    }
+
+    /// Promote the Unreleased release, `date` is an ISO 8601 date, e.g. "2024-06-01".
+    #[wasm_bindgen(js_name = promoteUnreleased)]
+    pub fn promote_unreleased(&mut self, version: String, date: String) -> Result<(), JsError> {
+        self.try_promote_unreleased(&version, &date)
+            .map_err(to_js_error)
+    }
+}
+
+impl JsChangelog {
+    fn try_add_release(&mut self, version: &str, date: Option<&str>) -> Result<()> {
+        let mut builder = Release::builder();
+        builder.version(parse_version(version)?);
+
+        if let Some(date) = date {
+            builder.date(parse_date(date)?);
+        }
+
+        self.0.add_release(builder.build()?);
+        Ok(())
+    }
+
+    fn try_promote_unreleased(&mut self, version: &str, date: &str) -> Result<()> {
+        self.0
+            .promote_unreleased(parse_version(version)?, parse_date(date)?)?;
+        Ok(())
+    }
+}
+
+fn parse_version(version: &str) -> Result<ReleaseVersion> {
+    ReleaseVersion::parse(version).wrap_err_with(|| format!("Failed to parse version: {version}"))
+}
+
+fn parse_date(date: &str) -> Result<Date> {
+    Date::parse_ymd(date).ok_or_eyre(format!("Failed to parse date: {date}"))
+}
+
+fn to_js_error(e: eyre::Error) -> JsError {
+    JsError::new(&format!("{e:#}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_promote_unreleased() -> Result<()> {
+        let markdown = "# Changelog\n## [Unreleased]\n### Added\n- New feature\n";
+        let mut changelog = JsChangelog(Changelog::parse(markdown.to_string(), None)?);
+
+        changelog.try_promote_unreleased("1.0.0", "2024-06-01")?;
+        changelog.try_add_release("1.0.1", Some("2024-07-01"))?;
+
+        assert!(changelog.to_string().contains("## [1.0.0] - 2024-06-01\n"));
+        assert!(changelog
+            .try_add_release("1.0.2", Some("June 1st"))
+            .is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use eyre::{OptionExt, Result};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{
+    diagnostics::{Diagnostic, Severity},
+    include, Changelog, ChangelogParseOptions,
+};
+
+/// Handle of [`Changelog::watch`], the file is watched until it's dropped.
+pub struct ChangelogWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl Changelog {
+    /// Watch CHANGELOG.md file at `path` and re-parse it on every change
+    ///
+    /// The `callback` is invoked from a background thread with the new changelog, or with the
+    /// [`diagnostics`](Changelog::diagnostics) if the file fails to parse. Saves which don't
+    /// change the file contents are ignored, as well as empty contents of a file being rewritten. The directory of the file is watched, so editors
+    /// replacing the file on save are supported.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use keep_a_changelog::Changelog;
+    ///
+    /// let _watcher = Changelog::watch("CHANGELOG.md", None, |result| match result {
+    ///     Ok(changelog) => println!("{} releases", changelog.releases().len()),
+    ///     Err(diagnostics) => diagnostics.iter().for_each(|d| eprintln!("{}", d.message)),
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn watch<P, F>(
+        path: P,
+        opts: Option<ChangelogParseOptions>,
+        mut callback: F,
+    ) -> Result<ChangelogWatcher>
+    where
+        P: AsRef<Path>,
+        F: FnMut(Result<Changelog, Vec<Diagnostic>>) + Send + 'static,
+    {
+        let path = path.as_ref().to_path_buf();
+        let file_name = path
+            .file_name()
+            .ok_or_eyre("Changelog path has no file name")?
+            .to_os_string();
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+
+        let mut last_source = None;
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            let event = match event {
+                Ok(event) => event,
+                Err(e) => return log::warn!("Failed to watch changelog: {e}"),
+            };
+
+            let changed = !matches!(event.kind, EventKind::Access(_))
+                && event
+                    .paths
+                    .iter()
+                    .any(|p| p.file_name() == Some(&file_name));
+            if !changed {
+                return;
+            }
+
+            // the file could be missing or truncated for a moment while it's rewritten
+            let source = match fs::read_to_string(&path) {
+                Ok(source) if !source.trim().is_empty() => source,
+                _ => return,
+            };
+            if last_source.as_ref() == Some(&source) {
+                return;
+            }
+
+            callback(reload(&path, source.clone(), opts.clone()));
+            last_source = Some(source);
+        })?;
+
+        watcher.watch(&dir, RecursiveMode::NonRecursive)?;
+        Ok(ChangelogWatcher { _watcher: watcher })
+    }
+}
+
+fn reload(
+    path: &Path,
+    source: String,
+    opts: Option<ChangelogParseOptions>,
+) -> Result<Changelog, Vec<Diagnostic>> {
+    let base_dir = opts
+        .as_ref()
+        .filter(|opts| opts.includes)
+        .map(|_| path.parent().unwrap_or(Path::new(".")));
+
+    include::parse(source.clone(), opts, base_dir).map_err(|e| {
+        let mut diagnostics = Changelog::diagnostics(&source);
+        if !diagnostics.iter().any(|d| d.severity == Severity::Error) {
+            diagnostics.insert(
+                0,
+                Diagnostic {
+                    range: 0..0,
+                    severity: Severity::Error,
+                    code: "parse::other",
+                    message: format!("{e:#}"),
+                },
+            );
+        }
+        diagnostics
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, time::Duration};
+
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_watch() -> Result<()> {
+        let dir = Path::new("tests/tmp").join(format!("watch_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("CHANGELOG.md");
+        fs::write(&path, "# Changelog\n")?;
+
+        let (sender, receiver) = mpsc::channel();
+        let watcher = Changelog::watch(&path, None, move |result| {
+            sender.send(result).unwrap();
+        })?;
+        let timeout = Duration::from_secs(5);
+
+        fs::write(
+            &path,
+            "# Changelog\n## [0.1.0] - 2024-04-28\n### Added\n- Initial release\n",
+        )?;
+        let changelog = receiver.recv_timeout(timeout)?.unwrap();
+        assert_eq!(changelog.releases().len(), 1);
+
+        fs::write(&path, "# Changelog\n## [0.1.0] - 2024-13-28\n")?;
+        let diagnostics = receiver.recv_timeout(timeout)?.unwrap_err();
+        assert_eq!(diagnostics[0].code, "parse::invalid_date");
+
+        drop(watcher);
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}